@@ -39,4 +39,7 @@ fn main() {
     build_binding("emscripten");
     build_binding("html5");
     build_binding("console");
+    build_binding("fetch");
+    build_binding("websocket");
+    build_binding("em_math");
 }
@@ -5,7 +5,14 @@
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+// The `em_math` constants are the exact values of the JavaScript `Math` ones, which clippy sees as approximations.
+#![allow(clippy::approx_constant)]
+// `websocket.h` includes `html5.h`, so its bindings redeclare the html5 functions with their own copies of the event structs.
+#![allow(clashing_extern_declarations)]
 
 pub mod console;
+pub mod em_math;
 pub mod emscripten;
+pub mod fetch;
 pub mod html5;
+pub mod websocket;
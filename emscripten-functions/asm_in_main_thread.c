@@ -1,4 +1,5 @@
 #include <emscripten.h>
+#include <emscripten/threading.h>
 
 void asm_in_main_thread(char *script) {
     MAIN_THREAD_EM_ASM("eval(UTF8ToString($0))", script);
@@ -11,3 +12,31 @@ int asm_in_main_thread_int(char *script) {
 double asm_in_main_thread_double(char *script) {
     return MAIN_THREAD_EM_ASM_DOUBLE("eval(UTF8ToString($0))", script);
 }
+
+void call_in_main_thread(void (*func)(void *), void *arg) {
+#ifdef __EMSCRIPTEN_PTHREADS__
+    if (!emscripten_is_main_runtime_thread()) {
+        emscripten_sync_run_in_main_runtime_thread(EM_FUNC_SIG_VI, func, arg);
+        return;
+    }
+#endif
+    // Without threads, or from the main thread itself, there's nothing to proxy.
+    func(arg);
+}
+
+// Tells the type of a compiler setting without calling `emscripten_get_compiler_setting`,
+// which throws in builds without `-sRETAIN_COMPILER_SETTINGS`:
+// 0 if the settings aren't retained, 1 for a number or boolean, 2 for a string or list, 3 for an unknown setting.
+int compiler_setting_kind(const char *name) {
+    return EM_ASM_INT(
+        "var value;"
+        "try {"
+        "  value = getCompilerSetting(UTF8ToString($0));"
+        "} catch (e) {"
+        "  return 0;"
+        "}"
+        "if (typeof value == 'number' || typeof value == 'boolean') return 1;"
+        "if (typeof value == 'string' && value.startsWith('invalid compiler setting: ')) return 3;"
+        "return 2;",
+        name);
+}
@@ -1,12 +1,24 @@
 //! Select functions (with rust-native parameter types) from the emscripten [`console.h`] [header file].
 //!
+//! The console groups and timers, which emscripten has no functions for, are handled by running scripts with [`run_script`].
+//!
 //! [`console.h`]: https://github.com/emscripten-core/emscripten/blob/main/site/source/docs/api_reference/console.h.rst
 //! [header file]: https://github.com/emscripten-core/emscripten/blob/main/system/include/emscripten/console.h
 
-use std::ffi::CString;
+use std::{
+    cell::Cell,
+    fmt::{self, Write},
+    io,
+    os::raw::c_char,
+};
 
 use emscripten_functions_sys::console;
 
+use crate::emscripten::{js_string_literal, run_script, to_cstring_lossy};
+
+#[cfg(feature = "log")]
+pub mod logger;
+
 /// Prints the given string using the [`console.log()`] JS function.
 ///
 /// [`console.log()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
@@ -23,7 +35,7 @@ pub fn log<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_console_log(cstring.as_ptr());
     }
@@ -45,7 +57,7 @@ pub fn warn<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_console_warn(cstring.as_ptr());
     }
@@ -67,7 +79,7 @@ pub fn error<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_console_error(cstring.as_ptr());
     }
@@ -87,7 +99,7 @@ pub fn out<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_out(cstring.as_ptr());
     }
@@ -107,7 +119,7 @@ pub fn err<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_err(cstring.as_ptr());
     }
@@ -127,8 +139,703 @@ pub fn dbg<T>(string: T)
 where
     T: AsRef<str>,
 {
-    let cstring = CString::new(string.as_ref()).unwrap();
+    let cstring = to_cstring_lossy(string.as_ref());
     unsafe {
         console::emscripten_dbg(cstring.as_ptr());
     }
 }
+
+/// Prints the given string along with a backtrace using the emscripten-defined `dbg()` JS function.
+/// It's the quickest way to find out who called a function.
+///
+/// # Arguments
+/// * `string` - The string to print.
+///
+/// # Examples
+/// ```rust
+/// dbg_backtrace("Reloading the level");
+/// dbg_backtrace(format!("Reloading level {}", level));
+/// ```
+pub fn dbg_backtrace<T>(string: T)
+where
+    T: AsRef<str>,
+{
+    let cstring = to_cstring_lossy(string.as_ref());
+    unsafe {
+        console::emscripten_dbg_backtrace(cstring.as_ptr());
+    }
+}
+
+// The number of console groups opened by `group` and `group_collapsed`, and not closed yet.
+thread_local! {
+    static GROUP_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A console group opened with [`group`] or [`group_collapsed`], which gets closed with [`console.groupEnd()`] when dropped.
+///
+/// The groups are closed in the right order even if the guards aren't dropped in reverse order:
+/// dropping a guard also closes the groups opened after it, and their guards then do nothing.
+///
+/// [`console.groupEnd()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/groupEnd_static
+#[must_use = "the group is closed when the guard is dropped"]
+#[derive(Debug)]
+pub struct GroupGuard {
+    depth: usize,
+}
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        let open = GROUP_DEPTH.with(|depth| depth.replace(self.depth.min(depth.get())));
+        for _ in self.depth..open {
+            run_script("console.groupEnd()");
+        }
+    }
+}
+
+// Opens a group with the given `console` function, returning its guard.
+fn open_group(function: &str, label: &str) -> GroupGuard {
+    run_script(format!(
+        "console.{}({})",
+        function,
+        js_string_literal(label)
+    ));
+
+    let depth = GROUP_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+    GroupGuard { depth }
+}
+
+/// Opens a console group with the given label, using the [`console.group()`] JS function,
+/// so that the messages printed until the returned guard is dropped are indented under it.
+///
+/// [`console.group()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/group_static
+///
+/// # Arguments
+/// * `label` - The label of the group.
+///
+/// # Examples
+/// ```rust
+/// for enemy in &enemies {
+///     let _group = group(format!("Enemy {}", enemy.id));
+///     log(format!("Position: {:?}", enemy.position));
+///     log(format!("Health: {}", enemy.health));
+/// }
+/// ```
+pub fn group<T>(label: T) -> GroupGuard
+where
+    T: AsRef<str>,
+{
+    open_group("group", label.as_ref())
+}
+
+/// Opens a console group with the given label, collapsed at first, using the [`console.groupCollapsed()`] JS function.
+/// Otherwise, it's like [`group`].
+///
+/// [`console.groupCollapsed()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/groupCollapsed_static
+///
+/// # Arguments
+/// * `label` - The label of the group.
+///
+/// # Examples
+/// ```rust
+/// let _group = group_collapsed("Frame diagnostics");
+/// log(format!("Draw calls: {}", draw_calls));
+/// ```
+pub fn group_collapsed<T>(label: T) -> GroupGuard
+where
+    T: AsRef<str>,
+{
+    open_group("groupCollapsed", label.as_ref())
+}
+
+/// Runs the given function inside a console group with the given label, opened with [`group`],
+/// returning the function's result. The group gets closed even if the function panics.
+///
+/// # Arguments
+/// * `label` - The label of the group.
+/// * `func` - The function to run inside the group.
+///
+/// # Examples
+/// ```rust
+/// let loaded = with_group("Loading assets", || {
+///     log("Textures");
+///     log("Sounds");
+///     true
+/// });
+/// ```
+pub fn with_group<T, F, R>(label: T, func: F) -> R
+where
+    T: AsRef<str>,
+    F: FnOnce() -> R,
+{
+    let _group = group(label);
+    func()
+}
+
+/// A console timer started with [`time`], which prints the time elapsed since it started
+/// with [`console.timeEnd()`] when dropped.
+///
+/// [`console.timeEnd()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/timeEnd_static
+#[must_use = "the timer ends right away if the guard isn't kept"]
+#[derive(Debug)]
+pub struct ConsoleTimer {
+    // The label, as a JavaScript string literal.
+    label: String,
+}
+impl ConsoleTimer {
+    /// Prints the time elapsed since the timer started, followed by the given text, using the [`console.timeLog()`] JS function.
+    /// The timer keeps running.
+    ///
+    /// [`console.timeLog()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/timeLog_static
+    ///
+    /// # Arguments
+    /// * `extra` - The text to print after the elapsed time.
+    pub fn log<T>(&self, extra: T)
+    where
+        T: AsRef<str>,
+    {
+        run_script(format!(
+            "console.timeLog({}, {})",
+            self.label,
+            js_string_literal(extra.as_ref())
+        ));
+    }
+}
+impl Drop for ConsoleTimer {
+    fn drop(&mut self) {
+        run_script(format!("console.timeEnd({})", self.label));
+    }
+}
+
+/// Starts a console timer with the given label, using the [`console.time()`] JS function.
+/// The elapsed time is printed when the returned timer is dropped, and in between with [`ConsoleTimer::log`].
+///
+/// The browser keeps one timer per label, so the labels of the timers running at the same time should differ.
+///
+/// [`console.time()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/time_static
+///
+/// # Arguments
+/// * `label` - The label of the timer.
+///
+/// # Examples
+/// ```rust
+/// let timer = time("Loading");
+/// load_textures();
+/// timer.log("textures loaded");
+/// load_sounds();
+/// drop(timer);
+/// ```
+pub fn time<T>(label: T) -> ConsoleTimer
+where
+    T: AsRef<str>,
+{
+    let label = js_string_literal(label.as_ref());
+    run_script(format!("console.time({})", label));
+
+    ConsoleTimer { label }
+}
+
+// The values whose JSON is longer than this are printed as text, cut short, instead of as objects.
+#[cfg(feature = "serde")]
+const MAX_VALUE_JSON_LEN: usize = 1024 * 1024;
+
+// Prints the given label and value with the given `console` function, the value being turned into a JavaScript object through JSON.
+#[cfg(feature = "serde")]
+fn print_value<V>(function: &str, label: &str, value: &V)
+where
+    V: serde::Serialize + ?Sized,
+{
+    let value = match serde_json::to_string(value) {
+        Ok(json) if json.len() <= MAX_VALUE_JSON_LEN => {
+            format!("JSON.parse({})", js_string_literal(&json))
+        }
+        Ok(json) => {
+            let mut end = MAX_VALUE_JSON_LEN;
+            while !json.is_char_boundary(end) {
+                end -= 1;
+            }
+            js_string_literal(&format!(
+                "{}... ({} bytes in total)",
+                &json[..end],
+                json.len()
+            ))
+        }
+        Err(err) => js_string_literal(&format!("<the value couldn't be serialized: {}>", err)),
+    };
+
+    run_script(format!(
+        "console.{}({}, {})",
+        function,
+        js_string_literal(label),
+        value
+    ));
+}
+
+/// Prints the given label and value using the [`console.log()`] JS function, with the value as an object that can be expanded in the console,
+/// instead of flat text.
+///
+/// The value is serialized to JSON, which is parsed back in JavaScript, so it looks like its JSON representation, e.g. a struct is an object.
+/// Values whose JSON is longer than 1 MiB are printed as text, cut short, and the ones that can't be serialized are printed as the error.
+///
+/// [`console.log()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
+///
+/// # Arguments
+/// * `label` - The text printed before the value.
+/// * `value` - The value to print.
+///
+/// # Examples
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct Player {
+///     name: String,
+///     position: (f32, f32),
+///     inventory: Vec<String>,
+/// }
+///
+/// log_value("Player:", &player);
+/// ```
+#[cfg(feature = "serde")]
+pub fn log_value<T, V>(label: T, value: &V)
+where
+    T: AsRef<str>,
+    V: serde::Serialize + ?Sized,
+{
+    print_value("log", label.as_ref(), value);
+}
+
+/// Prints the given label and value using the [`console.warn()`] JS function, with the value as an object, like [`log_value`].
+///
+/// [`console.warn()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/warn
+///
+/// # Arguments
+/// * `label` - The text printed before the value.
+/// * `value` - The value to print.
+///
+/// # Examples
+/// ```rust
+/// warn_value("Unexpected config:", &config);
+/// ```
+#[cfg(feature = "serde")]
+pub fn warn_value<T, V>(label: T, value: &V)
+where
+    T: AsRef<str>,
+    V: serde::Serialize + ?Sized,
+{
+    print_value("warn", label.as_ref(), value);
+}
+
+/// Prints the given label and value using the [`console.error()`] JS function, with the value as an object, like [`log_value`].
+///
+/// [`console.error()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/error
+///
+/// # Arguments
+/// * `label` - The text printed before the value.
+/// * `value` - The value to print.
+///
+/// # Examples
+/// ```rust
+/// error_value("The save is invalid:", &save);
+/// ```
+#[cfg(feature = "serde")]
+pub fn error_value<T, V>(label: T, value: &V)
+where
+    T: AsRef<str>,
+    V: serde::Serialize + ?Sized,
+{
+    print_value("error", label.as_ref(), value);
+}
+
+// The messages formatted by the macros that are shorter than this, NUL terminator included, don't allocate.
+const STACK_BUFFER_LEN: usize = 256;
+
+// Collects a formatted message into a buffer on the stack, moving it to the heap only if it doesn't fit.
+// The NUL characters are removed, like in `to_cstring_lossy`.
+struct MessageWriter {
+    buffer: [u8; STACK_BUFFER_LEN],
+    len: usize,
+    heap: Option<Vec<u8>>,
+}
+impl MessageWriter {
+    fn push_bytes(&mut self, bytes: &[u8]) {
+        if let Some(heap) = &mut self.heap {
+            heap.extend_from_slice(bytes);
+        } else if self.len + bytes.len() < STACK_BUFFER_LEN {
+            self.buffer[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+            self.len += bytes.len();
+        } else {
+            let mut heap = Vec::with_capacity((self.len + bytes.len()) * 2);
+            heap.extend_from_slice(&self.buffer[..self.len]);
+            heap.extend_from_slice(bytes);
+            self.heap = Some(heap);
+        }
+    }
+}
+impl Write for MessageWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for part in s.split('\0') {
+            self.push_bytes(part.as_bytes());
+        }
+        Ok(())
+    }
+}
+
+// Formats the given message, and gives it NUL-terminated to the given `console.h` function.
+fn print_fmt(print: unsafe extern "C" fn(*const c_char), args: fmt::Arguments) {
+    let mut writer = MessageWriter {
+        buffer: [0; STACK_BUFFER_LEN],
+        len: 0,
+        heap: None,
+    };
+    // A `Display` implementation returning an error only cuts the message short, like with `println!`.
+    let _ = writer.write_fmt(args);
+
+    match &mut writer.heap {
+        Some(heap) => {
+            heap.push(0);
+            unsafe { print(heap.as_ptr() as *const c_char) };
+        }
+        None => {
+            // There's always room for the terminator, as `push_bytes` keeps the messages shorter than the buffer.
+            writer.buffer[writer.len] = 0;
+            unsafe { print(writer.buffer.as_ptr() as *const c_char) };
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn _log(args: fmt::Arguments) {
+    print_fmt(console::emscripten_console_log, args);
+}
+
+#[doc(hidden)]
+pub fn _warn(args: fmt::Arguments) {
+    print_fmt(console::emscripten_console_warn, args);
+}
+
+#[doc(hidden)]
+pub fn _error(args: fmt::Arguments) {
+    print_fmt(console::emscripten_console_error, args);
+}
+
+#[doc(hidden)]
+pub fn _dbg(args: fmt::Arguments) {
+    print_fmt(console::emscripten_dbg, args);
+}
+
+#[doc(hidden)]
+pub fn _dbg_backtrace(args: fmt::Arguments) {
+    print_fmt(console::emscripten_dbg_backtrace, args);
+}
+
+/// Prints the given message using the [`console.log()`] JS function, like [`log`](crate::console::log),
+/// with the formatting syntax of [`println!`].
+///
+/// Unlike `log(format!(...))`, messages shorter than 256 bytes are formatted on the stack, without allocating.
+///
+/// [`console.log()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
+///
+/// # Examples
+/// ```rust
+/// console_log!("Hello, world!");
+/// console_log!("0.1 + 0.2 = {}", 0.1 + 0.2);
+/// ```
+#[macro_export]
+macro_rules! console_log {
+    () => {
+        $crate::console::_log(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_log(format_args!($($arg)*))
+    };
+}
+
+/// Prints the given message using the [`console.warn()`] JS function, like [`warn`](crate::console::warn),
+/// with the formatting syntax of [`println!`].
+///
+/// Unlike `warn(format!(...))`, messages shorter than 256 bytes are formatted on the stack, without allocating.
+///
+/// [`console.warn()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/warn
+///
+/// # Examples
+/// ```rust
+/// console_warn!("Low memory");
+/// console_warn!("Only {} MB left", free_mb);
+/// ```
+#[macro_export]
+macro_rules! console_warn {
+    () => {
+        $crate::console::_warn(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_warn(format_args!($($arg)*))
+    };
+}
+
+/// Prints the given message using the [`console.error()`] JS function, like [`error`](crate::console::error),
+/// with the formatting syntax of [`println!`].
+///
+/// Unlike `error(format!(...))`, messages shorter than 256 bytes are formatted on the stack, without allocating.
+///
+/// [`console.error()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/error
+///
+/// # Examples
+/// ```rust
+/// console_error!("Couldn't load the level");
+/// console_error!("Couldn't load {}: {}", path, err);
+/// ```
+#[macro_export]
+macro_rules! console_error {
+    () => {
+        $crate::console::_error(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_error(format_args!($($arg)*))
+    };
+}
+
+/// Prints the given message using the emscripten-defined `dbg()` JS function, like [`dbg`](crate::console::dbg),
+/// with the formatting syntax of [`println!`].
+///
+/// Unlike `dbg(format!(...))`, messages shorter than 256 bytes are formatted on the stack, without allocating.
+///
+/// # Examples
+/// ```rust
+/// console_dbg!("Entering the boss fight");
+/// console_dbg!("Player at {:?}", position);
+/// ```
+#[macro_export]
+macro_rules! console_dbg {
+    () => {
+        $crate::console::_dbg(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_dbg(format_args!($($arg)*))
+    };
+}
+
+/// Prints the given message along with a backtrace, like [`dbg_backtrace`](crate::console::dbg_backtrace),
+/// with the formatting syntax of [`println!`].
+///
+/// Unlike `dbg_backtrace(format!(...))`, messages shorter than 256 bytes are formatted on the stack, without allocating.
+///
+/// # Examples
+/// ```rust
+/// console_trace!();
+/// console_trace!("Spawning enemy {}", id);
+/// ```
+#[macro_export]
+macro_rules! console_trace {
+    () => {
+        $crate::console::_dbg_backtrace(format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        $crate::console::_dbg_backtrace(format_args!($($arg)*))
+    };
+}
+
+// The longest line kept by `OutWriter` and `ErrWriter` before printing it anyway, for output that never ends a line.
+const MAX_LINE_LEN: usize = 16 * 1024;
+
+// The bytes written to `OutWriter` or `ErrWriter` since the last printed line.
+#[derive(Debug)]
+struct LineBuffer {
+    buffer: Vec<u8>,
+}
+impl LineBuffer {
+    // Prints the given bytes as a line, with the invalid UTF-8 sequences replaced with `U+FFFD`.
+    fn print_line(print: unsafe extern "C" fn(*const c_char), line: &[u8]) {
+        print_fmt(print, format_args!("{}", String::from_utf8_lossy(line)));
+    }
+
+    fn write(&mut self, print: unsafe extern "C" fn(*const c_char), buf: &[u8]) {
+        self.buffer.extend_from_slice(buf);
+
+        let mut start = 0;
+        while let Some(newline) = self.buffer[start..].iter().position(|&b| b == b'\n') {
+            Self::print_line(print, &self.buffer[start..start + newline]);
+            start += newline + 1;
+        }
+        self.buffer.drain(..start);
+
+        if self.buffer.len() > MAX_LINE_LEN {
+            self.print_complete(print);
+        }
+    }
+
+    // Prints the buffered bytes, except for a UTF-8 sequence cut at the end, which is kept for the next write to complete.
+    fn print_complete(&mut self, print: unsafe extern "C" fn(*const c_char)) {
+        let len = match std::str::from_utf8(&self.buffer) {
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            _ => self.buffer.len(),
+        };
+        if len > 0 {
+            Self::print_line(print, &self.buffer[..len]);
+            self.buffer.drain(..len);
+        }
+    }
+}
+
+/// A [`std::io::Write`] implementation printing to the browser console with the emscripten-defined `out()` JS function, like [`out`].
+///
+/// The bytes are printed one line at a time, once the line ends. A UTF-8 sequence split between two writes is put back together.
+/// [`flush`](io::Write::flush) and dropping the writer print the unfinished line too,
+/// and so does a line longer than 16 KiB, so that an output without newlines still shows up.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Write;
+///
+/// let mut writer = OutWriter::new();
+/// writeln!(writer, "{:<10} {:>5}", "Level", "Score").unwrap();
+/// for (level, score) in &scores {
+///     writeln!(writer, "{:<10} {:>5}", level, score).unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OutWriter {
+    lines: LineBuffer,
+}
+impl Default for OutWriter {
+    fn default() -> Self {
+        OutWriter::new()
+    }
+}
+impl OutWriter {
+    /// Creates a writer with nothing buffered.
+    pub fn new() -> Self {
+        OutWriter {
+            lines: LineBuffer { buffer: Vec::new() },
+        }
+    }
+}
+impl io::Write for OutWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lines.write(console::emscripten_out, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lines.print_complete(console::emscripten_out);
+        Ok(())
+    }
+}
+impl Drop for OutWriter {
+    fn drop(&mut self) {
+        if !self.lines.buffer.is_empty() {
+            LineBuffer::print_line(console::emscripten_out, &self.lines.buffer);
+        }
+    }
+}
+
+/// A [`std::io::Write`] implementation printing to the browser console with the emscripten-defined `err()` JS function, like [`err`].
+///
+/// It prints the lines like [`OutWriter`].
+///
+/// # Examples
+/// ```rust
+/// use std::io::Write;
+///
+/// let mut writer = ErrWriter::new();
+/// report.write_to(&mut writer).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ErrWriter {
+    lines: LineBuffer,
+}
+impl Default for ErrWriter {
+    fn default() -> Self {
+        ErrWriter::new()
+    }
+}
+impl ErrWriter {
+    /// Creates a writer with nothing buffered.
+    pub fn new() -> Self {
+        ErrWriter {
+            lines: LineBuffer { buffer: Vec::new() },
+        }
+    }
+}
+impl io::Write for ErrWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.lines.write(console::emscripten_err, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.lines.print_complete(console::emscripten_err);
+        Ok(())
+    }
+}
+impl Drop for ErrWriter {
+    fn drop(&mut self) {
+        if !self.lines.buffer.is_empty() {
+            LineBuffer::print_line(console::emscripten_err, &self.lines.buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    thread_local! {
+        static PRINTED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // Records the lines printed by a `LineBuffer`, in place of `out()` or `err()`.
+    unsafe extern "C" fn record(line: *const c_char) {
+        let line = unsafe { std::ffi::CStr::from_ptr(line) };
+        PRINTED.with(|printed| {
+            printed
+                .borrow_mut()
+                .push(line.to_string_lossy().into_owned())
+        });
+    }
+
+    fn printed() -> Vec<String> {
+        PRINTED.with(|printed| printed.take())
+    }
+
+    #[test]
+    fn line_buffer_prints_whole_lines() {
+        let mut lines = LineBuffer { buffer: Vec::new() };
+
+        lines.write(record, b"Level ");
+        assert!(printed().is_empty());
+        lines.write(record, b"3\nScore 42\nBo");
+        assert_eq!(printed(), ["Level 3", "Score 42"]);
+
+        lines.print_complete(record);
+        assert_eq!(printed(), ["Bo"]);
+        lines.print_complete(record);
+        assert!(printed().is_empty());
+    }
+
+    #[test]
+    fn line_buffer_puts_split_utf8_back_together() {
+        let mut lines = LineBuffer { buffer: Vec::new() };
+        let heart = "♥".as_bytes();
+
+        lines.write(record, &heart[..1]);
+        // A flush in the middle of the sequence keeps it for the next write.
+        lines.print_complete(record);
+        assert!(printed().is_empty());
+        lines.write(record, &heart[1..]);
+        lines.write(record, b"\n");
+        assert_eq!(printed(), ["♥"]);
+
+        lines.write(record, b"bad \xff byte\n");
+        assert_eq!(printed(), ["bad \u{FFFD} byte"]);
+    }
+
+    #[test]
+    fn line_buffer_prints_overlong_lines() {
+        let mut lines = LineBuffer { buffer: Vec::new() };
+
+        lines.write(record, &[b'a'; MAX_LINE_LEN + 1]);
+        let printed = printed();
+        assert_eq!(printed.len(), 1);
+        assert_eq!(printed[0].len(), MAX_LINE_LEN + 1);
+        assert!(lines.buffer.is_empty());
+    }
+}
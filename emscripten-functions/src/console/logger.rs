@@ -0,0 +1,137 @@
+//! A backend for the [`log`] crate, printing the log records to the browser console, with the matching console levels.
+//!
+//! This way, the output of the dependencies logging through the `log` facade shows up as proper console errors and warnings,
+//! instead of going to the standard output.
+
+use std::cmp::Reverse;
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use emscripten_functions_sys::console;
+
+use super::print_fmt;
+
+/// A [`Log`] implementation printing to the browser console.
+///
+/// The records are printed with their level and module path, like `WARN my_game::assets: Missing texture`, using:
+/// * [`console.error()`] for [`Level::Error`],
+/// * [`console.warn()`] for [`Level::Warn`],
+/// * [`console.log()`] for [`Level::Info`],
+/// * the emscripten-defined `dbg()` for [`Level::Debug`] and [`Level::Trace`].
+///
+/// [`console.error()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/error
+/// [`console.warn()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/warn
+/// [`console.log()`]: https://developer.mozilla.org/en-US/docs/Web/API/console/log
+///
+/// # Examples
+/// ```rust
+/// ConsoleLogger::new()
+///     .with_level(LevelFilter::Info)
+///     .with_module_level("my_game::physics", LevelFilter::Trace)
+///     .with_module_level("wgpu_core", LevelFilter::Warn)
+///     .init()
+///     .unwrap();
+///
+/// log::info!("Starting up");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConsoleLogger {
+    level: LevelFilter,
+    // Sorted by decreasing length, so that the first match is the most specific one.
+    module_levels: Vec<(String, LevelFilter)>,
+}
+impl Default for ConsoleLogger {
+    fn default() -> Self {
+        ConsoleLogger::new()
+    }
+}
+impl ConsoleLogger {
+    /// Creates a logger printing all the records, of all the levels.
+    pub fn new() -> Self {
+        ConsoleLogger {
+            level: LevelFilter::Trace,
+            module_levels: Vec::new(),
+        }
+    }
+
+    /// Sets the most verbose level printed for the modules without a level of their own.
+    ///
+    /// # Arguments
+    /// * `level` - The most verbose level printed.
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the most verbose level printed for the given module and its submodules.
+    /// The most specific module given takes precedence.
+    ///
+    /// # Arguments
+    /// * `module` - The module path, like `my_game::physics`, matched against the target of the records.
+    /// * `level` - The most verbose level printed for the module.
+    pub fn with_module_level<T>(mut self, module: T, level: LevelFilter) -> Self
+    where
+        T: Into<String>,
+    {
+        let module = module.into();
+        self.module_levels.retain(|(other, _)| *other != module);
+        self.module_levels.push((module, level));
+        self.module_levels
+            .sort_by_key(|(module, _)| Reverse(module.len()));
+        self
+    }
+
+    /// Sets this logger as the logger of the `log` crate, with [`log::set_logger`],
+    /// and sets the maximum level of the `log` crate to the most verbose one of this logger.
+    ///
+    /// Returns an error if a logger was already set.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let max_level = self
+            .module_levels
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Ord::max);
+
+        // The logger has to live for the rest of the program anyway.
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    // The most verbose level printed for the given target.
+    fn level_for(&self, target: &str) -> LevelFilter {
+        self.module_levels
+            .iter()
+            .find(|(module, _)| {
+                target
+                    .strip_prefix(module.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let print = match record.level() {
+            Level::Error => console::emscripten_console_error,
+            Level::Warn => console::emscripten_console_warn,
+            Level::Info => console::emscripten_console_log,
+            Level::Debug | Level::Trace => console::emscripten_dbg,
+        };
+        let module = record.module_path().unwrap_or(record.target());
+        print_fmt(
+            print,
+            format_args!("{} {}: {}", record.level(), module, record.args()),
+        );
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,347 @@
+//! Functions (with rust-native parameter and return value types) computing with the JavaScript [`Math`] object,
+//! from the emscripten [`em_math.h`] header file. They require the `em_math` feature.
+//!
+//! Each call crosses over to JavaScript, so they're slower than the methods of `f64`,
+//! but they don't pull the implementations of the math library into the wasm binary,
+//! which makes them a good fit for builds optimized for size.
+//!
+//! The header leaves out the `Math` operations having their own WebAssembly instructions, like `abs`, `floor` or `min`,
+//! so there are no functions for them here; the methods of `f64` compile to those instructions.
+//!
+//! [`Math`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math
+//! [`em_math.h`]: https://github.com/emscripten-core/emscripten/blob/main/system/include/emscripten/em_math.h
+
+use emscripten_functions_sys::em_math;
+
+/// Euler's number, the base of the natural logarithms.
+pub const E: f64 = em_math::EM_MATH_E;
+/// The natural logarithm of 2.
+pub const LN2: f64 = em_math::EM_MATH_LN2;
+/// The natural logarithm of 10.
+pub const LN10: f64 = em_math::EM_MATH_LN10;
+/// The base 2 logarithm of `e`.
+pub const LOG2E: f64 = em_math::EM_MATH_LOG2E;
+/// The base 10 logarithm of `e`.
+pub const LOG10E: f64 = em_math::EM_MATH_LOG10E;
+/// The ratio of a circle's circumference to its diameter.
+pub const PI: f64 = em_math::EM_MATH_PI;
+/// The square root of 1/2.
+pub const SQRT1_2: f64 = em_math::EM_MATH_SQRT1_2;
+/// The square root of 2.
+pub const SQRT2: f64 = em_math::EM_MATH_SQRT2;
+
+/// Returns the arccosine of the given number, using the emscripten-defined `emscripten_math_acos`, which calls [`Math.acos`].
+///
+/// [`Math.acos`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/acos
+pub fn acos(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_acos(x) }
+}
+
+/// Returns the hyperbolic arccosine of the given number, using the emscripten-defined `emscripten_math_acosh`, which calls [`Math.acosh`].
+///
+/// [`Math.acosh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/acosh
+pub fn acosh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_acosh(x) }
+}
+
+/// Returns the arcsine of the given number, using the emscripten-defined `emscripten_math_asin`, which calls [`Math.asin`].
+///
+/// [`Math.asin`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/asin
+pub fn asin(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_asin(x) }
+}
+
+/// Returns the hyperbolic arcsine of the given number, using the emscripten-defined `emscripten_math_asinh`, which calls [`Math.asinh`].
+///
+/// [`Math.asinh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/asinh
+pub fn asinh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_asinh(x) }
+}
+
+/// Returns the arctangent of the given number, using the emscripten-defined `emscripten_math_atan`, which calls [`Math.atan`].
+///
+/// [`Math.atan`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atan
+pub fn atan(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_atan(x) }
+}
+
+/// Returns the angle between the positive x axis and the ray to the point `(x, y)`, in radians,
+/// using the emscripten-defined `emscripten_math_atan2`, which calls [`Math.atan2`].
+/// The arguments are in the same order as in JavaScript and C, like [`f64::atan2`].
+///
+/// [`Math.atan2`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atan2
+///
+/// # Arguments
+/// * `y` - The y coordinate of the point.
+/// * `x` - The x coordinate of the point.
+pub fn atan2(y: f64, x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_atan2(y, x) }
+}
+
+/// Returns the hyperbolic arctangent of the given number, using the emscripten-defined `emscripten_math_atanh`, which calls [`Math.atanh`].
+///
+/// [`Math.atanh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/atanh
+pub fn atanh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_atanh(x) }
+}
+
+/// Returns the cube root of the given number, using the emscripten-defined `emscripten_math_cbrt`, which calls [`Math.cbrt`].
+///
+/// [`Math.cbrt`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cbrt
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(em_math::cbrt(27.0), 27.0_f64.cbrt());
+/// ```
+pub fn cbrt(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_cbrt(x) }
+}
+
+/// Returns the cosine of the given number, using the emscripten-defined `emscripten_math_cos`, which calls [`Math.cos`].
+///
+/// [`Math.cos`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cos
+pub fn cos(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_cos(x) }
+}
+
+/// Returns the hyperbolic cosine of the given number, using the emscripten-defined `emscripten_math_cosh`, which calls [`Math.cosh`].
+///
+/// [`Math.cosh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/cosh
+pub fn cosh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_cosh(x) }
+}
+
+/// Returns `e` raised to the power `x`, using the emscripten-defined `emscripten_math_exp`, which calls [`Math.exp`].
+///
+/// [`Math.exp`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/exp
+pub fn exp(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_exp(x) }
+}
+
+/// Returns `e` raised to the power `x`, minus 1, using the emscripten-defined `emscripten_math_expm1`, which calls [`Math.expm1`].
+/// It stays precise when `x` is close to 0, unlike `exp(x) - 1.0`.
+///
+/// [`Math.expm1`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/expm1
+pub fn expm1(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_expm1(x) }
+}
+
+/// Returns the remainder of dividing `x` by `y`, with the sign of `x`,
+/// using the emscripten-defined `emscripten_math_fmod`, which uses the JavaScript `%` operator.
+///
+/// # Arguments
+/// * `x` - The dividend.
+/// * `y` - The divisor.
+pub fn fmod(x: f64, y: f64) -> f64 {
+    unsafe { em_math::emscripten_math_fmod(x, y) }
+}
+
+/// Returns the length of the hypotenuse of a right triangle with the given legs, i.e. `sqrt(x * x + y * y)` without overflowing,
+/// using the emscripten-defined `emscripten_math_hypot`, which calls [`Math.hypot`].
+///
+/// [`Math.hypot`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/hypot
+///
+/// # Arguments
+/// * `x` - The length of a leg.
+/// * `y` - The length of the other leg.
+pub fn hypot(x: f64, y: f64) -> f64 {
+    unsafe { em_math::emscripten_math_hypot(2, x, y) }
+}
+
+/// Returns the natural logarithm of the given number, using the emscripten-defined `emscripten_math_log`, which calls [`Math.log`].
+///
+/// [`Math.log`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log
+pub fn log(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_log(x) }
+}
+
+/// Returns the natural logarithm of `1 + x`, using the emscripten-defined `emscripten_math_log1p`, which calls [`Math.log1p`].
+/// It stays precise when `x` is close to 0, unlike `log(1.0 + x)`.
+///
+/// [`Math.log1p`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log1p
+pub fn log1p(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_log1p(x) }
+}
+
+/// Returns the base 10 logarithm of the given number, using the emscripten-defined `emscripten_math_log10`, which calls [`Math.log10`].
+///
+/// [`Math.log10`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log10
+pub fn log10(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_log10(x) }
+}
+
+/// Returns the base 2 logarithm of the given number, using the emscripten-defined `emscripten_math_log2`, which calls [`Math.log2`].
+///
+/// [`Math.log2`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/log2
+pub fn log2(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_log2(x) }
+}
+
+/// Returns `x` raised to the power `y`, using the emscripten-defined `emscripten_math_pow`, which calls [`Math.pow`].
+///
+/// [`Math.pow`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/pow
+///
+/// # Arguments
+/// * `x` - The base.
+/// * `y` - The exponent.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(em_math::pow(2.0, 10.0), 1024.0);
+/// ```
+pub fn pow(x: f64, y: f64) -> f64 {
+    unsafe { em_math::emscripten_math_pow(x, y) }
+}
+
+/// Returns a pseudo-random number in the `[0, 1)` range, using the emscripten-defined `emscripten_math_random`, which calls [`Math.random`].
+/// Unlike [`random`](crate::emscripten::random), it has the full precision of an `f64`.
+///
+/// [`Math.random`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
+pub fn random() -> f64 {
+    unsafe { em_math::emscripten_math_random() }
+}
+
+/// Returns the integer nearest to the given number, using the emscripten-defined `emscripten_math_round`, which calls [`Math.round`].
+/// Halves are rounded towards positive infinity, e.g. `-2.5` becomes `-2.0`, while [`f64::round`] rounds them away from zero.
+///
+/// [`Math.round`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/round
+pub fn round(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_round(x) }
+}
+
+/// Returns the sign of the given number as `1.0` or `-1.0`, using the emscripten-defined `emscripten_math_sign`, which calls [`Math.sign`].
+/// Zeros and NaN are returned as they are, while [`f64::signum`] gives `1.0` or `-1.0` for zeros too.
+///
+/// [`Math.sign`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sign
+pub fn sign(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_sign(x) }
+}
+
+/// Returns the sine of the given number, using the emscripten-defined `emscripten_math_sin`, which calls [`Math.sin`].
+///
+/// [`Math.sin`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sin
+pub fn sin(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_sin(x) }
+}
+
+/// Returns the hyperbolic sine of the given number, using the emscripten-defined `emscripten_math_sinh`, which calls [`Math.sinh`].
+///
+/// [`Math.sinh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sinh
+pub fn sinh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_sinh(x) }
+}
+
+/// Returns the square root of the given number, using the emscripten-defined `emscripten_math_sqrt`, which calls [`Math.sqrt`].
+///
+/// [`Math.sqrt`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/sqrt
+pub fn sqrt(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_sqrt(x) }
+}
+
+/// Returns the tangent of the given number, using the emscripten-defined `emscripten_math_tan`, which calls [`Math.tan`].
+///
+/// [`Math.tan`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/tan
+pub fn tan(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_tan(x) }
+}
+
+/// Returns the hyperbolic tangent of the given number, using the emscripten-defined `emscripten_math_tanh`, which calls [`Math.tanh`].
+///
+/// [`Math.tanh`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/tanh
+pub fn tanh(x: f64) -> f64 {
+    unsafe { em_math::emscripten_math_tanh(x) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUTS: [f64; 9] = [-7.5, -2.5, -1.0, -0.25, 0.0, 1e-10, 0.5, 3.0, 42.125];
+
+    // The JavaScript results can differ from the Rust ones in the last bits.
+    fn assert_close(js: f64, rust: f64, what: &str, x: f64) {
+        let close = (js.is_nan() && rust.is_nan())
+            || js == rust
+            || (js - rust).abs() <= 1e-13 * js.abs().max(rust.abs());
+        assert!(
+            close,
+            "{}({}): {} in JavaScript, {} in Rust",
+            what, x, js, rust
+        );
+    }
+
+    // The name of a function, its JavaScript version and its Rust version.
+    type UnaryCase = (&'static str, fn(f64) -> f64, fn(f64) -> f64);
+
+    #[test]
+    fn unary_functions_match_f64() {
+        let functions: [UnaryCase; 20] = [
+            ("acos", acos, f64::acos),
+            ("acosh", acosh, f64::acosh),
+            ("asin", asin, f64::asin),
+            ("asinh", asinh, f64::asinh),
+            ("atan", atan, f64::atan),
+            ("atanh", atanh, f64::atanh),
+            ("cbrt", cbrt, f64::cbrt),
+            ("cos", cos, f64::cos),
+            ("cosh", cosh, f64::cosh),
+            ("exp", exp, f64::exp),
+            ("expm1", expm1, f64::exp_m1),
+            ("log", log, f64::ln),
+            ("log1p", log1p, f64::ln_1p),
+            ("log10", log10, f64::log10),
+            ("log2", log2, f64::log2),
+            ("sin", sin, f64::sin),
+            ("sinh", sinh, f64::sinh),
+            ("sqrt", sqrt, f64::sqrt),
+            ("tan", tan, f64::tan),
+            ("tanh", tanh, f64::tanh),
+        ];
+
+        for (name, js, rust) in functions {
+            for x in INPUTS {
+                assert_close(js(x), rust(x), name, x);
+            }
+        }
+    }
+
+    #[test]
+    fn binary_functions_match_f64() {
+        for x in INPUTS {
+            for y in INPUTS {
+                assert_close(atan2(y, x), y.atan2(x), "atan2", x);
+                assert_close(fmod(x, y), x % y, "fmod", x);
+                assert_close(hypot(x, y), x.hypot(y), "hypot", x);
+                assert_close(pow(x, y), x.powf(y), "pow", x);
+            }
+        }
+    }
+
+    #[test]
+    fn round_and_sign_differ_from_f64_where_documented() {
+        assert_eq!(round(-2.5), -2.0);
+        assert_eq!((-2.5_f64).round(), -3.0);
+        assert_eq!(round(2.5), 3.0);
+        assert_eq!(round(-7.4), -7.0);
+
+        assert_eq!(sign(-3.0), -1.0);
+        assert_eq!(sign(42.125), 1.0);
+        assert!(sign(0.0) == 0.0 && sign(0.0).is_sign_positive());
+        assert!(sign(-0.0) == 0.0 && sign(-0.0).is_sign_negative());
+        assert!(sign(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn constants_match_f64() {
+        use std::f64::consts;
+
+        assert_eq!(E, consts::E);
+        assert_eq!(LN2, consts::LN_2);
+        assert_eq!(LN10, consts::LN_10);
+        assert_eq!(LOG2E, consts::LOG2_E);
+        assert_eq!(LOG10E, consts::LOG10_E);
+        assert_eq!(PI, consts::PI);
+        assert_eq!(SQRT1_2, consts::FRAC_1_SQRT_2);
+        assert_eq!(SQRT2, consts::SQRT_2);
+    }
+}
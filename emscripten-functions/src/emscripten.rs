@@ -1,32 +1,207 @@
 //! Select functions (with rust-native parameter and return value types) from the emscripten [`emscripten.h`] header file, and helper functions and types for them.
-//! Only most of the "Calling JavaScript From C/C++" and "Browser Execution Environment" functions are implemented.
+//! Only most of the "Calling JavaScript From C/C++", "Browser Execution Environment", "Emscripten Asynchronous File System API" and "Logging utilities" functions are implemented.
 //!
 //! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html
 
 use std::{
-    cell::RefCell,
-    ffi::{CStr, CString},
+    any::Any,
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::{CStr, CString, NulError},
     fmt::Display,
-    os::raw::{c_char, c_double, c_int},
+    ops::{BitOr, BitOrAssign, ControlFlow},
+    os::{
+        fd::{AsFd, AsRawFd, RawFd},
+        raw::{c_char, c_double, c_int, c_uint, c_void},
+    },
+    panic::{self, AssertUnwindSafe},
+    ptr::{null_mut, NonNull},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use emscripten_functions_sys::emscripten;
+use emscripten_functions_sys::{emscripten, html5};
 
-// The function to run in `set_main_loop_with_arg` sits in this thread-local object so that it will remain permanent throughout the main loop's run.
-// It needs to stay in a global place so that the `wrapper_func` that is passed as argument to `emscripten_set_main_loop`, which must be an `extern "C"` function, can access it (it couldn't have been a closure).
-// As the `thread_local` thing only gives us an immutable reference, we use a `RefCell` to be able to change the data when the function gets called.
+pub mod audio;
+pub mod blockers;
+#[cfg(feature = "dylink")]
+pub mod dylink;
+pub mod frame_stats;
+pub mod fs;
+pub mod idb;
+pub mod sockets;
+pub mod webgl;
+pub mod worker;
+
+// The function to run in `set_main_loop_with_arg` is boxed along with its state argument, and the box is given to `emscripten_set_main_loop_arg`,
+// which gives it back to `main_loop_wrapper` on every call. The wrapper is generic, so it knows the types in the box.
+// The box is also registered in this thread-local object, so that cancelling the main loop can free it, or give back its state.
+// If the main loop gets cancelled or replaced from inside its running function, the box is only marked as detached from the registry,
+// and the wrapper frees it once the function returns.
 thread_local! {
-    static MAIN_LOOP_FUNCTION: RefCell<Option<Box<dyn FnMut()>>> = RefCell::new(None);
+    static MAIN_LOOP: Cell<Option<MainLoopEntry>> = const { Cell::new(None) };
+    static RUNNING_MAIN_LOOP: Cell<*mut c_void> = const { Cell::new(null_mut()) };
+    static RUNNING_MAIN_LOOP_DETACHED: Cell<bool> = const { Cell::new(false) };
+    static LAST_MAIN_LOOP_ID: Cell<u64> = const { Cell::new(0) };
+    static MAIN_LOOP_PAUSE_REASONS: Cell<u8> = const { Cell::new(0) };
+    static MAIN_LOOP_PANIC_POLICY: Cell<MainLoopPanicPolicy> = const { Cell::new(MainLoopPanicPolicy::Cancel) };
+}
+
+// The main loop function and its state, as boxed for emscripten.
+struct MainLoopState<F, T> {
+    func: F,
+    arg: T,
+}
+
+// The box of the main loop that is set, with the function that frees it, giving back its type-erased state,
+// and the wrapper given to emscripten, for running it by hand.
+// The id tells the main loop apart from the ones set before it, for the `MainLoop` handles.
+#[derive(Clone, Copy)]
+struct MainLoopEntry {
+    id: u64,
+    state: *mut c_void,
+    into_arg: unsafe fn(*mut c_void) -> Box<dyn Any>,
+    step: unsafe extern "C" fn(*mut c_void),
+}
+
+unsafe fn main_loop_into_arg<F, T>(state: *mut c_void) -> Box<dyn Any>
+where
+    F: 'static,
+    T: 'static,
+{
+    let state = unsafe { Box::from_raw(state as *mut MainLoopState<F, T>) };
+    Box::new(state.arg)
+}
+
+unsafe extern "C" fn main_loop_wrapper<F, T>(state_ptr: *mut c_void)
+where
+    F: 'static + FnMut(&mut T) -> ControlFlow<()>,
+    T: 'static,
+{
+    let iteration = unsafe { run_main_loop_iteration::<F, T>(state_ptr) };
+
+    if iteration.stopped {
+        unsafe {
+            emscripten::emscripten_cancel_main_loop();
+        }
+    }
+    if let Some(message) = iteration.panic_message {
+        crate::console::error(&message);
+
+        // Throwing skips the destructors, so everything was dropped beforehand.
+        if let Some(exception) =
+            main_loop_panic_exception(message, MAIN_LOOP_PANIC_POLICY.with(Cell::get))
+        {
+            throw_string(exception);
+        }
+    }
+}
+
+// Returns what to throw after the main loop function panicked with the given message, if anything:
+// the report left by the panic hook set with `PanicHookOptions::rethrow`, or else the message itself if the policy asks for it.
+fn main_loop_panic_exception(message: String, policy: MainLoopPanicPolicy) -> Option<String> {
+    match take_panic_report() {
+        Some(report) => Some(report),
+        None if policy == MainLoopPanicPolicy::Throw => Some(message),
+        None => None,
+    }
+}
+
+// What is left to do after an iteration of the main loop, which `main_loop_wrapper` does by calling into emscripten.
+#[derive(Debug)]
+struct MainLoopIteration {
+    // Whether the iteration took the main loop out of the registry, so emscripten must stop calling it.
+    stopped: bool,
+    // The message to report if the function panicked.
+    panic_message: Option<String>,
+}
+
+// Runs the function of the main loop whose box is given, once, and does the bookkeeping of the registry:
+// the loop is taken out of it if the function breaks out of it or panics, as the loop state may be left inconsistent by the panic.
+// A panic must not unwind out of `main_loop_wrapper`, which is an `extern "C"` function, so it's caught here.
+// If the loop got cancelled or replaced while the function ran, the registry holds no longer this box, or no box at all,
+// so the box is dropped once the function returns.
+unsafe fn run_main_loop_iteration<F, T>(state_ptr: *mut c_void) -> MainLoopIteration
+where
+    F: 'static + FnMut(&mut T) -> ControlFlow<()>,
+    T: 'static,
+{
+    let previous_running = RUNNING_MAIN_LOOP.with(|running| running.replace(state_ptr));
+    let previous_detached = RUNNING_MAIN_LOOP_DETACHED.with(|detached| detached.replace(false));
+
+    let state = unsafe { &mut *(state_ptr as *mut MainLoopState<F, T>) };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| (state.func)(&mut state.arg)));
+
+    let detached = RUNNING_MAIN_LOOP_DETACHED.with(Cell::get);
+    let iteration = match result {
+        Ok(ControlFlow::Continue(())) => MainLoopIteration {
+            stopped: false,
+            panic_message: None,
+        },
+        Ok(ControlFlow::Break(())) => MainLoopIteration {
+            stopped: !detached,
+            panic_message: None,
+        },
+        Err(payload) => MainLoopIteration {
+            stopped: !detached,
+            panic_message: Some(format!(
+                "The main loop panicked: {}",
+                panic_message(&*payload)
+            )),
+        },
+    };
+    if iteration.stopped {
+        // The function is running, so this only marks it as detached.
+        drop(unregister_main_loop());
+    }
+
+    RUNNING_MAIN_LOOP.with(|running| running.set(previous_running));
+    if RUNNING_MAIN_LOOP_DETACHED.with(|detached| detached.replace(previous_detached)) {
+        // The function, along with its state, is dropped here, now that it's no longer running.
+        drop(unsafe { Box::from_raw(state_ptr as *mut MainLoopState<F, T>) });
+    }
+
+    iteration
+}
+
+// Turns the given string into a `CString`, removing the NUL characters in it instead of failing.
+pub(crate) fn to_cstring_lossy(string: &str) -> CString {
+    match CString::new(string) {
+        Ok(cstring) => cstring,
+        // We just removed every NUL, so this can't fail.
+        Err(_) => CString::new(string.replace('\0', "")).unwrap(),
+    }
+}
+
+// Extracts the message out of the payload of a caught panic.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<dyn Any>"
+    }
 }
 
-/// Sets the given function as the main loop of the calling thread, using the emscripten-defined [`emscripten_set_main_loop`].
+/// Sets the given function as the main loop of the calling thread, using the emscripten-defined [`emscripten_set_main_loop_arg`].
 /// The given function accepts a mutable reference (argument `arg`) to the variable that will contain the loop state and whatever else is needed for it to run.
 ///
 /// If you don't need that state argument, check out [`set_main_loop`].
 ///
-/// The main loop can be cancelled using the [`cancel_main_loop`] function.
+/// The main loop can be cancelled using the [`cancel_main_loop`] function, which can be called from inside `func` too, e.g. for a "quit" menu item.
+/// With `simulate_infinite_loop` set to `false`, the returned [`MainLoop`] handle can control it too.
+///
+/// If a main loop is already set, it's replaced: it gets cancelled, and its function and state are dropped,
+/// before the new one is set. Use [`cancel_main_loop_and_take`] first to keep the old state.
+///
+/// If `func` panics, the panic message is printed using [`console::error`](crate::console::error) and the main loop is cancelled,
+/// so that the panic doesn't unwind into the browser.
+/// The panic can also be turned into a JavaScript exception, with [`set_main_loop_panic_policy`].
+/// This requires the program to be built with `-C panic=unwind`; with `panic=abort`, a panic still aborts the whole program.
 ///
-/// [`emscripten_set_main_loop`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_main_loop
+/// [`emscripten_set_main_loop_arg`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_main_loop_arg
 ///
 /// # Arguments
 /// * `func` - The function to be set as main event loop for the calling thread.
@@ -36,6 +211,8 @@ thread_local! {
 /// * `fps` - The number of calls of the function per second.
 ///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used (recommended when using the main function for rendering) instead of a fixed rate.
 /// * `simulate_infinite_loop` - If `true`, no code after the function call will be executed, otherwise the code after the function call will be executed.
+///   **With `true`, the locals of the calling functions are leaked, not dropped**: emscripten unwinds out of them with a JavaScript exception.
+///   Check out [`set_main_loop_forever`], whose `!` return type makes this visible to the compiler.
 ///
 /// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
 ///
@@ -65,46 +242,296 @@ thread_local! {
 /// ```
 pub fn set_main_loop_with_arg<F, T>(
     mut func: F,
-    mut arg: T,
+    arg: T,
     fps: c_int,
     simulate_infinite_loop: bool,
-) where
+) -> MainLoop
+where
     F: 'static + FnMut(&mut T),
     T: 'static,
 {
-    // In `MAIN_LOOP_FUNCTION` we store a closure with no arguments, so that its type would be independent of `T`.
-    // That closure calls the `func` parameter with `arg` as parameter, and owns them both.
-    MAIN_LOOP_FUNCTION.with(|func_ref| {
-        *func_ref.borrow_mut() = Some(Box::new(move || {
-            func(&mut arg);
-        }));
-    });
+    set_main_loop_with_control(
+        move |arg| {
+            func(arg);
+            ControlFlow::Continue(())
+        },
+        arg,
+        fps,
+        simulate_infinite_loop,
+    )
+}
 
-    unsafe extern "C" fn wrapper_func() {
-        MAIN_LOOP_FUNCTION.with(|func_ref| {
-            if let Some(function) = &mut *func_ref.borrow_mut() {
-                (*function)();
-            }
-        });
+/// Sets the given function as the main loop of the calling thread, like [`set_main_loop_with_arg`],
+/// with the function deciding whether the loop goes on.
+///
+/// When the function returns [`ControlFlow::Break`], the main loop is cancelled like with [`cancel_main_loop`],
+/// and the function and its state argument are dropped once it has returned.
+///
+/// # Arguments
+/// * `func` - The function to be set as main event loop for the calling thread.
+/// * `arg` - The variable that represents the state that the main event loop ought to interact with.
+///   It will be consumed so that it can be kept alive during the loop.
+/// * `fps` - The number of calls of the function per second.
+///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used instead of a fixed rate.
+/// * `simulate_infinite_loop` - If `true`, no code after the function call will be executed, otherwise the code after the function call will be executed.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+///
+/// # Examples
+/// ```rust
+/// set_main_loop_with_control(|countdown| {
+///     println!("{}...", countdown);
+///     *countdown -= 1;
+///
+///     if *countdown == 0 {
+///         println!("Liftoff!");
+///         ControlFlow::Break(())
+///     } else {
+///         ControlFlow::Continue(())
+///     }
+/// }, 10, 1, true);
+/// ```
+pub fn set_main_loop_with_control<F, T>(
+    func: F,
+    arg: T,
+    fps: c_int,
+    simulate_infinite_loop: bool,
+) -> MainLoop
+where
+    F: 'static + FnMut(&mut T) -> ControlFlow<()>,
+    T: 'static,
+{
+    // Emscripten only allows one main loop at a time, so the previous one gets cancelled first.
+    // Its function is dropped here, or once it returns if this is called from inside it.
+    unsafe {
+        emscripten::emscripten_cancel_main_loop();
     }
+    let (entry, old_arg) = register_main_loop(func, arg);
+    drop(old_arg);
 
     unsafe {
-        emscripten::emscripten_set_main_loop(Some(wrapper_func), fps, simulate_infinite_loop)
+        emscripten::emscripten_set_main_loop_arg(
+            Some(entry.step),
+            entry.state,
+            fps,
+            simulate_infinite_loop,
+        )
+    };
+
+    MainLoop { id: entry.id }
+}
+
+// Puts the given function and state in the registry as the main loop, in place of the previous one,
+// whose state is given back, unless its function is running.
+fn register_main_loop<F, T>(func: F, arg: T) -> (MainLoopEntry, Option<Box<dyn Any>>)
+where
+    F: 'static + FnMut(&mut T) -> ControlFlow<()>,
+    T: 'static,
+{
+    let id = LAST_MAIN_LOOP_ID.with(|last_id| last_id.get() + 1);
+    LAST_MAIN_LOOP_ID.with(|last_id| last_id.set(id));
+    let entry = MainLoopEntry {
+        id,
+        state: Box::into_raw(Box::new(MainLoopState { func, arg })) as *mut c_void,
+        into_arg: main_loop_into_arg::<F, T>,
+        step: main_loop_wrapper::<F, T>,
+    };
+
+    let old_arg = unregister_main_loop();
+    MAIN_LOOP.with(|main_loop| main_loop.set(Some(entry)));
+
+    (entry, old_arg)
+}
+
+/// Sets the given function as the main loop of the calling thread, like [`set_main_loop_with_arg`],
+/// with the function also given the time elapsed since its previous call, measured with [`get_now`].
+///
+/// The elapsed time is zero on the first call, and it's clamped to `max_delta`,
+/// so that the game doesn't jump ahead after the page was hidden and the main loop stalled, or after a long hiccup.
+///
+/// # Arguments
+/// * `func` - The function to be set as main event loop for the calling thread.
+/// * `arg` - The variable that represents the state that the main event loop ought to interact with.
+///   It will be consumed so that it can be kept alive during the loop.
+/// * `max_delta` - The longest elapsed time given to the function.
+/// * `fps` - The number of calls of the function per second.
+///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used instead of a fixed rate.
+/// * `simulate_infinite_loop` - If `true`, no code after the function call will be executed, otherwise the code after the function call will be executed.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+///
+/// # Examples
+/// ```rust
+/// struct Ball {
+///     position: f64,
+///     velocity: f64,
+/// }
+///
+/// set_main_loop_timed(|ball, dt| {
+///     ball.position += ball.velocity * dt.as_secs_f64();
+/// }, Ball { position: 0.0, velocity: 2.5 }, Duration::from_millis(100), 0, true);
+/// ```
+pub fn set_main_loop_timed<F, T>(
+    mut func: F,
+    arg: T,
+    max_delta: Duration,
+    fps: c_int,
+    simulate_infinite_loop: bool,
+) -> MainLoop
+where
+    F: 'static + FnMut(&mut T, Duration),
+    T: 'static,
+{
+    let mut last_time: Option<f64> = None;
+
+    set_main_loop_with_arg(
+        move |arg| {
+            let now = get_now();
+            let delta = main_loop_delta(last_time, now, max_delta);
+            last_time = Some(now);
+
+            func(arg, delta);
+        },
+        arg,
+        fps,
+        simulate_infinite_loop,
+    )
+}
+
+// The time elapsed between the given times, in milliseconds, of two calls of a `set_main_loop_timed` function,
+// clamped to the given maximum. It's zero for the first call, which has no previous time.
+fn main_loop_delta(last_time: Option<f64>, now: f64, max_delta: Duration) -> Duration {
+    let delta = match last_time {
+        // The time can't go back, but a zero is safer than a panic if it ever does.
+        Some(last_time) => Duration::from_secs_f64(((now - last_time) / 1000.0).max(0.0)),
+        None => Duration::ZERO,
     };
+    delta.min(max_delta)
+}
+
+/// Sets the given function as the main loop of the calling thread, like [`set_main_loop_with_arg`] with `simulate_infinite_loop` set to `true`,
+/// and never returns.
+///
+/// Emscripten unwinds out of the calling code by throwing a JavaScript exception, so the code after the call never runs,
+/// and the locals of the calling functions are leaked instead of dropped.
+/// The `!` return type lets the compiler warn about such unreachable code, like a cleanup that would never happen.
+/// Anything that needs to live on, or to be dropped when the loop ends, belongs in `arg`.
+///
+/// # Arguments
+/// * `func` - The function to be set as main event loop for the calling thread.
+/// * `arg` - The variable that represents the state that the main event loop ought to interact with.
+///   It will be consumed so that it can be kept alive during the loop.
+/// * `fps` - The number of calls of the function per second.
+///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used (recommended when using the main function for rendering) instead of a fixed rate.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+///
+/// # Examples
+/// ```rust
+/// let game = Game::new();
+/// set_main_loop_forever(|game| game.frame(), game, 0);
+/// // Nothing here would ever run, so the compiler warns about any code put here.
+/// ```
+pub fn set_main_loop_forever<F, T>(func: F, arg: T, fps: c_int) -> !
+where
+    F: 'static + FnMut(&mut T),
+    T: 'static,
+{
+    set_main_loop_with_arg(func, arg, fps, true);
+    unreachable!("emscripten_set_main_loop_arg returned despite simulate_infinite_loop");
+}
+
+/// Sets the given function as the main loop of the calling thread, like [`set_main_loop_with_arg`],
+/// with its state made by the `setup` function on the first iteration of the loop, instead of before setting it.
+///
+/// The setup runs from the browser's event loop, after this function returned or, with `simulate_infinite_loop`,
+/// after the code calling it got unwound, e.g. once the canvas got its size and a WebGL context can be made for it.
+/// If the setup fails, the main loop is cancelled without calling `func`, and the error is given to `on_error`.
+///
+/// The state of the loop is kept as an `Option<T>`, which is `None` until the setup ran,
+/// so it can be taken back with `cancel_main_loop_and_take::<Option<T>>()`.
+///
+/// # Arguments
+/// * `setup` - The function making the state of the main loop, called once, on its first iteration.
+/// * `func` - The function to be set as main event loop for the calling thread.
+/// * `on_error` - The function to call with the error of the setup, if it fails.
+/// * `fps` - The number of calls of the function per second.
+///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used instead of a fixed rate.
+/// * `simulate_infinite_loop` - If `true`, no code after the function call will be executed, otherwise the code after the function call will be executed.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+///
+/// # Examples
+/// ```rust
+/// set_main_loop_with_setup(
+///     || Renderer::new("#canvas"),
+///     |renderer| renderer.draw_frame(),
+///     |err| console::error(format!("Couldn't set up the renderer: {}", err)),
+///     0,
+///     true,
+/// );
+/// ```
+pub fn set_main_loop_with_setup<S, F, H, T, E>(
+    setup: S,
+    mut func: F,
+    on_error: H,
+    fps: c_int,
+    simulate_infinite_loop: bool,
+) -> MainLoop
+where
+    S: 'static + FnOnce() -> Result<T, E>,
+    F: 'static + FnMut(&mut T),
+    H: 'static + FnOnce(E),
+    T: 'static,
+{
+    // Both are called at most once, so they're taken out when they are.
+    let mut setup = Some(setup);
+    let mut on_error = Some(on_error);
+
+    set_main_loop_with_control(
+        move |state: &mut Option<T>| {
+            if state.is_none() {
+                let Some(setup) = setup.take() else {
+                    return ControlFlow::Break(());
+                };
+                match setup() {
+                    Ok(value) => *state = Some(value),
+                    Err(err) => {
+                        if let Some(on_error) = on_error.take() {
+                            on_error(err);
+                        }
+                        return ControlFlow::Break(());
+                    }
+                }
+            }
+
+            if let Some(state) = state {
+                func(state);
+            }
+            ControlFlow::Continue(())
+        },
+        None,
+        fps,
+        simulate_infinite_loop,
+    )
 }
 
-/// Sets the given function as the main loop of the calling thread, using the emscripten-defined [`emscripten_set_main_loop`].
+/// Sets the given function as the main loop of the calling thread, using the emscripten-defined [`emscripten_set_main_loop_arg`].
 /// The given function has no parameters.
 ///
-/// The main loop can be cancelled using the [`cancel_main_loop`] function.
+/// The main loop can be cancelled using the [`cancel_main_loop`] function, which can be called from inside `func` too, e.g. for a "quit" menu item.
+/// With `simulate_infinite_loop` set to `false`, the returned [`MainLoop`] handle can control it too.
+/// A main loop that's already set gets replaced, and panics are handled, like in [`set_main_loop_with_arg`].
 ///
-/// [`emscripten_set_main_loop`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_main_loop
+/// [`emscripten_set_main_loop_arg`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_main_loop_arg
 ///
 /// # Arguments
 /// * `func` - The function to be set as main event loop for the calling thread.
 /// * `fps` - The number of calls of the function per second.
 ///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used (recommended when using the main function for rendering) instead of a fixed rate.
 /// * `simulate_infinite_loop` - If `true`, no code after the function call will be executed, otherwise the code after the function call will be executed.
+///   **With `true`, the locals of the calling functions are leaked, not dropped**: emscripten unwinds out of them with a JavaScript exception.
+///   Check out [`set_main_loop_forever`], whose `!` return type makes this visible to the compiler.
 ///
 /// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
 ///
@@ -114,37 +541,212 @@ pub fn set_main_loop_with_arg<F, T>(
 ///     println!("Hello world every half second!");
 /// }, 2, true);
 /// ```
-pub fn set_main_loop<F>(mut func: F, fps: c_int, simulate_infinite_loop: bool)
+pub fn set_main_loop<F>(mut func: F, fps: c_int, simulate_infinite_loop: bool) -> MainLoop
 where
     F: 'static + FnMut(),
 {
-    set_main_loop_with_arg(move |_| func(), (), fps, simulate_infinite_loop);
+    set_main_loop_with_arg(move |_| func(), (), fps, simulate_infinite_loop)
 }
 
 /// Cancels the main loop of the calling thread that was set using [`set_main_loop_with_arg`] or [`set_main_loop`].
+///
+/// It can be called from inside the main loop function, which is then dropped once it returns.
+/// Alternatively, a function set with [`set_main_loop_with_control`] can return [`ControlFlow::Break`].
 pub fn cancel_main_loop() {
+    // Also let's not forget to free up the main loop function and its state arg.
+    // If it's the one running, it gets dropped once it returns.
+    drop(take_main_loop());
+}
+
+/// Cancels the main loop of the calling thread like [`cancel_main_loop`], giving back the state argument
+/// that was given to [`set_main_loop_with_arg`] or [`set_main_loop_with_control`], instead of dropping it.
+/// This way, expensive state, like the loaded textures of a game, can be reused by the next main loop.
+///
+/// Returns `None` if there was no main loop, or if its state isn't of type `T`, in which case the state is dropped.
+/// It also returns `None` when called from inside the main loop function, as the state is in use;
+/// it's then dropped once the function returns.
+///
+/// # Examples
+/// ```rust
+/// struct Game {
+///     textures: Vec<Texture>,
+///     level: u32,
+/// }
+///
+/// set_main_loop_with_arg(|game: &mut Game| game.draw(), game, 0, false);
+///
+/// // Later, when the player goes back to the menu:
+/// if let Some(mut game) = cancel_main_loop_and_take::<Game>() {
+///     game.level = 1;
+///     set_main_loop_with_arg(|game: &mut Game| game.draw(), game, 0, false);
+/// }
+/// ```
+pub fn cancel_main_loop_and_take<T>() -> Option<T>
+where
+    T: 'static,
+{
+    take_main_loop()?.downcast().ok().map(|arg| *arg)
+}
+
+/// Runs one iteration of the main loop of the calling thread, calling its function once, right away,
+/// e.g. for an "advance one frame" debugging feature while the loop is paused, or for tests running under Node.js.
+///
+/// It only works with the main loops set with this crate's functions, like [`set_main_loop_with_arg`].
+/// The function is called like emscripten would call it: returning [`ControlFlow::Break`] or panicking cancels the loop.
+///
+/// The iteration happens on top of the ones scheduled by emscripten, so stepping a main loop that isn't paused
+/// makes it tick twice in a frame. Pause it first, with [`pause_main_loop`].
+///
+/// Returns `false`, without calling anything, if there is no main loop,
+/// or if it's called from inside the main loop function, as the function is already running.
+///
+/// # Examples
+/// ```rust
+/// let main_loop = set_main_loop(|| draw_frame(), 0, false);
+///
+/// let _keydown = set_keydown_callback(Target::Window, move |event| {
+///     if event.key_code() == KeyCode::N && main_loop.is_paused() {
+///         main_loop_step();
+///     }
+///     true
+/// });
+/// ```
+pub fn main_loop_step() -> bool {
+    let Some(entry) = MAIN_LOOP.with(Cell::get) else {
+        return false;
+    };
+    if entry.state == RUNNING_MAIN_LOOP.with(Cell::get) {
+        return false;
+    }
+
+    unsafe { (entry.step)(entry.state) };
+    true
+}
+
+// Cancels the main loop, dropping its function and giving back its state, unless the function is running.
+fn take_main_loop() -> Option<Box<dyn Any>> {
     unsafe {
         emscripten::emscripten_cancel_main_loop();
     }
+    unregister_main_loop()
+}
 
-    // Also let's not forget to free up the main loop function and its state arg.
-    MAIN_LOOP_FUNCTION.with(|func_ref| {
-        *func_ref.borrow_mut() = None;
-    });
+// Takes the main loop out of the registry, dropping its function and giving back its state, unless the function is running.
+// In that case the running function gets marked as detached, for `run_main_loop_iteration` to drop it once it returns.
+fn unregister_main_loop() -> Option<Box<dyn Any>> {
+    MAIN_LOOP_PAUSE_REASONS.with(|reasons| reasons.set(0));
+
+    let entry = MAIN_LOOP.with(Cell::take)?;
+    if entry.state == RUNNING_MAIN_LOOP.with(Cell::get) {
+        RUNNING_MAIN_LOOP_DETACHED.with(|detached| detached.set(true));
+        return None;
+    }
+
+    Some(unsafe { (entry.into_arg)(entry.state) })
+}
+
+/// What happens after the main loop function panics, as set with [`set_main_loop_panic_policy`].
+///
+/// Either way, the panic message is printed using [`console::error`](crate::console::error),
+/// and the main loop is cancelled, with its function and state dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MainLoopPanicPolicy {
+    /// Nothing else happens, and the rest of the program keeps running, e.g. its event callbacks.
+    /// The report of a panic hook set with [`PanicHookOptions::rethrow`] is still thrown, though.
+    #[default]
+    Cancel,
+    /// The panic message is then thrown as a JavaScript exception, like with [`throw_string`],
+    /// so that it reaches `window.onerror` and the error tracking scripts of the page.
+    Throw,
+}
+
+/// Sets what happens after the main loop function of the calling thread panics, for the current and future main loops.
+///
+/// Panics can only be caught if the program is built with `-C panic=unwind`; with `panic=abort`, a panic still aborts the whole program.
+///
+/// # Arguments
+/// * `policy` - What happens after a panic. It's [`MainLoopPanicPolicy::Cancel`] by default.
+///
+/// # Examples
+/// ```rust
+/// // Let the error tracking of the page know about the crash.
+/// set_main_loop_panic_policy(MainLoopPanicPolicy::Throw);
+/// set_main_loop(|| update_game(), 0, true);
+/// ```
+pub fn set_main_loop_panic_policy(policy: MainLoopPanicPolicy) {
+    MAIN_LOOP_PANIC_POLICY.with(|policy_ref| policy_ref.set(policy));
+}
+
+// The reasons for the main loop to be paused, as bits of `MAIN_LOOP_PAUSE_REASONS`.
+// Emscripten doesn't tell whether the main loop is paused, so it's tracked here,
+// and the loop only gets resumed once there's no reason left for it to be paused.
+const PAUSE_REASON_MANUAL: u8 = 1;
+const PAUSE_REASON_HIDDEN: u8 = 2;
+
+// Returns the pause reasons after adding the given one to the given ones,
+// and whether the loop must be paused, which is only when it had no reason to be paused before.
+fn pause_reasons_with(reasons: u8, reason: u8) -> (u8, bool) {
+    let new_reasons = reasons | reason;
+    (new_reasons, reasons == 0 && new_reasons != 0)
+}
+
+// Returns the pause reasons after removing the given one from the given ones,
+// and whether the loop must be resumed, which is only when that was the last reason left for it to be paused.
+fn pause_reasons_without(reasons: u8, reason: u8) -> (u8, bool) {
+    let new_reasons = reasons & !reason;
+    (new_reasons, reasons != 0 && new_reasons == 0)
+}
+
+fn add_pause_reason(reason: u8) {
+    let (reasons, pause) = pause_reasons_with(MAIN_LOOP_PAUSE_REASONS.with(Cell::get), reason);
+    MAIN_LOOP_PAUSE_REASONS.with(|reasons_ref| reasons_ref.set(reasons));
+    if pause {
+        unsafe {
+            emscripten::emscripten_pause_main_loop();
+        }
+    }
+}
+
+fn remove_pause_reason(reason: u8) {
+    let (reasons, resume) = pause_reasons_without(MAIN_LOOP_PAUSE_REASONS.with(Cell::get), reason);
+    MAIN_LOOP_PAUSE_REASONS.with(|reasons_ref| reasons_ref.set(reasons));
+    // E.g. the page can get hidden before any main loop is set, and shown again before one is.
+    if resume && is_main_loop_set() {
+        unsafe {
+            emscripten::emscripten_resume_main_loop();
+        }
+    }
 }
 
 /// Pauses the main loop of the calling thread.
 pub fn pause_main_loop() {
-    unsafe {
-        emscripten::emscripten_pause_main_loop();
-    }
+    add_pause_reason(PAUSE_REASON_MANUAL);
 }
 
 /// Resumes the main loop of the calling thread.
+///
+/// If it's also paused by [`auto_pause_on_hidden`] because the page is hidden, it's only resumed once the page is visible again.
 pub fn resume_main_loop() {
-    unsafe {
-        emscripten::emscripten_resume_main_loop();
-    }
+    remove_pause_reason(PAUSE_REASON_MANUAL);
+}
+
+/// Returns whether the main loop of the calling thread is paused, with [`pause_main_loop`] or by [`auto_pause_on_hidden`].
+///
+/// The pauses are tracked by this crate, so a main loop paused with the raw `emscripten_pause_main_loop` isn't reported as paused.
+/// Setting or cancelling a main loop clears the pause.
+///
+/// # Examples
+/// ```rust
+/// if is_main_loop_paused() {
+///     resume_main_loop();
+///     pause_button.set_label("Pause");
+/// } else {
+///     pause_main_loop();
+///     pause_button.set_label("Resume");
+/// }
+/// ```
+pub fn is_main_loop_paused() -> bool {
+    MAIN_LOOP_PAUSE_REASONS.with(Cell::get) != 0
 }
 
 /// Parameters of the main loop's scheduling mode.
@@ -163,6 +765,44 @@ pub enum MainLoopTiming {
     /// While the said function can be emulated using `postMessage()`, this mode of running the main loop is discouraged by the Emscripten devs.
     SetImmediate,
 }
+impl MainLoopTiming {
+    /// Returns the timing running the main loop function with the given period, using `setTimeout()`.
+    ///
+    /// The sub-millisecond part of the period is truncated, and periods longer than about 24.8 days are shortened to that.
+    ///
+    /// # Arguments
+    /// * `period` - The time between the calls of the main loop function.
+    ///
+    /// # Examples
+    /// ```rust
+    /// assert_eq!(MainLoopTiming::from_duration(Duration::from_micros(33_900)), MainLoopTiming::SetTimeout(33));
+    /// ```
+    pub fn from_duration(period: Duration) -> Self {
+        MainLoopTiming::SetTimeout(period.as_millis().min(c_int::MAX as u128) as c_int)
+    }
+
+    /// Turns the given raw timing parameters, as used by emscripten, into their [`MainLoopTiming`].
+    ///
+    /// If the parameters have an invalid mode, an error with them is returned instead.
+    ///
+    /// # Arguments
+    /// * `mode` - The scheduling mode, one of the `EM_TIMING_*` constants.
+    /// * `value` - The payload of the mode.
+    ///
+    /// # Examples
+    /// ```rust
+    /// assert_eq!(MainLoopTiming::from_raw(1, 2), Ok(MainLoopTiming::RequestAnimationFrame(2)));
+    /// assert_eq!(MainLoopTiming::from_raw(42, 0).unwrap_err().raw(), (42, 0));
+    /// ```
+    pub fn from_raw(mode: c_int, value: c_int) -> Result<Self, MainLoopInvalidTiming> {
+        match mode as u32 {
+            emscripten::EM_TIMING_SETTIMEOUT => Ok(MainLoopTiming::SetTimeout(value)),
+            emscripten::EM_TIMING_RAF => Ok(MainLoopTiming::RequestAnimationFrame(value)),
+            emscripten::EM_TIMING_SETIMMEDIATE => Ok(MainLoopTiming::SetImmediate),
+            _ => Err(MainLoopInvalidTiming { mode, value }),
+        }
+    }
+}
 
 /// Applies the given main loop timing parameters to the main loop.
 ///
@@ -197,6 +837,12 @@ pub struct MainLoopInvalidTiming {
     mode: c_int,
     value: c_int,
 }
+impl MainLoopInvalidTiming {
+    /// Returns the raw timing parameters that were found, as `(mode, value)`.
+    pub fn raw(&self) -> (c_int, c_int) {
+        (self.mode, self.value)
+    }
+}
 impl Display for MainLoopInvalidTiming {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -236,16 +882,17 @@ pub fn get_main_loop_timing() -> Result<MainLoopTiming, MainLoopInvalidTiming> {
         emscripten::emscripten_get_main_loop_timing(&mut mode, &mut value);
     }
 
-    match mode as u32 {
-        emscripten::EM_TIMING_SETTIMEOUT => Ok(MainLoopTiming::SetTimeout(value)),
-        emscripten::EM_TIMING_RAF => Ok(MainLoopTiming::RequestAnimationFrame(value)),
-        emscripten::EM_TIMING_SETIMMEDIATE => Ok(MainLoopTiming::SetImmediate),
-        _ => Err(MainLoopInvalidTiming { mode, value }),
-    }
+    MainLoopTiming::from_raw(mode, value)
 }
 
 /// It returns `true` if the main loop function is set, and `false` if the main loop function isn't set.
 ///
+/// The main loops set with this crate's functions, like [`set_main_loop`], are tracked by the crate,
+/// so for them the answer comes without calling into emscripten, and it's `true` while the loop is paused too.
+/// Otherwise, e.g. for a main loop set by C code or with the raw bindings, it's probed by setting the main loop timing
+/// to the values that it already is, which can fail spuriously in some emscripten versions and may wake up a paused loop.
+/// A main loop of this crate cancelled with the raw `emscripten_cancel_main_loop` is still reported as set.
+///
 /// # Examples
 /// ```rust
 /// if is_main_loop_set() {
@@ -253,6 +900,10 @@ pub fn get_main_loop_timing() -> Result<MainLoopTiming, MainLoopInvalidTiming> {
 /// }
 /// ```
 pub fn is_main_loop_set() -> bool {
+    if is_main_loop_registered() {
+        return true;
+    }
+
     // This is done by setting the main loop timing to the values that it already is,
     // and using the `emscripten_set_main_loop_timing` return value to see
     // if the main loop function is set.
@@ -265,22 +916,380 @@ pub fn is_main_loop_set() -> bool {
     }
 }
 
-/// Exits the program immediately while keeping the runtime alive, using [`emscripten_exit_with_live_runtime`].
-///
-/// [`emscripten_exit_with_live_runtime`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_exit_with_live_runtime
-pub fn exit_with_live_runtime() {
-    unsafe {
-        emscripten::emscripten_exit_with_live_runtime();
-    }
+// Returns whether a main loop of this crate is set, and hasn't been cancelled through this crate, even if it's paused.
+fn is_main_loop_registered() -> bool {
+    MAIN_LOOP.with(Cell::get).is_some()
 }
 
-/// Exits the program and kills the runtime, using [`emscripten_force_exit`].
-/// Like libc's [`exit`], but works even if [`exit_with_live_runtime`] was run.
+/// A handle to the main loop set by [`set_main_loop_with_arg`], [`set_main_loop_with_control`] or [`set_main_loop`],
+/// for code that controls it without reaching for the free functions, like [`pause_main_loop`].
 ///
-/// Only works if the project is built with `EXIT_RUNTIME` set - this is not the default.
-/// Build with `-sEXIT_RUNTIME` if you want to use this function.
+/// The handle only controls the main loop it was returned for: once that one is cancelled or replaced,
+/// its methods do nothing, and [`is_active`](MainLoop::is_active) returns `false`.
+/// Dropping it leaves the main loop running.
 ///
-/// [`emscripten_force_exit`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_force_exit
+/// # Examples
+/// ```rust
+/// let main_loop = set_main_loop(|| draw_frame(), 0, false);
+///
+/// let _keydown = set_keydown_callback(Target::Window, move |event| {
+///     if event.key_code() == KeyCode::P {
+///         if main_loop.is_paused() {
+///             main_loop.resume();
+///         } else {
+///             main_loop.pause();
+///         }
+///     }
+///     false
+/// })?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MainLoop {
+    id: u64,
+}
+impl MainLoop {
+    /// Returns whether the main loop is still set, i.e. it's neither cancelled nor replaced.
+    pub fn is_active(&self) -> bool {
+        MAIN_LOOP
+            .with(Cell::get)
+            .is_some_and(|entry| entry.id == self.id)
+    }
+
+    /// Pauses the main loop, like [`pause_main_loop`].
+    pub fn pause(&self) {
+        if self.is_active() {
+            pause_main_loop();
+        }
+    }
+
+    /// Resumes the main loop, like [`resume_main_loop`].
+    pub fn resume(&self) {
+        if self.is_active() {
+            resume_main_loop();
+        }
+    }
+
+    /// Returns whether the main loop is paused with [`pause`](MainLoop::pause) or [`pause_main_loop`],
+    /// or by [`auto_pause_on_hidden`].
+    pub fn is_paused(&self) -> bool {
+        self.is_active() && is_main_loop_paused()
+    }
+
+    /// Cancels the main loop, like [`cancel_main_loop`].
+    pub fn cancel(&self) {
+        if self.is_active() {
+            cancel_main_loop();
+        }
+    }
+
+    /// Applies the given timing parameters to the main loop, like [`set_main_loop_timing`].
+    ///
+    /// Returns `false` if the main loop is no longer set.
+    pub fn set_timing(&self, timing: &MainLoopTiming) -> bool {
+        self.is_active() && set_main_loop_timing(timing)
+    }
+
+    /// Returns the timing parameters of the main loop, like [`get_main_loop_timing`].
+    ///
+    /// Returns `None` if the main loop is no longer set, as the timing would then be the one of another main loop, if any.
+    pub fn timing(&self) -> Option<Result<MainLoopTiming, MainLoopInvalidTiming>> {
+        self.is_active().then(get_main_loop_timing)
+    }
+}
+
+// The number of frame intervals measured by `estimate_refresh_rate`.
+const REFRESH_RATE_SAMPLES: usize = 10;
+
+// The frame timestamps collected by `estimate_refresh_rate`, and the function to call with the result.
+struct RefreshRateEstimate<F> {
+    timestamps: Vec<f64>,
+    on_done: F,
+}
+
+unsafe extern "C" fn refresh_rate_frame<F>(time: f64, user_data: *mut c_void) -> bool
+where
+    F: 'static + FnOnce(Option<f64>),
+{
+    let estimate = unsafe { &mut *(user_data as *mut RefreshRateEstimate<F>) };
+    estimate.timestamps.push(time);
+    if estimate.timestamps.len() <= REFRESH_RATE_SAMPLES {
+        return true;
+    }
+
+    let estimate = unsafe { Box::from_raw(user_data as *mut RefreshRateEstimate<F>) };
+    let mut intervals: Vec<f64> = estimate
+        .timestamps
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect();
+    intervals.sort_by(f64::total_cmp);
+
+    // The median is used, so that a few dropped or delayed frames don't skew the result.
+    let median = intervals[intervals.len() / 2];
+    let rate = if median > 0.0 {
+        Some(1000.0 / median)
+    } else {
+        None
+    };
+    (estimate.on_done)(rate);
+
+    false
+}
+
+/// Estimates the refresh rate of the display, in Hz, by timing the next frames with [`requestAnimationFrame()`],
+/// using the emscripten-defined [`emscripten_request_animation_frame_loop`].
+///
+/// The median interval between 10 frames is measured, then the given function is called with the rate.
+/// It's given `None` if the browser gave the same timestamp to all those frames, leaving no interval to measure.
+///
+/// The estimate is only reliable while the page is visible, as browsers slow down or stop the frames of hidden pages.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+/// [`emscripten_request_animation_frame_loop`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_request_animation_frame_loop
+///
+/// # Arguments
+/// * `on_done` - The function to call with the estimated refresh rate.
+///
+/// # Examples
+/// ```rust
+/// estimate_refresh_rate(|rate| {
+///     // Run the game at about 60fps, even on 120Hz and 144Hz displays.
+///     let swap_interval = rate.map_or(1, |rate| (rate / 60.0).round().max(1.0) as c_int);
+///     set_main_loop_timing(&MainLoopTiming::RequestAnimationFrame(swap_interval));
+/// });
+/// ```
+pub fn estimate_refresh_rate<F>(on_done: F)
+where
+    F: 'static + FnOnce(Option<f64>),
+{
+    let estimate = Box::new(RefreshRateEstimate {
+        timestamps: Vec::with_capacity(REFRESH_RATE_SAMPLES + 1),
+        on_done,
+    });
+
+    unsafe {
+        html5::emscripten_request_animation_frame_loop(
+            Some(refresh_rate_frame::<F>),
+            Box::into_raw(estimate) as *mut c_void,
+        );
+    }
+}
+
+/// The errors reported by the emscripten functions that return an `EMSCRIPTEN_RESULT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmscriptenError {
+    /// The requested operation isn't supported by the browser or the current context.
+    NotSupported,
+    /// The requested operation couldn't be completed now, and it wasn't deferred for later either.
+    FailedNotDeferred,
+    /// The given target is invalid.
+    InvalidTarget,
+    /// The given target doesn't match any element.
+    UnknownTarget,
+    /// An invalid parameter was given.
+    InvalidParam,
+    /// A generic failure.
+    Failed,
+    /// There is no data available for the request.
+    NoData,
+    /// The operation timed out.
+    TimedOut,
+    /// A result code that isn't known by this crate.
+    Other(c_int),
+}
+impl EmscriptenError {
+    // Turns an `EMSCRIPTEN_RESULT` into a `Result`, with deferred operations considered successful.
+    pub(crate) fn from_result(result: c_int) -> Result<(), Self> {
+        match result {
+            // `EMSCRIPTEN_RESULT_SUCCESS` and `EMSCRIPTEN_RESULT_DEFERRED` are the only non-negative results.
+            0.. => Ok(()),
+            emscripten::EMSCRIPTEN_RESULT_NOT_SUPPORTED => Err(EmscriptenError::NotSupported),
+            emscripten::EMSCRIPTEN_RESULT_FAILED_NOT_DEFERRED => {
+                Err(EmscriptenError::FailedNotDeferred)
+            }
+            emscripten::EMSCRIPTEN_RESULT_INVALID_TARGET => Err(EmscriptenError::InvalidTarget),
+            emscripten::EMSCRIPTEN_RESULT_UNKNOWN_TARGET => Err(EmscriptenError::UnknownTarget),
+            emscripten::EMSCRIPTEN_RESULT_INVALID_PARAM => Err(EmscriptenError::InvalidParam),
+            emscripten::EMSCRIPTEN_RESULT_FAILED => Err(EmscriptenError::Failed),
+            emscripten::EMSCRIPTEN_RESULT_NO_DATA => Err(EmscriptenError::NoData),
+            emscripten::EMSCRIPTEN_RESULT_TIMED_OUT => Err(EmscriptenError::TimedOut),
+            result => Err(EmscriptenError::Other(result)),
+        }
+    }
+}
+impl Display for EmscriptenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmscriptenError::NotSupported => write!(f, "Operation not supported"),
+            EmscriptenError::FailedNotDeferred => {
+                write!(f, "Operation failed and could not be deferred")
+            }
+            EmscriptenError::InvalidTarget => write!(f, "Invalid target"),
+            EmscriptenError::UnknownTarget => write!(f, "Unknown target"),
+            EmscriptenError::InvalidParam => write!(f, "Invalid parameter"),
+            EmscriptenError::Failed => write!(f, "Operation failed"),
+            EmscriptenError::NoData => write!(f, "No data available"),
+            EmscriptenError::TimedOut => write!(f, "Operation timed out"),
+            EmscriptenError::Other(result) => write!(f, "Unknown emscripten result: {}", result),
+        }
+    }
+}
+
+// The main loop timing equivalent to the `fps` parameter of `emscripten_set_main_loop`.
+fn main_loop_timing_from_fps(fps: c_int) -> MainLoopTiming {
+    if fps <= 0 {
+        MainLoopTiming::RequestAnimationFrame(1)
+    } else {
+        MainLoopTiming::SetTimeout((1000 / fps).max(1))
+    }
+}
+
+/// Makes the main loop run at a lower rate while the page is hidden, e.g. when its tab is in the background,
+/// so that it doesn't burn CPU and battery for nothing.
+///
+/// It sets a visibility change callback (using [`html5::set_visibilitychange_callback`](crate::html5::set_visibilitychange_callback),
+/// so it replaces the function you might have set with it) that applies the matching timing with [`set_main_loop_timing`]
+/// each time the page gets hidden or shown.
+///
+/// It works with any main loop, including one set afterwards; while there's no main loop, the callback does nothing.
+/// Keep in mind that a loop using `requestAnimationFrame()` doesn't run at all while the page is hidden,
+/// so a `background_fps` > 0 keeps it running, at a slow pace, with `setTimeout()`.
+///
+/// # Arguments
+/// * `normal_fps` - The number of calls of the main loop function per second while the page is visible.
+///   If set to a value <= 0, the browser's [`requestAnimationFrame()`] function will be used instead of a fixed rate.
+/// * `background_fps` - The number of calls of the main loop function per second while the page is hidden,
+///   with the same meaning for values <= 0.
+///
+/// [`requestAnimationFrame()`]: https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame
+///
+/// # Examples
+/// ```rust
+/// set_main_loop(|| {
+///     // Render the game.
+/// }, 0, false);
+///
+/// // Run at the display's rate while visible, and once per second in the background.
+/// set_main_loop_auto_throttle(0, 1)?;
+/// ```
+pub fn set_main_loop_auto_throttle(
+    normal_fps: c_int,
+    background_fps: c_int,
+) -> Result<(), EmscriptenError> {
+    crate::html5::set_visibilitychange_callback(move |event| {
+        let fps = if event.hidden {
+            background_fps
+        } else {
+            normal_fps
+        };
+        // This does nothing if there is no main loop.
+        set_main_loop_timing(&main_loop_timing_from_fps(fps));
+
+        false
+    })
+    // The throttling lasts until the callback gets replaced or removed, not just for the lifetime of a guard.
+    .map(std::mem::forget)
+}
+
+/// Pauses the main loop while the page is hidden, e.g. when its tab is in the background, and resumes it once the page is visible again,
+/// so that a loop running with `setTimeout()` doesn't burn CPU and battery for nothing.
+///
+/// It sets a visibility change callback (using [`html5::set_visibilitychange_callback`](crate::html5::set_visibilitychange_callback),
+/// so it replaces the function you might have set with it, like the one of [`set_main_loop_auto_throttle`]).
+/// Disabling it removes that callback, and resumes the main loop if it was paused by it.
+///
+/// It doesn't interfere with [`pause_main_loop`] and [`resume_main_loop`]: a loop paused on purpose stays paused when the page is shown again,
+/// and a loop resumed on purpose while the page is hidden only resumes once the page is visible.
+/// Cancelling the main loop forgets about the pause, so a main loop set while the page is hidden runs until the page is hidden again.
+///
+/// # Arguments
+/// * `enabled` - Whether to pause the main loop while the page is hidden.
+/// * `on_change` - A function to call each time the page gets hidden or shown, with whether it's hidden, e.g. to mute the audio.
+///
+/// # Examples
+/// ```rust
+/// auto_pause_on_hidden(true, Some(Box::new(|hidden| {
+///     if hidden {
+///         mute_audio();
+///     } else {
+///         unmute_audio();
+///     }
+/// })))?;
+/// ```
+pub fn auto_pause_on_hidden(
+    enabled: bool,
+    mut on_change: Option<Box<dyn FnMut(bool)>>,
+) -> Result<(), EmscriptenError> {
+    if !enabled {
+        remove_pause_reason(PAUSE_REASON_HIDDEN);
+        return crate::html5::remove_visibilitychange_callback();
+    }
+
+    crate::html5::set_visibilitychange_callback(move |event| {
+        if event.hidden {
+            add_pause_reason(PAUSE_REASON_HIDDEN);
+        } else {
+            remove_pause_reason(PAUSE_REASON_HIDDEN);
+        }
+        if let Some(on_change) = &mut on_change {
+            on_change(event.hidden);
+        }
+
+        false
+    })
+    // The pausing lasts until it gets disabled, or the callback gets replaced or removed, not just for the lifetime of a guard.
+    .map(std::mem::forget)
+}
+
+/// Exits the program immediately while keeping the runtime alive, using [`emscripten_exit_with_live_runtime`].
+///
+/// [`emscripten_exit_with_live_runtime`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_exit_with_live_runtime
+pub fn exit_with_live_runtime() {
+    unsafe {
+        emscripten::emscripten_exit_with_live_runtime();
+    }
+}
+
+/// Stops running the calling code and returns control to the browser's event loop, using the emscripten-defined [`emscripten_unwind_to_js_event_loop`].
+/// The registered callbacks, like timers and event handlers, keep getting called afterwards.
+///
+/// It's the way to end `main` while leaving the program running in the background, without setting a main loop.
+///
+/// **The stack is abandoned, not unwound: the destructors of the local variables of every function on the call stack never run.**
+/// Anything that must be cleaned up, or that the callbacks still need, shouldn't live on the stack when calling this function.
+///
+/// Unlike [`exit_with_live_runtime`], which does the same but also keeps the runtime alive forever,
+/// this function lets the runtime exit once nothing keeps it alive anymore, if the program is built with `-sEXIT_RUNTIME`.
+///
+/// [`emscripten_unwind_to_js_event_loop`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_unwind_to_js_event_loop
+///
+/// # Examples
+/// ```rust
+/// html5::set_online_callback(|online| println!("Online: {}", online));
+///
+/// // The calling function never returns, but the callback keeps being called.
+/// unwind_to_js_event_loop();
+/// ```
+pub fn unwind_to_js_event_loop() -> ! {
+    unsafe { html5::emscripten_unwind_to_js_event_loop() }
+}
+
+/// Exits the program and kills the runtime, using [`emscripten_force_exit`].
+/// Like libc's [`exit`], but works even if [`exit_with_live_runtime`] was run.
+///
+/// **Only works if the project is built with `EXIT_RUNTIME` set - this is not the default.**
+/// Build with `-sEXIT_RUNTIME` if you want to use this function.
+///
+/// Without `EXIT_RUNTIME`, the program still stops, but the runtime is never shut down:
+/// no `atexit` handlers run, the standard streams aren't flushed, and the `onExit` handler of the page's `Module` isn't called,
+/// so any cleanup code relying on them silently never runs.
+/// Emscripten warns about it in the browser console only in builds with `-sASSERTIONS`, which is the default for unoptimized builds.
+///
+/// This function can't check for `EXIT_RUNTIME` itself, as the compiler settings can only be queried at runtime
+/// in builds with `-sRETAIN_COMPILER_SETTINGS`.
+/// Builds that do retain them can check it with [`compiler_setting_int`] before calling this function.
+///
+/// [`emscripten_force_exit`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_force_exit
 /// [`exit`]: https://linux.die.net/man/3/exit
 ///
 /// # Arguments
@@ -302,6 +1311,171 @@ pub fn force_exit(status: c_int) {
     }
 }
 
+/// The value of a compiler setting, as returned by [`compiler_setting`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompilerSetting {
+    /// A numeric or boolean setting, such as `OPT_LEVEL` or `EXIT_RUNTIME`.
+    /// Booleans are returned as `0` or `1`.
+    Int(i64),
+    /// A string setting, such as `EMSCRIPTEN_VERSION`.
+    /// List settings are returned as their elements joined by commas.
+    Str(String),
+}
+
+impl CompilerSetting {
+    /// Returns the value if it's numeric.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            CompilerSetting::Int(value) => Some(*value),
+            CompilerSetting::Str(_) => None,
+        }
+    }
+
+    /// Returns the value if it's a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CompilerSetting::Int(_) => None,
+            CompilerSetting::Str(value) => Some(value),
+        }
+    }
+}
+
+impl Display for CompilerSetting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompilerSetting::Int(value) => write!(f, "{}", value),
+            CompilerSetting::Str(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The error returned when a compiler setting can't be read, e.g. by [`compiler_setting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompilerSettingError {
+    /// The program wasn't built with `-sRETAIN_COMPILER_SETTINGS`, so no setting can be read.
+    NotRetained,
+    /// There is no compiler setting with the given name.
+    Unknown,
+    /// The setting isn't of the requested type, e.g. a string setting read with [`compiler_setting_int`].
+    WrongType,
+}
+impl Display for CompilerSettingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompilerSettingError::NotRetained => write!(
+                f,
+                "The compiler settings aren't retained; build with -sRETAIN_COMPILER_SETTINGS"
+            ),
+            CompilerSettingError::Unknown => write!(f, "Unknown compiler setting"),
+            CompilerSettingError::WrongType => {
+                write!(f, "The compiler setting isn't of the requested type")
+            }
+        }
+    }
+}
+
+// The kinds of compiler settings told apart by `compiler_setting_kind` in `asm_in_main_thread.c`.
+const COMPILER_SETTING_NOT_RETAINED: c_int = 0;
+const COMPILER_SETTING_INT: c_int = 1;
+const COMPILER_SETTING_STR: c_int = 2;
+
+// Reads the compiler setting with the given name, of the kind that it has,
+// checked beforehand, as `emscripten_get_compiler_setting` throws in builds not retaining the settings,
+// and returns either the value itself or a pointer to a string, with no way to tell them apart.
+fn read_compiler_setting(name: &str) -> Result<CompilerSetting, CompilerSettingError> {
+    let name = to_cstring_lossy(name);
+
+    let kind = unsafe { compiler_setting_kind(name.as_ptr()) };
+    match kind {
+        COMPILER_SETTING_NOT_RETAINED => return Err(CompilerSettingError::NotRetained),
+        COMPILER_SETTING_INT | COMPILER_SETTING_STR => {}
+        _ => return Err(CompilerSettingError::Unknown),
+    }
+
+    let value = unsafe { emscripten::emscripten_get_compiler_setting(name.as_ptr()) };
+    if kind == COMPILER_SETTING_STR {
+        // The string is cached on the JS side and must not be freed.
+        let value = unsafe { CStr::from_ptr(value as *const c_char) };
+        Ok(CompilerSetting::Str(value.to_string_lossy().into_owned()))
+    } else {
+        Ok(CompilerSetting::Int(value as i64))
+    }
+}
+
+/// Returns the value the program was compiled with for the given compiler setting,
+/// using the emscripten-defined [`emscripten_get_compiler_setting`].
+///
+/// **Only works if the project is built with `-sRETAIN_COMPILER_SETTINGS` - this is not the default.**
+/// Without it, [`CompilerSettingError::NotRetained`] is returned.
+///
+/// Whether the value is a [`CompilerSetting::Int`] or a [`CompilerSetting::Str`] is found out at runtime.
+/// When the type is known beforehand, [`compiler_setting_int`] and [`compiler_setting_str`] return the value directly.
+///
+/// [`emscripten_get_compiler_setting`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_compiler_setting
+///
+/// # Arguments
+/// * `name` - the name of the setting, as passed to `emcc` with `-s`, without its value.
+///
+/// # Examples
+/// ```rust
+/// match compiler_setting("EMSCRIPTEN_VERSION") {
+///     Ok(version) => console::log(format!("Built with emscripten {}", version)),
+///     Err(err) => console::log(format!("Unknown emscripten version: {}", err)),
+/// }
+/// ```
+pub fn compiler_setting<T>(name: T) -> Result<CompilerSetting, CompilerSettingError>
+where
+    T: AsRef<str>,
+{
+    read_compiler_setting(name.as_ref())
+}
+
+/// Returns the value the program was compiled with for the given numeric or boolean compiler setting, like [`compiler_setting`].
+/// Booleans are returned as `0` or `1`.
+///
+/// It returns [`CompilerSettingError::WrongType`] if the setting is a string or a list.
+///
+/// # Arguments
+/// * `name` - the name of the setting, as passed to `emcc` with `-s`, without its value.
+///
+/// # Examples
+/// ```rust
+/// if compiler_setting_int("EXIT_RUNTIME") == Ok(1) {
+///     force_exit(0);
+/// }
+/// ```
+pub fn compiler_setting_int<T>(name: T) -> Result<i64, CompilerSettingError>
+where
+    T: AsRef<str>,
+{
+    match read_compiler_setting(name.as_ref())? {
+        CompilerSetting::Int(value) => Ok(value),
+        CompilerSetting::Str(_) => Err(CompilerSettingError::WrongType),
+    }
+}
+
+/// Returns the value the program was compiled with for the given string or list compiler setting, like [`compiler_setting`].
+/// Lists are returned as their elements joined by commas.
+///
+/// It returns [`CompilerSettingError::WrongType`] if the setting is a number or a boolean.
+///
+/// # Arguments
+/// * `name` - the name of the setting, as passed to `emcc` with `-s`, without its value.
+///
+/// # Examples
+/// ```rust
+/// let version = compiler_setting_str("EMSCRIPTEN_VERSION").unwrap_or_default();
+/// ```
+pub fn compiler_setting_str<T>(name: T) -> Result<String, CompilerSettingError>
+where
+    T: AsRef<str>,
+{
+    match read_compiler_setting(name.as_ref())? {
+        CompilerSetting::Str(value) => Ok(value),
+        CompilerSetting::Int(_) => Err(CompilerSettingError::WrongType),
+    }
+}
+
 /// Returns the value of [`window.devicePixelRatio`], using the emscripten-defined [`emscripten_get_device_pixel_ratio`].
 ///
 /// [`window.devicePixelRatio`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/devicePixelRatio
@@ -317,6 +1491,9 @@ pub fn get_device_pixel_ratio() -> f64 {
 
 /// Returns the window title, using the emscripten-defined [`emscripten_get_window_title`].
 ///
+/// If emscripten gives no title at all, an empty string is returned.
+/// Titles that aren't valid UTF-8 have the invalid parts replaced with `U+FFFD`.
+///
 /// [`emscripten_get_window_title`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_window_title
 ///
 /// # Examples
@@ -325,9 +1502,12 @@ pub fn get_device_pixel_ratio() -> f64 {
 /// ```
 pub fn get_window_title() -> String {
     let title = unsafe { emscripten::emscripten_get_window_title() };
+    if title.is_null() {
+        return String::new();
+    }
 
     let title_cstr = unsafe { CStr::from_ptr(title) };
-    title_cstr.to_str().unwrap().to_string()
+    title_cstr.to_string_lossy().into_owned()
 }
 
 /// Sets the window title, using the emscripten-defined [`emscripten_set_window_title`].
@@ -337,6 +1517,9 @@ pub fn get_window_title() -> String {
 /// # Arguments
 /// * `title` - The new title
 ///
+/// Any NUL characters in the title are stripped, as they can't be passed to emscripten.
+/// Use [`try_set_window_title`] to get an error for such titles instead.
+///
 /// # Examples
 /// ```rust
 /// set_window_title("My Web App");
@@ -346,11 +1529,96 @@ pub fn set_window_title<T>(title: T)
 where
     T: AsRef<str>,
 {
-    let title = CString::new(title.as_ref()).unwrap();
+    let title = to_cstring_lossy(title.as_ref());
+
+    unsafe {
+        emscripten::emscripten_set_window_title(title.as_ptr());
+    }
+}
+
+/// Sets the window title, using the emscripten-defined [`emscripten_set_window_title`].
+/// Unlike [`set_window_title`], it returns an error if the title contains a NUL character.
+///
+/// [`emscripten_set_window_title`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_window_title
+///
+/// # Arguments
+/// * `title` - The new title
+///
+/// # Examples
+/// ```rust
+/// assert!(try_set_window_title("My Web App").is_ok());
+/// assert!(try_set_window_title("My\0App").is_err());
+/// ```
+pub fn try_set_window_title<T>(title: T) -> Result<(), NulError>
+where
+    T: AsRef<str>,
+{
+    let title = CString::new(title.as_ref())?;
 
     unsafe {
         emscripten::emscripten_set_window_title(title.as_ptr());
     }
+
+    Ok(())
+}
+
+// Turns the given string into a JavaScript string literal, quotes included, to safely put it in a script.
+pub(crate) fn js_string_literal(string: &str) -> String {
+    let mut literal = String::with_capacity(string.len() + 2);
+    literal.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            // Besides the control characters, the line and paragraph separators can't appear raw in older JavaScript string literals.
+            c if c.is_control() || c == '\u{2028}' || c == '\u{2029}' => {
+                literal.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Sets the background of the page's body to the given CSS color, by running a script with [`run_script`].
+/// It's useful e.g. to match the letterboxing around a canvas smaller than the viewport to the app's background.
+///
+/// The color is passed to the page as a string, so it can't break out of the style assignment.
+/// An invalid color is ignored by the browser.
+///
+/// # Arguments
+/// * `css_color` - The CSS color, like `#1e1e2e`, `black` or `rgb(30, 30, 46)`.
+///
+/// # Examples
+/// ```rust
+/// set_page_background("#1e1e2e");
+/// ```
+pub fn set_page_background<T>(css_color: T)
+where
+    T: AsRef<str>,
+{
+    run_script(format!(
+        "document.body.style.background = {};",
+        js_string_literal(css_color.as_ref())
+    ));
+}
+
+/// Returns whether the browser is online, with the [`navigator.onLine`] JS property.
+///
+/// Browsers report being online when connected to a network, which doesn't guarantee that the internet is reachable.
+/// To get notified when this changes, check out [`set_online_callback`](crate::html5::set_online_callback).
+///
+/// [`navigator.onLine`]: https://developer.mozilla.org/en-US/docs/Web/API/Navigator/onLine
+///
+/// # Examples
+/// ```rust
+/// if !is_online() {
+///     println!("Playing offline");
+/// }
+/// ```
+pub fn is_online() -> bool {
+    run_script_int("navigator.onLine ? 1 : 0") != 0
 }
 
 /// The result of the [`get_screen_size`] function.
@@ -413,7 +1681,7 @@ pub fn get_screen_size() -> ScreenSize {
 
 /// Hides the OS mouse cursor over the canvas, unlike SDL's [`SDL_ShowCursor`], which works with the SDL cursor.
 ///
-/// Useful if you draw your own cursor.
+/// Useful if you draw your own cursor. It can be shown again with [`show_mouse`].
 ///
 /// [`SDL_ShowCursor`]: https://wiki.libsdl.org/SDL2/SDL_ShowCursor
 pub fn hide_mouse() {
@@ -422,216 +1690,3245 @@ pub fn hide_mouse() {
     }
 }
 
-/// Returns the representation of the current app running time with the highest precision using the emscripten-defined [`emscripten_get_now`].
-/// It is most likely implemented using [`performance.now()`], and is relevant only in comparison with other calls to this function.
+/// Shows the OS mouse cursor over the canvas again, after it was hidden with [`hide_mouse`].
 ///
-/// [`emscripten_get_now`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_now
-/// [`performance.now()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/now
+/// Emscripten's function hides the cursor with a `cursor: none` rule for `canvas.emscripten` in the page's first stylesheet,
+/// so this function clears that rule, by running a script with [`run_script`].
+/// It also clears a cursor set with [`set_css_cursor`], bringing back the default one.
 ///
 /// # Examples
 /// ```rust
-/// let start_time = get_now();
-///
-/// let x = 0;
-/// for i in 1..100 {
-///     x += i;
-/// }
-///
-/// println!("It took {} seconds", get_now() - start_time);
+/// hide_mouse();
+/// // The player opened the pause menu.
+/// show_mouse();
 /// ```
-pub fn get_now() -> f64 {
-    unsafe { emscripten::emscripten_get_now() }
+pub fn show_mouse() {
+    run_script(
+        r#"
+            (function () {
+                try {
+                    var rules = document.styleSheets[0].cssRules;
+                    for (var i = 0; i < rules.length; i++) {
+                        if (rules[i].selectorText === "canvas.emscripten" && rules[i].style.cursor === "none") {
+                            rules[i].style.cursor = "";
+                        }
+                    }
+                } catch (e) {
+                    // There's no stylesheet, or it can't be read, so there's no rule hiding the cursor.
+                }
+                if (Module["canvas"]) {
+                    Module["canvas"].style.cursor = "";
+                }
+            })();
+        "#,
+    );
 }
 
-/// Returns a random number in range [0,1), with [`Math.random()`], using the emscripten-defined [`emscripten_random`].
+/// A shape of the mouse cursor, as set with [`set_css_cursor`].
 ///
-/// [`Math.random()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
-/// [`emscripten_random`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_random
+/// The variants are the values of the [`cursor`] CSS property.
 ///
-/// # Examples
-/// ```rust
-/// assert!(random() >= 0);
-/// assert!(random() < 1);
+/// [`cursor`]: https://developer.mozilla.org/en-US/docs/Web/CSS/cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cursor<'a> {
+    /// The default cursor of the platform, usually an arrow.
+    Default,
+    /// No cursor.
+    None,
+    /// A pointing hand, as over links.
+    Pointer,
+    /// A cross, for precise selections.
+    Crosshair,
+    /// An open hand, for something that can be grabbed.
+    Grab,
+    /// A closed hand, for something being dragged.
+    Grabbing,
+    /// A text cursor, for selectable text.
+    Text,
+    /// An image at the given URL, with its hotspot, i.e. the point that clicks, at the given coordinates in the image.
+    /// The default cursor is used while the image loads, or if it fails to.
+    Url(&'a str, c_int, c_int),
+    /// Any other value of the `cursor` CSS property, e.g. `"url(cursor.png) 4 4, auto"` for an image.
+    Custom(&'a str),
+}
+impl<'a> Cursor<'a> {
+    /// Returns the value of the `cursor` CSS property for this cursor.
+    pub fn css_value(&self) -> Cow<'a, str> {
+        let value = match self {
+            Cursor::Default => "default",
+            Cursor::None => "none",
+            Cursor::Pointer => "pointer",
+            Cursor::Crosshair => "crosshair",
+            Cursor::Grab => "grab",
+            Cursor::Grabbing => "grabbing",
+            Cursor::Text => "text",
+            Cursor::Url(url, hotspot_x, hotspot_y) => {
+                return Cow::Owned(format!(
+                    "url({}) {} {}, auto",
+                    css_string_literal(url),
+                    hotspot_x,
+                    hotspot_y
+                ));
+            }
+            Cursor::Custom(value) => value,
+        };
+        Cow::Borrowed(value)
+    }
+}
+
+// Turns the given string into a CSS string literal, quotes included, e.g. for a URL that can't break out of its `url()`.
+fn css_string_literal(string: &str) -> String {
+    let mut literal = String::with_capacity(string.len() + 2);
+    literal.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            // The trailing space ends the hexadecimal escape, in case a hexadecimal digit follows.
+            c if c.is_control() => literal.push_str(&format!("\\{:x} ", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+/// Sets the shape of the mouse cursor over the canvas, by setting the `cursor` CSS property of the `Module.canvas` element with [`run_script`].
+///
+/// The value is passed to the page as a string, so a [`Cursor::Custom`] one can't break out of the style assignment,
+/// and the URL of a [`Cursor::Url`] one is quoted, so it can't break out of its `url()`.
+/// An invalid value is ignored by the browser.
+///
+/// # Arguments
+/// * `cursor` - The shape of the cursor.
+///
+/// # Examples
+/// ```rust
+/// if hovering_a_button {
+///     set_css_cursor(Cursor::Pointer);
+/// } else if build_mode {
+///     set_css_cursor(Cursor::Url("hammer.png", 2, 2));
+/// } else if aiming {
+///     set_css_cursor(Cursor::Custom("crosshair"));
+/// } else {
+///     set_css_cursor(Cursor::Default);
+/// }
+/// ```
+pub fn set_css_cursor(cursor: Cursor) {
+    run_script(format!(
+        r#"if (Module["canvas"]) {{ Module["canvas"].style.cursor = {}; }}"#,
+        js_string_literal(&cursor.css_value())
+    ));
+}
+
+/// Returns the representation of the current app running time with the highest precision using the emscripten-defined [`emscripten_get_now`].
+/// It is most likely implemented using [`performance.now()`], and is relevant only in comparison with other calls to this function.
+///
+/// The time is in milliseconds, with a fractional part. For seconds, e.g. for physics code, check out [`get_now_seconds`].
+///
+/// It is not a wall clock: its starting point is arbitrary and differs between sessions.
+/// If you need the actual date and time, check out [`date_now`].
+/// It's monotonic though, so it's the one to use for measuring durations.
+///
+/// [`emscripten_get_now`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_now
+/// [`performance.now()`]: https://developer.mozilla.org/en-US/docs/Web/API/Performance/now
+///
+/// # Examples
+/// ```rust
+/// let start_time = get_now();
+///
+/// let x = 0;
+/// for i in 1..100 {
+///     x += i;
+/// }
+///
+/// println!("It took {} milliseconds", get_now() - start_time);
+/// ```
+pub fn get_now() -> f64 {
+    unsafe { emscripten::emscripten_get_now() }
+}
+
+/// Returns the same time as [`get_now`], in seconds instead of milliseconds.
+///
+/// Physics code usually works with velocities in units per second, so it's less error-prone to measure the time in seconds too.
+///
+/// # Examples
+/// ```rust
+/// let mut last_time = get_now_seconds();
+/// let mut position = 0.0;
+/// let velocity = 2.5; // Units per second.
+///
+/// set_main_loop(move || {
+///     let now = get_now_seconds();
+///     position += velocity * (now - last_time);
+///     last_time = now;
+/// }, 0, true);
+/// ```
+pub fn get_now_seconds() -> f64 {
+    get_now() / 1000.0
+}
+
+/// Returns the current wall-clock time, as the number of milliseconds since the Unix epoch,
+/// using the emscripten-defined [`emscripten_date_now`], which works like JS's [`Date.now()`].
+///
+/// Unlike [`get_now`], it can be compared across sessions, e.g. for timestamps that get saved.
+/// However, it can jump back and forth if the system clock gets changed, so use [`get_now`] for measuring durations.
+///
+/// [`emscripten_date_now`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_date_now
+/// [`Date.now()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now
+///
+/// # Examples
+/// ```rust
+/// println!("Log entry at {} ms since the epoch", date_now());
+/// ```
+pub fn date_now() -> f64 {
+    unsafe { html5::emscripten_date_now() }
+}
+
+/// Returns the current wall-clock time as a [`SystemTime`], from the milliseconds since the Unix epoch given by [`date_now`].
+///
+/// The sub-millisecond part given by some browsers is kept, and so are the times before the epoch, in case the system clock is set that way.
+///
+/// # Examples
+/// ```rust
+/// let elapsed = system_time_now().duration_since(saved_time).unwrap_or_default();
+/// println!("You've been away for {} minutes", elapsed.as_secs() / 60);
+/// ```
+pub fn system_time_now() -> SystemTime {
+    let ms = date_now();
+    let since_epoch = Duration::from_secs_f64(ms.abs() / 1000.0);
+
+    if ms >= 0.0 {
+        UNIX_EPOCH + since_epoch
+    } else {
+        UNIX_EPOCH - since_epoch
+    }
+}
+
+/// Returns a random number in range [0,1), with [`Math.random()`], using the emscripten-defined [`emscripten_random`].
+///
+/// [`Math.random()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random
+/// [`emscripten_random`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_random
+///
+/// # Examples
+/// ```rust
+/// assert!(random() >= 0);
+/// assert!(random() < 1);
 /// ```
 pub fn random() -> f32 {
     unsafe { emscripten::emscripten_random() }
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
-/// using the emscripten-defined [`emscripten_run_script`].
+/// Returns whether the program was built with Asyncify, using the emscripten-defined [`emscripten_has_asyncify`].
+/// Asyncify, enabled by building with `-sASYNCIFY`, is what allows the blocking functions like [`sleep`] to work.
 ///
-/// If you need to run the script in the main thread, check out [`run_script_main_thread`].
+/// [`emscripten_has_asyncify`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_has_asyncify
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`emscripten_run_script`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script
+/// # Examples
+/// ```rust
+/// if !has_asyncify() {
+///     console::warn("Built without -sASYNCIFY, saving is unavailable");
+/// }
+/// ```
+pub fn has_asyncify() -> bool {
+    unsafe { emscripten::emscripten_has_asyncify() != 0 }
+}
+
+/// The error returned by the blocking functions when the program wasn't built with `-sASYNCIFY`.
+///
+/// Without it, emscripten's blocking functions trap with an opaque "unreachable" error, so they're never called then.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsyncifyUnavailable;
+impl Display for AsyncifyUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "This function requires building with -sASYNCIFY")
+    }
+}
+
+// Checks that the blocking functions can be called, before calling them.
+pub(crate) fn require_asyncify() -> Result<(), AsyncifyUnavailable> {
+    if has_asyncify() {
+        Ok(())
+    } else {
+        Err(AsyncifyUnavailable)
+    }
+}
+
+/// Blocks the calling thread for the given number of milliseconds, using the emscripten-defined [`emscripten_sleep`].
+/// Meanwhile, the browser keeps running its event loop, so the page doesn't freeze.
+///
+/// Requires building with `-sASYNCIFY`, otherwise it returns [`AsyncifyUnavailable`] without sleeping.
+///
+/// [`emscripten_sleep`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_sleep
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `ms` - The number of milliseconds to sleep.
 ///
 /// # Examples
 /// ```rust
-/// run_script("alert('Hello world')");
-///
-/// // The `.escape_unicode()` method makes it safe to pass untrusted user input.
-/// run_script(
-///     format!(
-///         r##"
-///             document.querySelector("#this-is-secure").innerHTML = "{}"
-///         "##,
-///         "untrusted user input".escape_unicode()
-///     )
-/// );
+/// sleep(500).unwrap();
+/// console::log("Half a second later");
 /// ```
-pub fn run_script<T>(script: T)
-where
-    T: AsRef<str>,
-{
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    unsafe { emscripten::emscripten_run_script(script_cstring.as_ptr()) }
+pub fn sleep(ms: c_uint) -> Result<(), AsyncifyUnavailable> {
+    require_asyncify()?;
+
+    unsafe {
+        emscripten::emscripten_sleep(ms);
+    }
+    Ok(())
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
-/// using the emscripten-defined [`emscripten_run_script_int`].
-/// It returns the return result of the script, interpreted as a C int.
-/// Most probably the return result is passed to [`parseInt()`], with NaN represented as 0.
+// The function given to the running `scan_registers` or `scan_stack`, as a pointer to a `&mut dyn FnMut`,
+// since `em_scan_func` has no user data argument.
+thread_local! {
+    static SCANNER: Cell<*mut c_void> = const { Cell::new(std::ptr::null_mut()) };
+}
+
+unsafe extern "C" fn scan_wrapper(begin: *mut c_void, end: *mut c_void) {
+    let scanner = SCANNER.with(Cell::get);
+    if scanner.is_null() {
+        return;
+    }
+
+    let scanner = unsafe { &mut *(scanner as *mut &mut dyn FnMut(*const u8, *const u8)) };
+    scanner(begin as *const u8, end as *const u8);
+}
+
+// Runs the given emscripten scanning function with `scan_wrapper`, which calls `scanner` while it runs.
+fn run_scanner(
+    mut scanner: &mut dyn FnMut(*const u8, *const u8),
+    scan: unsafe extern "C" fn(emscripten::em_scan_func),
+) {
+    // The previous scanner is put back afterwards, in case a scan is started from inside another one.
+    let previous = SCANNER.with(|scanner_ref| {
+        scanner_ref
+            .replace(&mut scanner as *mut &mut dyn FnMut(*const u8, *const u8) as *mut c_void)
+    });
+    unsafe {
+        scan(Some(scan_wrapper));
+    }
+    SCANNER.with(|scanner_ref| scanner_ref.set(previous));
+}
+
+/// Calls the given function with the memory range holding the values of the registers, i.e. the local variables not on the stack,
+/// using the emscripten-defined [`emscripten_scan_registers`].
+/// It's meant for conservative garbage collectors looking for the pointers they have to keep alive.
 ///
-/// If you need to run the script in the main thread, check out [`run_script_main_thread_int`].
+/// WebAssembly doesn't allow reading the locals, so emscripten spills them to memory first, by unwinding the stack with Asyncify.
+/// The function is called before returning.
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`emscripten_run_script_int`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script_int
-/// [`parseInt()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/parseInt
+/// Requires building with `-sASYNCIFY`, otherwise it returns [`AsyncifyUnavailable`] without calling the function.
+///
+/// [`emscripten_scan_registers`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_scan_registers
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `scanner` - The function to call with the beginning and the end of the memory range.
 ///
 /// # Examples
 /// ```rust
-/// assert_eq!(run_script_int("1 + 2"), 3);
+/// scan_registers(|begin, end| {
+///     gc.mark_conservatively(begin, end);
+/// })
+/// .unwrap();
 /// ```
-pub fn run_script_int<T>(script: T) -> c_int
+pub fn scan_registers<F>(mut scanner: F) -> Result<(), AsyncifyUnavailable>
 where
-    T: AsRef<str>,
+    F: FnMut(*const u8, *const u8),
 {
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    unsafe { emscripten::emscripten_run_script_int(script_cstring.as_ptr()) }
+    require_asyncify()?;
+
+    run_scanner(&mut scanner, emscripten::emscripten_scan_registers);
+    Ok(())
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
-/// using the emscripten-defined [`emscripten_run_script_string`].
-/// It returns the return result of the script, interpreted as a string if possible.
-/// Otherwise, it returns None.
+/// Calls the given function with the memory range of the C stack, i.e. the part of the stack that lives in the linear memory,
+/// using the emscripten-defined [`emscripten_scan_stack`].
+/// It's meant for conservative garbage collectors looking for the pointers they have to keep alive.
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`emscripten_run_script_string`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script_string
+/// The function is called before returning.
+///
+/// [`emscripten_scan_stack`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_scan_stack
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `scanner` - The function to call with the beginning and the end of the memory range.
 ///
 /// # Examples
 /// ```rust
-/// assert_eq!(run_script_string("alert('hi')"), None);
-/// assert_eq!(run_script_string("2"), Some("2"));
-/// assert_eq!(run_script_string("'hi'"), Some("hi"));
+/// scan_stack(|begin, end| {
+///     gc.mark_conservatively(begin, end);
+/// });
 /// ```
-pub fn run_script_string<T>(script: T) -> Option<String>
+pub fn scan_stack<F>(mut scanner: F)
 where
-    T: AsRef<str>,
+    F: FnMut(*const u8, *const u8),
 {
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    let result = unsafe { emscripten::emscripten_run_script_string(script_cstring.as_ptr()) };
+    run_scanner(&mut scanner, emscripten::emscripten_scan_stack);
+}
 
-    if result.is_null() {
-        return None;
+/// The flags of [`log_with_flags`], which can be combined with `|`.
+///
+/// # Examples
+/// ```rust
+/// let flags = LogFlags::CONSOLE | LogFlags::WARN | LogFlags::C_STACK;
+/// assert!(flags.contains(LogFlags::WARN));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct LogFlags(c_int);
+impl LogFlags {
+    /// Logs to the browser console instead of the standard output or error.
+    pub const CONSOLE: LogFlags = LogFlags(emscripten::EM_LOG_CONSOLE as c_int);
+    /// Logs as a warning.
+    pub const WARN: LogFlags = LogFlags(emscripten::EM_LOG_WARN as c_int);
+    /// Logs as an error.
+    pub const ERROR: LogFlags = LogFlags(emscripten::EM_LOG_ERROR as c_int);
+    /// Logs as an info message.
+    pub const INFO: LogFlags = LogFlags(emscripten::EM_LOG_INFO as c_int);
+    /// Logs as a debug message.
+    pub const DEBUG: LogFlags = LogFlags(emscripten::EM_LOG_DEBUG as c_int);
+    /// Appends the callstack of the compiled code.
+    pub const C_STACK: LogFlags = LogFlags(emscripten::EM_LOG_C_STACK as c_int);
+    /// Appends the callstack of the JavaScript code.
+    pub const JS_STACK: LogFlags = LogFlags(emscripten::EM_LOG_JS_STACK as c_int);
+    /// Leaves the file paths out of the callstacks, keeping only the file names.
+    pub const NO_PATHS: LogFlags = LogFlags(emscripten::EM_LOG_NO_PATHS as c_int);
+    /// Includes the function parameters in the callstacks, where available.
+    pub const FUNC_PARAMS: LogFlags = LogFlags(emscripten::EM_LOG_FUNC_PARAMS as c_int);
+
+    /// Returns no flags, which logs to the standard output.
+    pub const fn empty() -> Self {
+        LogFlags(0)
     }
 
-    let result_cstr = unsafe { CStr::from_ptr(result) };
-    Some(result_cstr.to_str().unwrap().to_string())
+    /// Returns the raw `EM_LOG_*` flags.
+    pub const fn bits(&self) -> c_int {
+        self.0
+    }
+
+    /// Returns whether all the given flags are set.
+    pub const fn contains(&self, other: LogFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
 }
+impl BitOr for LogFlags {
+    type Output = LogFlags;
 
-// The functions defined in `asm_in_main_thread.c`.
-extern "C" {
-    fn asm_in_main_thread(script: *const c_char);
-    fn asm_in_main_thread_int(script: *const c_char) -> c_int;
-    fn asm_in_main_thread_double(script: *const c_char) -> c_double;
+    fn bitor(self, rhs: Self) -> Self::Output {
+        LogFlags(self.0 | rhs.0)
+    }
+}
+impl BitOrAssign for LogFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
-/// using the emscripten-defined [`MAIN_THREAD_EM_ASM`].
+/// Logs the given message with the given flags, using the emscripten-defined [`emscripten_log`].
 ///
-/// If you need to run the script in the calling thread, check out [`run_script`].
+/// Unlike the functions of the [`console`](crate::console) module, it can add callstacks to the message.
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`MAIN_THREAD_EM_ASM`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM
+/// [`emscripten_log`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_log
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `flags` - Where and how to log the message.
+/// * `message` - The message to log.
 ///
 /// # Examples
 /// ```rust
-/// run_script_main_thread("alert('Hello world')");
-///
-/// // The `.escape_unicode()` method makes it safe to pass untrusted user input.
-/// run_script_main_thread(
-///     format!(
-///         r##"
-///             document.querySelector("#this-is-secure").innerHTML = "{}"
-///         "##,
-///         "untrusted user input".escape_unicode()
-///     )
-/// );
+/// log_with_flags(LogFlags::CONSOLE | LogFlags::ERROR | LogFlags::C_STACK, "The save is corrupt");
 /// ```
-pub fn run_script_main_thread<T>(script: T)
+pub fn log_with_flags<T>(flags: LogFlags, message: T)
 where
     T: AsRef<str>,
 {
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    unsafe { asm_in_main_thread(script_cstring.as_ptr()) }
+    let message = to_cstring_lossy(message.as_ref());
+
+    // The message goes through a `%s` so that any `%` in it doesn't get interpreted by the variadic C function.
+    unsafe {
+        emscripten::emscripten_log(flags.bits(), c"%s".as_ptr(), message.as_ptr());
+    }
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
-/// using the emscripten-defined [`MAIN_THREAD_EM_ASM_INT`].
-/// It returns the return result of the script, interpreted as a C int.
-/// Most probably the return result is passed to [`parseInt()`], with NaN represented as 0.
+/// Stops the program at a breakpoint, if the browser's developer tools are open, using the emscripten-defined [`emscripten_debugger`].
+/// It has the effect of the JavaScript [`debugger`] statement.
 ///
-/// If you need to run the script in the calling thread, check out [`run_script_int`].
+/// Check out the [`debugger_if`](crate::debugger_if) macro for breaking when an invariant fails.
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`MAIN_THREAD_EM_ASM_INT`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM_INT
-/// [`parseInt()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/parseInt
+/// [`emscripten_debugger`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_debugger
+/// [`debugger`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Statements/debugger
+///
+/// # Examples
+/// ```rust
+/// debugger();
+/// ```
+pub fn debugger() {
+    unsafe { emscripten::emscripten_debugger() }
+}
+
+/// Returns the current callstack, one frame per line, using the emscripten-defined [`emscripten_get_callstack`].
+///
+/// [`emscripten_get_callstack`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_callstack
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `flags` - Which callstacks to get and how, e.g. [`LogFlags::C_STACK`] for the one of the compiled code,
+///   or [`LogFlags::JS_STACK`] for the JavaScript one. The flags about where to log are ignored.
 ///
 /// # Examples
 /// ```rust
-/// assert_eq!(run_script_main_thread_int("1 + 2"), 3);
+/// println!("We got here through:\n{}", get_callstack(LogFlags::C_STACK | LogFlags::NO_PATHS));
 /// ```
-pub fn run_script_main_thread_int<T>(script: T) -> c_int
+pub fn get_callstack(flags: LogFlags) -> String {
+    callstack_from(|buffer| unsafe {
+        match buffer {
+            Some(buffer) => emscripten::emscripten_get_callstack(
+                flags.bits(),
+                buffer.as_mut_ptr() as *mut c_char,
+                buffer.len() as c_int,
+            ),
+            None => emscripten::emscripten_get_callstack(flags.bits(), null_mut(), 0),
+        }
+    })
+}
+
+// Gets a callstack with the given function, which works like `emscripten_get_callstack` given either no buffer or a buffer.
+fn callstack_from<F>(mut get_callstack: F) -> String
 where
-    T: AsRef<str>,
+    F: FnMut(Option<&mut [u8]>) -> c_int,
 {
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    unsafe { asm_in_main_thread_int(script_cstring.as_ptr()) }
+    // With no buffer, emscripten returns the size needed for the callstack, including the NUL terminator.
+    let needed = get_callstack(None);
+    if needed <= 0 {
+        return String::new();
+    }
+
+    // The callstack is taken again with each call, and it can be a bit longer the next time, as it's taken from another line.
+    // A truncated callstack fills the whole buffer, so we leave some room to tell it apart, and retry with a bigger buffer if needed.
+    let mut size = needed.saturating_add(64);
+    loop {
+        let mut buffer = vec![0u8; size as usize];
+        get_callstack(Some(&mut buffer));
+
+        let len = buffer.iter().position(|&b| b == 0).unwrap_or(buffer.len());
+        if len + 1 < buffer.len() || size == c_int::MAX {
+            buffer.truncate(len);
+            return String::from_utf8_lossy(&buffer).into_owned();
+        }
+        size = size.saturating_mul(2);
+    }
 }
 
-/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
-/// using the emscripten-defined [`MAIN_THREAD_EM_ASM_DOUBLE`].
-/// It returns the return result of the script, interpreted as a C double.
+/// Logs the callstack as an error to the browser console and stops the program at a breakpoint with [`debugger`](crate::emscripten::debugger),
+/// if the given condition is true.
 ///
-/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
-/// [`MAIN_THREAD_EM_ASM_DOUBLE`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM_DOUBLE
+/// Like `debug_assert!`, it does nothing in release builds, where the condition isn't even evaluated.
+///
+/// # Examples
+/// ```rust
+/// debugger_if!(player.health > player.max_health);
+/// ```
+#[macro_export]
+macro_rules! debugger_if {
+    ($cond:expr $(,)?) => {
+        if cfg!(debug_assertions) && $cond {
+            $crate::console::error(format!(
+                "debugger_if!({}) triggered at {}:{}, with the callstack:\n{}",
+                stringify!($cond),
+                file!(),
+                line!(),
+                $crate::emscripten::get_callstack($crate::emscripten::LogFlags::C_STACK)
+            ));
+            $crate::emscripten::debugger();
+        }
+    };
+}
+
+/// Throws a JavaScript exception with the given string as its value, using the emscripten-defined [`emscripten_throw_string`].
+///
+/// Unlike a Rust panic, it's a genuine JavaScript exception: if nothing catches it, it reaches `window.onerror`,
+/// and with it the error tracking scripts of the page.
+/// When the Rust code was called from JavaScript, e.g. through an exported function, the caller can catch it with `try`/`catch`,
+/// getting the string as the caught value.
+/// The exception unwinds through the Rust code without running any destructors,
+/// so anything still alive when calling this function is leaked.
+///
+/// Emscripten copies the string into a JavaScript string before throwing,
+/// so it doesn't need to outlive the call - it's leaked anyway, as it's never dropped.
+///
+/// [`emscripten_throw_string`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_throw_string
 ///
 /// # Arguments
-/// * `script` - The script to execute.
+/// * `message` - The value of the exception. Any nul characters in it are removed.
 ///
 /// # Examples
 /// ```rust
-/// assert_eq!(run_script_main_thread_double("1.1 + 2.1"), 3.2);
+/// if assets_missing {
+///     throw_string("The game assets couldn't be loaded");
+/// }
 /// ```
-pub fn run_script_main_thread_double<T>(script: T) -> c_double
+pub fn throw_string<T>(message: T) -> !
 where
     T: AsRef<str>,
 {
-    let script_cstring = CString::new(script.as_ref()).unwrap();
-    unsafe { asm_in_main_thread_double(script_cstring.as_ptr()) }
+    let message = to_cstring_lossy(message.as_ref());
+
+    unsafe {
+        emscripten::emscripten_throw_string(message.as_ptr());
+    }
+    unreachable!("emscripten_throw_string returned");
+}
+
+/// Throws a JavaScript exception with the given number as its value, using the emscripten-defined [`emscripten_throw_number`].
+///
+/// Like with [`throw_string`], no destructors run for the Rust code the exception unwinds through.
+///
+/// [`emscripten_throw_number`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_throw_number
+///
+/// # Arguments
+/// * `number` - The value of the exception.
+///
+/// # Examples
+/// ```rust
+/// throw_number(404.0);
+/// ```
+pub fn throw_number(number: f64) -> ! {
+    unsafe {
+        emscripten::emscripten_throw_number(number);
+    }
+    unreachable!("emscripten_throw_number returned");
+}
+
+// The report of the latest panic, left by the panic hook set with `PanicHookOptions::rethrow` for the code catching the panic to throw.
+thread_local! {
+    static PENDING_PANIC_REPORT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn take_panic_report() -> Option<String> {
+    PENDING_PANIC_REPORT.with(RefCell::take)
+}
+
+/// The options of the panic hook set with [`set_console_panic_hook_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PanicHookOptions {
+    /// Whether to throw the panic report as a JavaScript exception, like with [`throw_string`],
+    /// so that it reaches `window.onerror` and the error tracking scripts of the page.
+    ///
+    /// Throwing from the hook would skip the unwinding of the panic, so the report is thrown once the panic is caught
+    /// by the main loop functions of this crate, after they dropped what they had to, like with [`MainLoopPanicPolicy::Throw`].
+    /// A panic of the function given to [`run_in_main_thread`] takes its report along to the calling thread.
+    /// The panics caught with your own [`std::panic::catch_unwind`] aren't thrown, and the uncaught ones abort the program as usual.
+    pub rethrow: bool,
+    /// Whether to stop the program at a breakpoint with [`debugger`], in builds with debug assertions only.
+    pub debugger: bool,
+}
+
+/// Sets a panic hook printing the panics to the browser console, with the default [`PanicHookOptions`].
+/// Check out [`set_console_panic_hook_with`] for the details.
+///
+/// # Examples
+/// ```rust
+/// set_console_panic_hook();
+///
+/// let config: Vec<u8> = Vec::new();
+/// // This panic is printed as a console error, with the callstack.
+/// let first = config[0];
+/// ```
+pub fn set_console_panic_hook() {
+    set_console_panic_hook_with(PanicHookOptions::default());
+}
+
+/// Sets a panic hook, with [`std::panic::set_hook`], printing the panics to the browser console with [`console::error`](crate::console::error),
+/// along with the JavaScript and compiled code callstacks, as given by [`get_callstack`].
+/// Unlike the default hook, which prints to the standard error, the report keeps the callstack, and shows up as a console error.
+///
+/// # Arguments
+/// * `options` - Whether to also throw the report as a JavaScript exception, and to stop at a breakpoint.
+///
+/// # Examples
+/// ```rust
+/// set_console_panic_hook_with(PanicHookOptions {
+///     rethrow: true,
+///     debugger: true,
+/// });
+/// ```
+pub fn set_console_panic_hook_with(options: PanicHookOptions) {
+    panic::set_hook(Box::new(move |info| {
+        let location = match info.location() {
+            Some(location) => format!(
+                " at {}:{}:{}",
+                location.file(),
+                location.line(),
+                location.column()
+            ),
+            None => String::new(),
+        };
+        let report = format!(
+            "panicked{}:\n{}\n\nCallstack:\n{}",
+            location,
+            panic_message(info.payload()),
+            get_callstack(LogFlags::C_STACK | LogFlags::JS_STACK)
+        );
+
+        crate::console::error(&report);
+
+        if options.debugger && cfg!(debug_assertions) {
+            debugger();
+        }
+        if options.rethrow {
+            PENDING_PANIC_REPORT.with(|pending| pending.replace(Some(report)));
+        }
+    }));
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
+/// using the emscripten-defined [`emscripten_run_script`].
+///
+/// If you need to run the script in the main thread, check out [`run_script_main_thread`].
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`emscripten_run_script`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// run_script("alert('Hello world')");
+///
+/// // The `.escape_unicode()` method makes it safe to pass untrusted user input.
+/// run_script(
+///     format!(
+///         r##"
+///             document.querySelector("#this-is-secure").innerHTML = "{}"
+///         "##,
+///         "untrusted user input".escape_unicode()
+///     )
+/// );
+/// ```
+pub fn run_script<T>(script: T)
+where
+    T: AsRef<str>,
+{
+    run_script_as(script)
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
+/// using the emscripten-defined [`emscripten_run_script_int`].
+/// It returns the return result of the script, interpreted as a C int.
+/// Most probably the return result is passed to [`parseInt()`], with NaN represented as 0.
+///
+/// If you need to run the script in the main thread, check out [`run_script_main_thread_int`].
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`emscripten_run_script_int`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script_int
+/// [`parseInt()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/parseInt
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(run_script_int("1 + 2"), 3);
+/// ```
+pub fn run_script_int<T>(script: T) -> c_int
+where
+    T: AsRef<str>,
+{
+    run_script_as(script)
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
+/// using the emscripten-defined [`emscripten_run_script_string`].
+/// It returns the return result of the script, interpreted as a string if possible.
+/// Otherwise, it returns None.
+///
+/// Emscripten writes the result into a single buffer of its own, which gets reallocated by the next call,
+/// so it's copied into the returned `String` right away, before anything else can run.
+/// Strings that aren't valid UTF-8, such as ones with lone surrogates, have the invalid parts replaced with `U+FFFD`.
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`emscripten_run_script_string`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_script_string
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(run_script_string("alert('hi')"), None);
+/// assert_eq!(run_script_string("2"), Some("2".to_string()));
+/// assert_eq!(run_script_string("'hi'"), Some("hi".to_string()));
+/// ```
+pub fn run_script_string<T>(script: T) -> Option<String>
+where
+    T: AsRef<str>,
+{
+    run_script_as(script)
+}
+
+/// A type that the result of a JavaScript script can be turned into, for [`run_script_as`].
+///
+/// It's implemented for `()`, `i32`, `f64`, `String` and `Option<String>`,
+/// and it can be implemented for your own types, e.g. on top of the `Option<String>` implementation.
+///
+/// # Examples
+/// ```rust
+/// struct Rgb(u8, u8, u8);
+///
+/// impl FromScriptResult for Rgb {
+///     fn from_script(script: &str) -> Self {
+///         let color: String = run_script_as(format!("({}).join(',')", script));
+///         let mut channels = color.split(',').map(|channel| channel.parse().unwrap_or(0));
+///         Rgb(
+///             channels.next().unwrap_or(0),
+///             channels.next().unwrap_or(0),
+///             channels.next().unwrap_or(0),
+///         )
+///     }
+/// }
+///
+/// let Rgb(r, g, b) = run_script_as("[255, 128, 0]");
+/// ```
+pub trait FromScriptResult: Sized {
+    /// Runs the given script in the calling thread, and turns its result into this type.
+    fn from_script(script: &str) -> Self;
+}
+
+/// Runs the script like [`run_script`], ignoring its result.
+impl FromScriptResult for () {
+    fn from_script(script: &str) -> Self {
+        let script_cstring = to_cstring_lossy(script);
+        unsafe { emscripten::emscripten_run_script(script_cstring.as_ptr()) }
+    }
+}
+
+/// Runs the script like [`run_script_int`].
+impl FromScriptResult for c_int {
+    fn from_script(script: &str) -> Self {
+        let script_cstring = to_cstring_lossy(script);
+        unsafe { emscripten::emscripten_run_script_int(script_cstring.as_ptr()) }
+    }
+}
+
+/// Runs the script like [`run_script_string`], parsing the result as a number.
+/// It's NaN if the result isn't a number, like for `null` or `undefined`.
+impl FromScriptResult for f64 {
+    fn from_script(script: &str) -> Self {
+        // JavaScript turns the numbers into strings that Rust can parse, including `NaN` and `Infinity`.
+        Option::<String>::from_script(script)
+            .and_then(|result| result.parse().ok())
+            .unwrap_or(f64::NAN)
+    }
+}
+
+/// Runs the script like [`run_script_string`], with an empty string for a `null` or `undefined` result.
+impl FromScriptResult for String {
+    fn from_script(script: &str) -> Self {
+        Option::<String>::from_script(script).unwrap_or_default()
+    }
+}
+
+/// Runs the script like [`run_script_string`].
+impl FromScriptResult for Option<String> {
+    fn from_script(script: &str) -> Self {
+        let script_cstring = to_cstring_lossy(script);
+        let result = unsafe { emscripten::emscripten_run_script_string(script_cstring.as_ptr()) };
+
+        // The buffer stays valid until the next `emscripten_run_script_string` call, which can't happen before it's copied:
+        // the script has already finished running, and nothing between here and the copy calls into JavaScript.
+        unsafe { copy_script_string(result) }
+    }
+}
+
+// Copies the result of `emscripten_run_script_string`, which is null if the script's result isn't a string.
+unsafe fn copy_script_string(result: *const c_char) -> Option<String> {
+    if result.is_null() {
+        return None;
+    }
+
+    let result_cstr = unsafe { CStr::from_ptr(result) };
+    Some(result_cstr.to_string_lossy().into_owned())
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the calling thread,
+/// returning its result as the type asked for, which must implement [`FromScriptResult`].
+///
+/// It's a single entry point for [`run_script`], [`run_script_int`] and [`run_script_string`],
+/// with the type of the result picked by the type annotation.
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// let n: i32 = run_script_as("1 + 2");
+/// assert_eq!(n, 3);
+/// assert_eq!(run_script_as::<f64, _>("Math.PI / 2"), std::f64::consts::FRAC_PI_2);
+/// let title: Option<String> = run_script_as("document.title");
+/// ```
+pub fn run_script_as<R, T>(script: T) -> R
+where
+    R: FromScriptResult,
+    T: AsRef<str>,
+{
+    R::from_script(script.as_ref())
+}
+
+// The function set by `set_unhandled_rejection_handler`.
+type UnhandledRejectionHandler = Box<dyn FnMut(String)>;
+
+// It's `None` while it runs, so that it can be replaced from inside itself.
+// The generation is bumped each time it's set or removed, so that it can tell whether that happened while it ran.
+thread_local! {
+    static UNHANDLED_REJECTION_HANDLER: RefCell<Option<UnhandledRejectionHandler>> = const { RefCell::new(None) };
+    static UNHANDLED_REJECTION_HANDLER_GENERATION: Cell<u64> = const { Cell::new(0) };
+    static UNHANDLED_REJECTION_LISTENER_SET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Replaces the function set by `set_unhandled_rejection_handler`, returning the previous one.
+fn replace_unhandled_rejection_handler(
+    handler: Option<UnhandledRejectionHandler>,
+) -> Option<UnhandledRejectionHandler> {
+    UNHANDLED_REJECTION_HANDLER_GENERATION
+        .with(|generation| generation.set(generation.get().wrapping_add(1)));
+    UNHANDLED_REJECTION_HANDLER.with(|handler_ref| handler_ref.replace(handler))
+}
+
+// Calls the function set by `set_unhandled_rejection_handler`, if any, with the given reason.
+fn call_unhandled_rejection_handler(reason: String) {
+    let handler = UNHANDLED_REJECTION_HANDLER.with(|handler_ref| handler_ref.borrow_mut().take());
+    let Some(mut handler) = handler else {
+        return;
+    };
+    let generation = UNHANDLED_REJECTION_HANDLER_GENERATION.with(Cell::get);
+
+    handler(reason);
+
+    // The function goes back in its place, unless it got replaced or removed while it ran.
+    if UNHANDLED_REJECTION_HANDLER_GENERATION.with(Cell::get) == generation {
+        UNHANDLED_REJECTION_HANDLER.with(|handler_ref| *handler_ref.borrow_mut() = Some(handler));
+    }
+}
+
+// Called by the `unhandledrejection` event listener installed by `set_unhandled_rejection_handler`.
+// Passing a string from JavaScript would need the page to export memory allocation functions too,
+// so the listener leaves the reason on the `Module` object, and it's read from here with a script.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn emscripten_functions_unhandled_rejection() {
+    let reason = run_script_string(
+        r#"
+            (function () {
+                var reason = Module["emscriptenFunctionsRejectionReason"];
+                delete Module["emscriptenFunctionsRejectionReason"];
+                return reason;
+            })()
+        "#,
+    )
+    .unwrap_or_default();
+
+    call_unhandled_rejection_handler(reason);
+}
+
+/// Sets the function to be called when a JavaScript promise is rejected without a rejection handler,
+/// with the [`unhandledrejection`] event, e.g. when an async script started with [`run_script`] fails.
+/// It replaces the function previously set.
+///
+/// The function is given the rejection reason as a string: the stack trace for an `Error`, if there is one,
+/// or the reason turned into a string otherwise.
+///
+/// The events reach the function through an exported function,
+/// so `_emscripten_functions_unhandled_rejection` must be added to the `-sEXPORTED_FUNCTIONS` link argument.
+///
+/// [`unhandledrejection`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/unhandledrejection_event
+///
+/// # Arguments
+/// * `handler` - The function to call with the reason of each unhandled rejection.
+///
+/// # Examples
+/// ```rust
+/// set_unhandled_rejection_handler(|reason| {
+///     console::error(format!("Unhandled promise rejection: {}", reason));
+/// });
+///
+/// run_script("fetch('/missing.json').then((response) => response.json())");
+/// ```
+pub fn set_unhandled_rejection_handler<F>(handler: F)
+where
+    F: 'static + FnMut(String),
+{
+    drop(replace_unhandled_rejection_handler(Some(Box::new(handler))));
+
+    if !UNHANDLED_REJECTION_LISTENER_SET.with(|set| set.replace(true)) {
+        run_script(
+            r#"
+                window.addEventListener("unhandledrejection", function (event) {
+                    var reason = event.reason;
+                    try {
+                        reason = reason instanceof Error && reason.stack ? reason.stack : String(reason);
+                    } catch (e) {
+                        reason = Object.prototype.toString.call(reason);
+                    }
+                    Module["emscriptenFunctionsRejectionReason"] = reason;
+                    Module["_emscripten_functions_unhandled_rejection"]();
+                });
+            "#,
+        );
+    }
+}
+
+/// Removes the function set with [`set_unhandled_rejection_handler`], which doesn't get called anymore.
+/// It can be called from inside that function too.
+///
+/// # Examples
+/// ```rust
+/// remove_unhandled_rejection_handler();
+/// ```
+pub fn remove_unhandled_rejection_handler() {
+    drop(replace_unhandled_rejection_handler(None));
+}
+
+// A function given to `request_idle_callback`.
+type IdleCallback = Box<dyn FnOnce(f64)>;
+
+// The functions given to `request_idle_callback` that haven't been called yet, under the id given to the JavaScript callback.
+thread_local! {
+    static IDLE_CALLBACKS: RefCell<HashMap<c_int, IdleCallback>> = RefCell::new(HashMap::new());
+    static LAST_IDLE_CALLBACK_ID: Cell<c_int> = const { Cell::new(0) };
+}
+
+// Called by the JavaScript callback installed by `request_idle_callback`.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn emscripten_functions_idle_callback(id: c_int, time_remaining: c_double) {
+    let callback = IDLE_CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().remove(&id));
+    if let Some(callback) = callback {
+        callback(time_remaining);
+    }
+}
+
+/// Calls the given function once the browser is idle, using the [`requestIdleCallback()`] JS function,
+/// e.g. for background work like warming up caches, without taking time from the frames.
+///
+/// The function is given the number of milliseconds left until the browser needs the thread again.
+/// Where `requestIdleCallback()` isn't available, e.g. in Safari, the function is called with `setTimeout()` instead,
+/// and it's given 50 milliseconds, the most that the browsers give.
+///
+/// The function reaches the page through an exported function,
+/// so `_emscripten_functions_idle_callback` must be added to the `-sEXPORTED_FUNCTIONS` link argument.
+///
+/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+///
+/// # Arguments
+/// * `func` - The function to call once the browser is idle.
+///
+/// # Examples
+/// ```rust
+/// request_idle_callback(|time_remaining| {
+///     let start = get_now();
+///     while get_now() - start < time_remaining - 1.0 && cache.warm_next() {}
+/// });
+/// ```
+pub fn request_idle_callback<F>(func: F)
+where
+    F: 'static + FnOnce(f64),
+{
+    let id = LAST_IDLE_CALLBACK_ID.with(|last_id| last_id.get().wrapping_add(1));
+    LAST_IDLE_CALLBACK_ID.with(|last_id| last_id.set(id));
+    IDLE_CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref.borrow_mut().insert(id, Box::new(func));
+    });
+
+    run_script(format!(
+        r#"
+            if (typeof requestIdleCallback == "function") {{
+                requestIdleCallback(function (deadline) {{
+                    Module["_emscripten_functions_idle_callback"]({id}, deadline.timeRemaining());
+                }});
+            }} else {{
+                setTimeout(function () {{
+                    Module["_emscripten_functions_idle_callback"]({id}, 50);
+                }}, 0);
+            }}
+        "#
+    ));
+}
+
+// The functions defined in `asm_in_main_thread.c`.
+extern "C" {
+    fn asm_in_main_thread(script: *const c_char);
+    fn asm_in_main_thread_int(script: *const c_char) -> c_int;
+    fn asm_in_main_thread_double(script: *const c_char) -> c_double;
+    fn call_in_main_thread(func: unsafe extern "C" fn(*mut c_void), arg: *mut c_void);
+    fn compiler_setting_kind(name: *const c_char) -> c_int;
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
+/// using the emscripten-defined [`MAIN_THREAD_EM_ASM`].
+///
+/// If you need to run the script in the calling thread, check out [`run_script`].
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`MAIN_THREAD_EM_ASM`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// run_script_main_thread("alert('Hello world')");
+///
+/// // The `.escape_unicode()` method makes it safe to pass untrusted user input.
+/// run_script_main_thread(
+///     format!(
+///         r##"
+///             document.querySelector("#this-is-secure").innerHTML = "{}"
+///         "##,
+///         "untrusted user input".escape_unicode()
+///     )
+/// );
+/// ```
+pub fn run_script_main_thread<T>(script: T)
+where
+    T: AsRef<str>,
+{
+    let script_cstring = to_cstring_lossy(script.as_ref());
+    unsafe { asm_in_main_thread(script_cstring.as_ptr()) }
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
+/// using the emscripten-defined [`MAIN_THREAD_EM_ASM_INT`].
+/// It returns the return result of the script, interpreted as a C int.
+/// Most probably the return result is passed to [`parseInt()`], with NaN represented as 0.
+///
+/// If you need to run the script in the calling thread, check out [`run_script_int`].
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`MAIN_THREAD_EM_ASM_INT`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM_INT
+/// [`parseInt()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/parseInt
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(run_script_main_thread_int("1 + 2"), 3);
+/// ```
+pub fn run_script_main_thread_int<T>(script: T) -> c_int
+where
+    T: AsRef<str>,
+{
+    let script_cstring = to_cstring_lossy(script.as_ref());
+    unsafe { asm_in_main_thread_int(script_cstring.as_ptr()) }
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function, in the main thread,
+/// using the emscripten-defined [`MAIN_THREAD_EM_ASM_DOUBLE`].
+/// It returns the return result of the script, interpreted as a C double.
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`MAIN_THREAD_EM_ASM_DOUBLE`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.MAIN_THREAD_EM_ASM_DOUBLE
+///
+/// # Arguments
+/// * `script` - The script to execute.
+///
+/// # Examples
+/// ```rust
+/// assert_eq!(run_script_main_thread_double("1.1 + 2.1"), 3.2);
+/// ```
+pub fn run_script_main_thread_double<T>(script: T) -> c_double
+where
+    T: AsRef<str>,
+{
+    let script_cstring = to_cstring_lossy(script.as_ref());
+    unsafe { asm_in_main_thread_double(script_cstring.as_ptr()) }
+}
+
+/// Calls the given function in the main runtime thread, i.e. the browser's main thread most of the time,
+/// blocking until it returns, and returns its result, using the emscripten-defined `emscripten_sync_run_in_main_runtime_thread`.
+/// It's meant for the code running in a pthread that needs something only the main thread can do, like a WebGL query.
+///
+/// The function is called exactly once. From the main thread itself, or in a build without threads, it's called right away.
+/// If it panics, the panic is resumed in the calling thread, once the main thread is done with it.
+///
+/// # Arguments
+/// * `func` - The function to call in the main thread.
+///
+/// # Examples
+/// ```rust
+/// // In a worker pthread:
+/// let canvas_size = run_in_main_thread(|| html5::get_canvas_element_size("#canvas"));
+/// ```
+pub fn run_in_main_thread<F, R>(func: F) -> R
+where
+    F: FnOnce() -> R + Send,
+    R: Send,
+{
+    // The function and its result live on the stack of the calling thread, which waits for the main thread to be done with them.
+    // The blocking call synchronizes both threads, so the result written by the main thread is visible once it returns.
+    // The report left by the panic hook in the main thread, if the function panicked, goes along with the result.
+    struct Call<F, R> {
+        func: Option<F>,
+        result: Option<thread::Result<R>>,
+        panic_report: Option<String>,
+    }
+
+    unsafe extern "C" fn wrapper<F, R>(arg: *mut c_void)
+    where
+        F: FnOnce() -> R + Send,
+        R: Send,
+    {
+        let call = unsafe { &mut *(arg as *mut Call<F, R>) };
+        if let Some(func) = call.func.take() {
+            // A panic must not unwind out of this `extern "C"` function, so it's caught and handed to the calling thread.
+            let result = panic::catch_unwind(AssertUnwindSafe(func));
+            if result.is_err() {
+                call.panic_report = take_panic_report();
+            }
+            call.result = Some(result);
+        }
+    }
+
+    let mut call = Call {
+        func: Some(func),
+        result: None,
+        panic_report: None,
+    };
+    unsafe {
+        call_in_main_thread(wrapper::<F, R>, &mut call as *mut Call<F, R> as *mut c_void);
+    }
+
+    match call.result {
+        Some(Ok(result)) => result,
+        Some(Err(payload)) => {
+            if let Some(report) = call.panic_report {
+                PENDING_PANIC_REPORT.with(|pending| pending.replace(Some(report)));
+            }
+            panic::resume_unwind(payload)
+        }
+        None => unreachable!("the main thread didn't run the function"),
+    }
+}
+
+// Calls the given function once, after the given number of milliseconds, using `emscripten_set_timeout`.
+pub(crate) fn set_timeout<F>(delay_ms: f64, func: F)
+where
+    F: 'static + FnOnce(),
+{
+    unsafe extern "C" fn wrapper<F>(user_data: *mut c_void)
+    where
+        F: 'static + FnOnce(),
+    {
+        let func = unsafe { Box::from_raw(user_data as *mut F) };
+        func();
+    }
+
+    unsafe {
+        html5::emscripten_set_timeout(
+            Some(wrapper::<F>),
+            delay_ms,
+            Box::into_raw(Box::new(func)) as *mut c_void,
+        );
+    }
+}
+
+// Turns the given duration into milliseconds, saturated to the longest delay the browsers support, about 24.8 days.
+// Browsers treat longer delays as zero.
+fn duration_to_ms(duration: Duration) -> f64 {
+    (duration.as_secs_f64() * 1000.0).min(c_int::MAX as f64)
+}
+
+/// Calls the given function once, after the given delay, using the emscripten-defined [`emscripten_set_timeout`].
+///
+/// The delay is given to the browser's `setTimeout()` with its sub-millisecond part, which browsers round down.
+/// Delays longer than about 24.8 days are shortened to that, as browsers treat them as zero otherwise.
+///
+/// [`emscripten_set_timeout`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_timeout
+///
+/// # Arguments
+/// * `func` - The function to call.
+/// * `delay` - The time to wait before calling the function.
+///
+/// # Examples
+/// ```rust
+/// set_timeout_dur(|| println!("Half a second later"), Duration::from_millis(500));
+/// ```
+pub fn set_timeout_dur<F>(func: F, delay: Duration)
+where
+    F: 'static + FnOnce(),
+{
+    set_timeout(duration_to_ms(delay), func);
+}
+
+/// Calls the given function once, after the given delay, using the emscripten-defined [`emscripten_async_call`].
+///
+/// Unlike with [`set_timeout_dur`], the sub-millisecond part of the delay is truncated, as emscripten takes a whole number of milliseconds.
+/// Delays longer than about 24.8 days are shortened to that.
+///
+/// [`emscripten_async_call`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_call
+///
+/// # Arguments
+/// * `func` - The function to call.
+/// * `delay` - The time to wait before calling the function.
+///
+/// # Examples
+/// ```rust
+/// async_call_dur(|| println!("One second later"), Duration::from_secs(1));
+/// ```
+pub fn async_call_dur<F>(func: F, delay: Duration)
+where
+    F: 'static + FnOnce(),
+{
+    unsafe extern "C" fn wrapper<F>(arg: *mut c_void)
+    where
+        F: 'static + FnOnce(),
+    {
+        let func = unsafe { Box::from_raw(arg as *mut F) };
+        func();
+    }
+
+    // The cast truncates the sub-millisecond part.
+    let delay_ms = duration_to_ms(delay) as c_int;
+    unsafe {
+        emscripten::emscripten_async_call(
+            Some(wrapper::<F>),
+            Box::into_raw(Box::new(func)) as *mut c_void,
+            delay_ms,
+        );
+    }
+}
+
+type IntervalFunction = Box<dyn FnMut()>;
+
+// The functions of the intervals set by `set_interval_dur`, keyed by an id of ours, given to emscripten as user data.
+// Each one is `None` while it runs, so that its interval can be cleared from inside it.
+thread_local! {
+    static INTERVALS: RefCell<HashMap<usize, Option<IntervalFunction>>> = RefCell::new(HashMap::new());
+    static LAST_INTERVAL_ID: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe extern "C" fn interval_wrapper(user_data: *mut c_void) {
+    let id = user_data as usize;
+    let func = INTERVALS.with(|intervals_ref| {
+        intervals_ref
+            .borrow_mut()
+            .get_mut(&id)
+            .and_then(Option::take)
+    });
+    let Some(mut func) = func else {
+        return;
+    };
+
+    func();
+
+    // The function goes back in its place, unless its interval got cleared while it ran.
+    INTERVALS.with(|intervals_ref| {
+        if let Some(stored) = intervals_ref.borrow_mut().get_mut(&id) {
+            *stored = Some(func);
+        }
+    });
+}
+
+/// The handle of an interval set by [`set_interval_dur`], which clears the interval when it's dropped.
+///
+/// To keep the interval for the rest of the program, use [`std::mem::forget`] on it.
+#[must_use]
+#[derive(Debug)]
+pub struct Interval {
+    id: usize,
+    emscripten_id: c_int,
+}
+impl Drop for Interval {
+    fn drop(&mut self) {
+        unsafe {
+            html5::emscripten_clear_interval(self.emscripten_id);
+        }
+
+        // The function gets dropped outside of the borrow, in case its destructor touches the registry.
+        let func = INTERVALS.with(|intervals_ref| intervals_ref.borrow_mut().remove(&self.id));
+        drop(func);
+    }
+}
+
+/// Calls the given function repeatedly, with the given period, using the emscripten-defined [`emscripten_set_interval`].
+/// The calls stop once the returned [`Interval`] gets dropped.
+///
+/// The period is given to the browser's `setInterval()` with its sub-millisecond part, which browsers round down.
+/// Periods longer than about 24.8 days are shortened to that, as browsers treat them as zero otherwise.
+///
+/// [`emscripten_set_interval`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_interval
+///
+/// # Arguments
+/// * `func` - The function to call.
+/// * `period` - The time between the calls of the function.
+///
+/// # Examples
+/// ```rust
+/// let autosave = set_interval_dur(|| save_game(), Duration::from_secs(60));
+/// // The game stops saving once `autosave` is dropped.
+/// ```
+pub fn set_interval_dur<F>(func: F, period: Duration) -> Interval
+where
+    F: 'static + FnMut(),
+{
+    let id = LAST_INTERVAL_ID.with(|last_id| last_id.get() + 1);
+    LAST_INTERVAL_ID.with(|last_id| last_id.set(id));
+    INTERVALS.with(|intervals_ref| {
+        intervals_ref.borrow_mut().insert(id, Some(Box::new(func)));
+    });
+
+    let emscripten_id = unsafe {
+        html5::emscripten_set_interval(
+            Some(interval_wrapper),
+            duration_to_ms(period),
+            id as *mut c_void,
+        )
+    };
+
+    Interval { id, emscripten_id }
+}
+
+/// Runs the given JavaScript script string with the [`eval()`] JS function after the given delay,
+/// like the emscripten-defined [`emscripten_async_run_script`].
+///
+/// Emscripten's function reads the script only when running it, so it would need the string to outlive the call.
+/// Instead, this function keeps its own copy of the script, which is dropped once it ran.
+///
+/// [`eval()`]: https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/eval
+/// [`emscripten_async_run_script`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_run_script
+///
+/// # Arguments
+/// * `script` - The script to execute.
+/// * `delay` - The time to wait before running the script.
+///
+/// # Examples
+/// ```rust
+/// async_run_script("alert('Five seconds later')", Duration::from_secs(5));
+/// ```
+pub fn async_run_script<T>(script: T, delay: Duration)
+where
+    T: AsRef<str>,
+{
+    let script_cstring = to_cstring_lossy(script.as_ref());
+
+    set_timeout(duration_to_ms(delay), move || unsafe {
+        emscripten::emscripten_run_script(script_cstring.as_ptr());
+    });
+}
+
+// The callbacks of a pending `async_load_script` call, of which only one gets called.
+type ScriptLoadCallbacks = (Box<dyn FnOnce()>, Box<dyn FnOnce()>);
+
+// `emscripten_async_load_script` gives no argument to its callbacks, so each pending load gets its own pair of callbacks,
+// taken from a fixed set of functions that only differ by the slot of this registry they look into.
+const SCRIPT_LOAD_SLOTS: usize = 8;
+thread_local! {
+    static SCRIPT_LOADS: RefCell<[Option<ScriptLoadCallbacks>; SCRIPT_LOAD_SLOTS]> = RefCell::new(Default::default());
+}
+
+unsafe extern "C" fn script_onload_wrapper<const SLOT: usize>() {
+    let callbacks = SCRIPT_LOADS.with(|loads_ref| loads_ref.borrow_mut()[SLOT].take());
+    if let Some((on_load, _)) = callbacks {
+        on_load();
+    }
+}
+
+unsafe extern "C" fn script_onerror_wrapper<const SLOT: usize>() {
+    let callbacks = SCRIPT_LOADS.with(|loads_ref| loads_ref.borrow_mut()[SLOT].take());
+    if let Some((_, on_error)) = callbacks {
+        on_error();
+    }
+}
+
+const SCRIPT_ONLOAD_WRAPPERS: [unsafe extern "C" fn(); SCRIPT_LOAD_SLOTS] = [
+    script_onload_wrapper::<0>,
+    script_onload_wrapper::<1>,
+    script_onload_wrapper::<2>,
+    script_onload_wrapper::<3>,
+    script_onload_wrapper::<4>,
+    script_onload_wrapper::<5>,
+    script_onload_wrapper::<6>,
+    script_onload_wrapper::<7>,
+];
+
+const SCRIPT_ONERROR_WRAPPERS: [unsafe extern "C" fn(); SCRIPT_LOAD_SLOTS] = [
+    script_onerror_wrapper::<0>,
+    script_onerror_wrapper::<1>,
+    script_onerror_wrapper::<2>,
+    script_onerror_wrapper::<3>,
+    script_onerror_wrapper::<4>,
+    script_onerror_wrapper::<5>,
+    script_onerror_wrapper::<6>,
+    script_onerror_wrapper::<7>,
+];
+
+/// Loads the JavaScript file at the given URL by adding a `<script>` element to the page, using the emscripten-defined [`emscripten_async_load_script`].
+/// Once the script has run, `on_load` is called, otherwise `on_error` is called if it couldn't be loaded.
+///
+/// At most 8 scripts can be loading at the same time.
+///
+/// [`emscripten_async_load_script`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_load_script
+///
+/// # Arguments
+/// * `url` - The URL of the script.
+/// * `on_load` - The function to call once the script has run.
+/// * `on_error` - The function to call if the script couldn't be loaded.
+///
+/// # Return value
+/// Returns `false` if there are already 8 scripts loading, in which case this one isn't loaded and none of the functions is called.
+///
+/// # Examples
+/// ```rust
+/// async_load_script(
+///     "https://example.com/analytics.js",
+///     || run_script("analytics.track('start')"),
+///     || console::warn("Analytics are unavailable"),
+/// );
+/// ```
+pub fn async_load_script<T, F, G>(url: T, on_load: F, on_error: G) -> bool
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(),
+    G: 'static + FnOnce(),
+{
+    let slot = SCRIPT_LOADS.with(|loads_ref| {
+        let mut loads = loads_ref.borrow_mut();
+        let slot = loads.iter().position(|load| load.is_none())?;
+        loads[slot] = Some((Box::new(on_load), Box::new(on_error)));
+        Some(slot)
+    });
+    let Some(slot) = slot else {
+        return false;
+    };
+
+    // Emscripten copies the URL into a JavaScript string right away.
+    let url_cstring = to_cstring_lossy(url.as_ref());
+    unsafe {
+        emscripten::emscripten_async_load_script(
+            url_cstring.as_ptr(),
+            Some(SCRIPT_ONLOAD_WRAPPERS[slot]),
+            Some(SCRIPT_ONERROR_WRAPPERS[slot]),
+        );
+    }
+
+    true
+}
+
+// A success and an error callback receiving a file path, of which only one gets called.
+type PathCallbacks = (Box<dyn FnOnce(&str)>, Box<dyn FnOnce(&str)>);
+
+// The callbacks of the pending `async_wget` downloads, stored together under the download's (resolved) destination path.
+// `emscripten_async_wget` doesn't let us pass any user data to its callbacks, just the destination path, so that's what we use to find them.
+// When a download finishes, both of its callbacks are removed from here, no matter which one gets called.
+thread_local! {
+    static ASYNC_WGET_CALLBACKS: RefCell<HashMap<String, PathCallbacks>> = RefCell::new(HashMap::new());
+}
+
+// Emscripten resolves the destination path against the current directory before giving it to the callbacks,
+// so we have to do the same thing to find the callbacks of a download.
+fn resolve_path(path: &str) -> String {
+    let full_path = if path.starts_with('/') {
+        path.to_string()
+    } else {
+        let current_dir = std::env::current_dir()
+            .map(|dir| dir.to_string_lossy().into_owned())
+            .unwrap_or_else(|_| "/".to_string());
+        format!("{}/{}", current_dir, path)
+    };
+
+    let mut parts = Vec::new();
+    for part in full_path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+
+    format!("/{}", parts.join("/"))
+}
+
+/// Downloads the file at the given URL into the emscripten virtual file system at the given path, asynchronously,
+/// using the emscripten-defined [`emscripten_async_wget`].
+/// When the download finishes, either `on_load` or `on_error` gets called with the destination path, and both get dropped.
+///
+/// Starting another download to the same destination path before this one finishes replaces the callbacks of this one.
+///
+/// [`emscripten_async_wget`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_wget
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+/// * `dest_path` - The path in the virtual file system where the file will be saved.
+/// * `on_load` - The function to call with the destination path once the file is downloaded.
+/// * `on_error` - The function to call with the destination path if the download fails.
+///
+/// # Examples
+/// ```rust
+/// let texture_creator = canvas.texture_creator();
+///
+/// async_wget(
+///     "https://example.com/square.png",
+///     "square.png",
+///     move |file| {
+///         // The file is now in the virtual file system, so SDL_image can load it.
+///         let texture = texture_creator.load_texture(file).unwrap();
+///         // ...
+///     },
+///     |file| {
+///         println!("Couldn't download {}", file);
+///     },
+/// );
+/// ```
+pub fn async_wget<T, U, F, G>(url: T, dest_path: U, on_load: F, on_error: G)
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    F: 'static + FnOnce(&str),
+    G: 'static + FnOnce(&str),
+{
+    let url_cstring = to_cstring_lossy(url.as_ref());
+    let dest_path_cstring = to_cstring_lossy(dest_path.as_ref());
+
+    ASYNC_WGET_CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref.borrow_mut().insert(
+            resolve_path(&dest_path_cstring.to_string_lossy()),
+            (Box::new(on_load), Box::new(on_error)),
+        );
+    });
+
+    unsafe extern "C" fn onload_wrapper(file: *const c_char) {
+        let file = unsafe { CStr::from_ptr(file) }.to_string_lossy();
+        let callbacks = ASYNC_WGET_CALLBACKS
+            .with(|callbacks_ref| callbacks_ref.borrow_mut().remove(file.as_ref()));
+
+        if let Some((on_load, _)) = callbacks {
+            on_load(&file);
+        }
+    }
+
+    unsafe extern "C" fn onerror_wrapper(file: *const c_char) {
+        let file = unsafe { CStr::from_ptr(file) }.to_string_lossy();
+        let callbacks = ASYNC_WGET_CALLBACKS
+            .with(|callbacks_ref| callbacks_ref.borrow_mut().remove(file.as_ref()));
+
+        if let Some((_, on_error)) = callbacks {
+            on_error(&file);
+        }
+    }
+
+    unsafe {
+        emscripten::emscripten_async_wget(
+            url_cstring.as_ptr(),
+            dest_path_cstring.as_ptr(),
+            Some(onload_wrapper),
+            Some(onerror_wrapper),
+        );
+    }
+}
+
+/// Downloads the file at the given URL straight into memory, asynchronously, using the emscripten-defined [`emscripten_async_wget_data`].
+/// When the download finishes, either `on_load` gets called with the file's contents or `on_error` gets called, and both get dropped.
+///
+/// Unlike [`async_wget`], this doesn't touch the virtual file system at all.
+///
+/// [`emscripten_async_wget_data`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_wget_data
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+/// * `on_load` - The function to call with the contents of the file once it's downloaded.
+/// * `on_error` - The function to call if the download fails.
+///
+/// # Examples
+/// ```rust
+/// async_wget_data(
+///     "config.json",
+///     |data| {
+///         println!("The config is: {}", String::from_utf8_lossy(&data));
+///     },
+///     || {
+///         println!("Couldn't download the config");
+///     },
+/// );
+/// ```
+pub fn async_wget_data<T, F, G>(url: T, on_load: F, on_error: G)
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(Vec<u8>),
+    G: 'static + FnOnce(),
+{
+    let url_cstring = to_cstring_lossy(url.as_ref());
+
+    // Both callbacks travel together through the `arg` pointer, and whichever gets called takes back the ownership of both.
+    let callbacks = Box::into_raw(Box::new((on_load, on_error)));
+
+    unsafe extern "C" fn onload_wrapper<F, G>(arg: *mut c_void, data: *mut c_void, size: c_int)
+    where
+        F: 'static + FnOnce(Vec<u8>),
+        G: 'static + FnOnce(),
+    {
+        let (on_load, _) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+
+        // The buffer is valid only during this call, so we copy it.
+        let data = if data.is_null() || size <= 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) }.to_vec()
+        };
+        on_load(data);
+    }
+
+    unsafe extern "C" fn onerror_wrapper<F, G>(arg: *mut c_void)
+    where
+        F: 'static + FnOnce(Vec<u8>),
+        G: 'static + FnOnce(),
+    {
+        let (_, on_error) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+        on_error();
+    }
+
+    unsafe {
+        emscripten::emscripten_async_wget_data(
+            url_cstring.as_ptr(),
+            callbacks as *mut c_void,
+            Some(onload_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+/// The error returned by the blocking download functions, like [`wget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WgetError {
+    /// The download failed, e.g. because of a network error or an HTTP error status.
+    ///
+    /// Emscripten doesn't tell the reason of the failure.
+    Failed,
+    /// The program wasn't built with `-sASYNCIFY`, so the download wasn't attempted.
+    AsyncifyUnavailable,
+}
+impl Display for WgetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WgetError::Failed => write!(f, "The download failed"),
+            WgetError::AsyncifyUnavailable => write!(f, "{}", AsyncifyUnavailable),
+        }
+    }
+}
+impl From<AsyncifyUnavailable> for WgetError {
+    fn from(_: AsyncifyUnavailable) -> Self {
+        WgetError::AsyncifyUnavailable
+    }
+}
+
+/// Downloads the file at the given URL into the emscripten virtual file system at the given path, blocking until it's done,
+/// using the emscripten-defined [`emscripten_wget`].
+/// Once it returns, the file can be read with the usual [`std::fs`] functions.
+///
+/// The whole program waits for the download, with nothing else of it running meanwhile, e.g. its main loop and its event callbacks,
+/// so it's meant for the one-time loading at startup. The browser's event loop keeps running, so the page doesn't freeze.
+///
+/// Requires building with `-sASYNCIFY`, otherwise it returns [`WgetError::AsyncifyUnavailable`] without downloading.
+/// For the other builds, check out [`async_wget`].
+///
+/// [`emscripten_wget`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_wget
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+/// * `file` - The path in the virtual file system where the file will be saved.
+///
+/// # Examples
+/// ```rust
+/// wget("https://example.com/level1.json", "level1.json").unwrap();
+/// let level = std::fs::read_to_string("level1.json").unwrap();
+/// ```
+pub fn wget<T, U>(url: T, file: U) -> Result<(), WgetError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    require_asyncify()?;
+
+    let url_cstring = to_cstring_lossy(url.as_ref());
+    let file_cstring = to_cstring_lossy(file.as_ref());
+
+    let result =
+        unsafe { emscripten::emscripten_wget(url_cstring.as_ptr(), file_cstring.as_ptr()) };
+    if result != 0 {
+        return Err(WgetError::Failed);
+    }
+    Ok(())
+}
+
+/// Downloads the file at the given URL straight into memory, blocking until it's done,
+/// using the emscripten-defined [`emscripten_wget_data`].
+///
+/// Unlike [`wget`], this doesn't touch the virtual file system at all, e.g. for a quick download of a config file at startup.
+/// Like it, the whole program waits for the download.
+///
+/// Requires building with `-sASYNCIFY`, otherwise it returns [`WgetError::AsyncifyUnavailable`] without downloading.
+/// For the other builds, check out [`async_wget_data`].
+///
+/// [`emscripten_wget_data`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_wget_data
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+///
+/// # Examples
+/// ```rust
+/// let config = wget_data("config.json").unwrap();
+/// println!("The config is: {}", String::from_utf8_lossy(&config));
+/// ```
+pub fn wget_data<T>(url: T) -> Result<Vec<u8>, WgetError>
+where
+    T: AsRef<str>,
+{
+    require_asyncify()?;
+
+    let url_cstring = to_cstring_lossy(url.as_ref());
+
+    let mut buffer: *mut c_void = null_mut();
+    let mut num: c_int = 0;
+    let mut error: c_int = 0;
+    unsafe {
+        emscripten::emscripten_wget_data(url_cstring.as_ptr(), &mut buffer, &mut num, &mut error);
+    }
+
+    // The buffer, if any, is allocated with `malloc`, and it's ours to free, even on error.
+    let data = if buffer.is_null() || num <= 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(buffer as *const u8, num as usize) }.to_vec()
+    };
+    if !buffer.is_null() {
+        unsafe { free(buffer) };
+    }
+
+    if error != 0 {
+        return Err(WgetError::Failed);
+    }
+    Ok(data)
+}
+
+/// The HTTP method of a request made with [`async_wget2`] or [`async_wget2_data`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+impl HttpMethod {
+    fn as_cstr(&self) -> &'static CStr {
+        match self {
+            HttpMethod::Get => c"GET",
+            HttpMethod::Post => c"POST",
+        }
+    }
+}
+
+/// The functions to call during and after a download started with [`async_wget2`].
+///
+/// # Examples
+/// ```rust
+/// let handlers = WgetHandlers::new(
+///     |file| println!("Downloaded {}", file),
+///     |status| println!("The download failed with HTTP status {}", status),
+/// )
+/// .on_progress(|percent| println!("{}% done", percent));
+/// ```
+pub struct WgetHandlers {
+    on_load: Box<dyn FnOnce(&str)>,
+    on_error: Box<dyn FnOnce(u16)>,
+    on_progress: Option<Box<dyn FnMut(u32)>>,
+}
+impl WgetHandlers {
+    /// Creates the handlers of a download, with no progress reporting.
+    ///
+    /// # Arguments
+    /// * `on_load` - The function to call with the destination path once the file is downloaded.
+    /// * `on_error` - The function to call with the HTTP status code if the download fails.
+    pub fn new<F, G>(on_load: F, on_error: G) -> Self
+    where
+        F: 'static + FnOnce(&str),
+        G: 'static + FnOnce(u16),
+    {
+        WgetHandlers {
+            on_load: Box::new(on_load),
+            on_error: Box::new(on_error),
+            on_progress: None,
+        }
+    }
+
+    /// Sets the function to call with the percentage of the download that is done, each time it progresses.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: 'static + FnMut(u32),
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+}
+
+// The handlers of the pending `async_wget2` downloads, keyed by their request handle.
+// They're dropped when the download finishes or gets aborted, since emscripten calls no callback for aborted downloads.
+thread_local! {
+    static ASYNC_WGET2_HANDLERS: RefCell<HashMap<c_int, WgetHandlers>> = RefCell::new(HashMap::new());
+}
+
+/// A handle to a download started with [`async_wget2`] or [`async_wget2_data`], that can be used to abort it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WgetHandle {
+    handle: c_int,
+}
+impl WgetHandle {
+    /// Aborts the download, using the emscripten-defined [`emscripten_async_wget2_abort`].
+    /// None of its handlers will be called afterwards, and they get dropped.
+    ///
+    /// Aborting a download that has already finished does nothing.
+    ///
+    /// [`emscripten_async_wget2_abort`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_wget2_abort
+    pub fn abort(&self) {
+        unsafe {
+            emscripten::emscripten_async_wget2_abort(self.handle);
+        }
+
+        // Both kinds of downloads share the same handle numbering, so the handle is in at most one of the registries.
+        let handlers = ASYNC_WGET2_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&self.handle));
+        drop(handlers);
+        let handlers = ASYNC_WGET2_DATA_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&self.handle));
+        drop(handlers);
+    }
+}
+
+/// Downloads the file at the given URL into the emscripten virtual file system at the given path, asynchronously,
+/// using the emscripten-defined [`emscripten_async_wget2`].
+///
+/// Unlike [`async_wget`], it supports POST requests, reports the HTTP status on failure and the download's progress,
+/// and it returns a [`WgetHandle`] that can abort the download.
+///
+/// [`emscripten_async_wget2`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_wget2
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+/// * `dest_path` - The path in the virtual file system where the file will be saved.
+/// * `method` - The HTTP method of the request.
+/// * `post_params` - The parameters of a POST request, if any.
+/// * `handlers` - The functions to call during and after the download.
+///
+/// # Examples
+/// ```rust
+/// let handle = async_wget2(
+///     "https://example.com/assets.pak",
+///     "assets.pak",
+///     HttpMethod::Get,
+///     None,
+///     WgetHandlers::new(
+///         |file| println!("Downloaded {}", file),
+///         |status| println!("The download failed with HTTP status {}", status),
+///     )
+///     .on_progress(|percent| println!("{}% done", percent)),
+/// );
+///
+/// // Changed our mind.
+/// handle.abort();
+/// ```
+pub fn async_wget2<T, U>(
+    url: T,
+    dest_path: U,
+    method: HttpMethod,
+    post_params: Option<&str>,
+    handlers: WgetHandlers,
+) -> WgetHandle
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    let url_cstring = to_cstring_lossy(url.as_ref());
+    let dest_path_cstring = to_cstring_lossy(dest_path.as_ref());
+    let post_params_cstring = to_cstring_lossy(post_params.unwrap_or(""));
+
+    unsafe extern "C" fn onload_wrapper(handle: u32, _arg: *mut c_void, file: *const c_char) {
+        let handlers = ASYNC_WGET2_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&(handle as c_int)));
+
+        if let Some(handlers) = handlers {
+            let file = unsafe { CStr::from_ptr(file) }.to_string_lossy();
+            (handlers.on_load)(&file);
+        }
+    }
+
+    unsafe extern "C" fn onerror_wrapper(handle: u32, _arg: *mut c_void, status: c_int) {
+        let handlers = ASYNC_WGET2_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&(handle as c_int)));
+
+        if let Some(handlers) = handlers {
+            (handlers.on_error)(status as u16);
+        }
+    }
+
+    unsafe extern "C" fn onprogress_wrapper(handle: u32, _arg: *mut c_void, percent: c_int) {
+        // The progress function is taken out while it runs, so that the download can be aborted from inside it.
+        let on_progress = ASYNC_WGET2_HANDLERS.with(|handlers_ref| {
+            handlers_ref
+                .borrow_mut()
+                .get_mut(&(handle as c_int))
+                .and_then(|handlers| handlers.on_progress.take())
+        });
+
+        if let Some(mut on_progress) = on_progress {
+            on_progress(percent as u32);
+
+            ASYNC_WGET2_HANDLERS.with(|handlers_ref| {
+                if let Some(handlers) = handlers_ref.borrow_mut().get_mut(&(handle as c_int)) {
+                    handlers.on_progress = Some(on_progress);
+                }
+            });
+        }
+    }
+
+    let handle = unsafe {
+        emscripten::emscripten_async_wget2(
+            url_cstring.as_ptr(),
+            dest_path_cstring.as_ptr(),
+            method.as_cstr().as_ptr(),
+            post_params_cstring.as_ptr(),
+            std::ptr::null_mut(),
+            Some(onload_wrapper),
+            Some(onerror_wrapper),
+            Some(onprogress_wrapper),
+        )
+    };
+
+    // The callbacks can't be called before the function returns the handle, so it's fine to store the handlers now.
+    ASYNC_WGET2_HANDLERS.with(|handlers_ref| {
+        handlers_ref.borrow_mut().insert(handle, handlers);
+    });
+
+    WgetHandle { handle }
+}
+
+/// The functions to call during and after a download started with [`async_wget2_data`].
+///
+/// # Examples
+/// ```rust
+/// let handlers = WgetDataHandlers::new(
+///     |data| println!("Downloaded {} bytes", data.len()),
+///     |status, message| println!("The download failed: {} {}", status, message),
+/// )
+/// .on_progress(|loaded, total| match total {
+///     Some(total) => println!("{}/{} bytes", loaded, total),
+///     None => println!("{} bytes", loaded),
+/// });
+/// ```
+pub struct WgetDataHandlers {
+    on_load: Box<dyn FnOnce(Vec<u8>)>,
+    on_error: Box<dyn FnOnce(u16, String)>,
+    on_progress: Option<WgetDataProgressFunction>,
+}
+type WgetDataProgressFunction = Box<dyn FnMut(u64, Option<u64>)>;
+impl WgetDataHandlers {
+    /// Creates the handlers of a download, with no progress reporting.
+    ///
+    /// # Arguments
+    /// * `on_load` - The function to call with the downloaded data.
+    /// * `on_error` - The function to call with the HTTP status code and status text if the download fails.
+    ///   A status code of 0 means that the request didn't get a response at all, e.g. because of a network failure.
+    pub fn new<F, G>(on_load: F, on_error: G) -> Self
+    where
+        F: 'static + FnOnce(Vec<u8>),
+        G: 'static + FnOnce(u16, String),
+    {
+        WgetDataHandlers {
+            on_load: Box::new(on_load),
+            on_error: Box::new(on_error),
+            on_progress: None,
+        }
+    }
+
+    /// Sets the function to call with the number of bytes downloaded so far and the total size of the download,
+    /// if the server reports it, each time the download progresses.
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: 'static + FnMut(u64, Option<u64>),
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+}
+
+// The handlers of the pending `async_wget2_data` downloads, keyed by their request handle, just like `ASYNC_WGET2_HANDLERS`.
+thread_local! {
+    static ASYNC_WGET2_DATA_HANDLERS: RefCell<HashMap<c_int, WgetDataHandlers>> = RefCell::new(HashMap::new());
+}
+
+/// Downloads the file at the given URL straight into memory, asynchronously, using the emscripten-defined [`emscripten_async_wget2_data`].
+///
+/// Unlike [`async_wget_data`], it supports POST requests, reports the HTTP status on failure and the download's progress,
+/// and it returns a [`WgetHandle`] that can abort the download.
+///
+/// The downloaded data is copied into the [`Vec`] given to the `on_load` handler.
+/// With `free` set to `true`, emscripten frees its own buffer once the handler returns,
+/// so both copies are alive while the handler runs.
+/// With `free` set to `false`, emscripten leaves the buffer to this function, which frees it before calling the handler,
+/// halving the peak memory use for big downloads.
+///
+/// [`emscripten_async_wget2_data`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_async_wget2_data
+///
+/// # Arguments
+/// * `url` - The URL of the file to download.
+/// * `method` - The HTTP method of the request.
+/// * `body` - The body of a POST request, if any.
+/// * `free` - Whether emscripten frees its buffer of the data, instead of this function, after the data is copied.
+/// * `handlers` - The functions to call during and after the download.
+///
+/// # Examples
+/// ```rust
+/// async_wget2_data(
+///     "https://example.com/api/scores",
+///     HttpMethod::Get,
+///     None,
+///     false,
+///     WgetDataHandlers::new(
+///         |data| println!("The scores are: {}", String::from_utf8_lossy(&data)),
+///         |status, message| {
+///             if status == 404 {
+///                 println!("There are no scores yet");
+///             } else {
+///                 println!("The download failed: {} {}", status, message);
+///             }
+///         },
+///     ),
+/// );
+/// ```
+pub fn async_wget2_data<T>(
+    url: T,
+    method: HttpMethod,
+    body: Option<&str>,
+    free: bool,
+    handlers: WgetDataHandlers,
+) -> WgetHandle
+where
+    T: AsRef<str>,
+{
+    let url_cstring = to_cstring_lossy(url.as_ref());
+    let body_cstring = to_cstring_lossy(body.unwrap_or(""));
+
+    // The `arg` tells whether emscripten frees the buffer: if it doesn't, it's ours to free, even if the handlers are gone.
+    unsafe extern "C" fn onload_wrapper(
+        handle: u32,
+        arg: *mut c_void,
+        data: *mut c_void,
+        size: u32,
+    ) {
+        let emscripten_frees = !arg.is_null();
+        let handlers = ASYNC_WGET2_DATA_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&(handle as c_int)));
+
+        // The buffer isn't allocated by the Rust allocator, so it's copied either way.
+        let copy = if handlers.is_none() || data.is_null() || size == 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) }.to_vec()
+        };
+        if !emscripten_frees && !data.is_null() {
+            // Emscripten allocates the buffer with `malloc`.
+            unsafe { self::free(data) };
+        }
+
+        if let Some(handlers) = handlers {
+            (handlers.on_load)(copy);
+        }
+    }
+
+    unsafe extern "C" fn onerror_wrapper(
+        handle: u32,
+        _arg: *mut c_void,
+        status: c_int,
+        status_text: *const c_char,
+    ) {
+        let handlers = ASYNC_WGET2_DATA_HANDLERS
+            .with(|handlers_ref| handlers_ref.borrow_mut().remove(&(handle as c_int)));
+
+        if let Some(handlers) = handlers {
+            let status_text = if status_text.is_null() {
+                String::new()
+            } else {
+                unsafe { CStr::from_ptr(status_text) }
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            (handlers.on_error)(status as u16, status_text);
+        }
+    }
+
+    unsafe extern "C" fn onprogress_wrapper(
+        handle: u32,
+        _arg: *mut c_void,
+        loaded: c_int,
+        total: c_int,
+    ) {
+        // The progress function is taken out while it runs, so that the download can be aborted from inside it.
+        let on_progress = ASYNC_WGET2_DATA_HANDLERS.with(|handlers_ref| {
+            handlers_ref
+                .borrow_mut()
+                .get_mut(&(handle as c_int))
+                .and_then(|handlers| handlers.on_progress.take())
+        });
+
+        if let Some(mut on_progress) = on_progress {
+            // Emscripten reports a total of 0 when the server doesn't send the size of the download.
+            let total = if total > 0 { Some(total as u64) } else { None };
+            on_progress(loaded.max(0) as u64, total);
+
+            ASYNC_WGET2_DATA_HANDLERS.with(|handlers_ref| {
+                if let Some(handlers) = handlers_ref.borrow_mut().get_mut(&(handle as c_int)) {
+                    handlers.on_progress = Some(on_progress);
+                }
+            });
+        }
+    }
+
+    let handle = unsafe {
+        emscripten::emscripten_async_wget2_data(
+            url_cstring.as_ptr(),
+            method.as_cstr().as_ptr(),
+            body_cstring.as_ptr(),
+            // Any non-null pointer does, as it's only checked for null.
+            if free {
+                NonNull::<c_void>::dangling().as_ptr()
+            } else {
+                null_mut()
+            },
+            free as c_int,
+            Some(onload_wrapper),
+            Some(onerror_wrapper),
+            Some(onprogress_wrapper),
+        )
+    };
+
+    // The callbacks can't be called before the function returns the handle, so it's fine to store the handlers now.
+    ASYNC_WGET2_DATA_HANDLERS.with(|handlers_ref| {
+        handlers_ref.borrow_mut().insert(handle, handlers);
+    });
+
+    WgetHandle { handle }
+}
+
+// The pending `run_preload_plugins` calls, keyed by the file's path, like for `async_wget`.
+// Emscripten gives the pointer of the path we passed to it back to the callbacks, so its `CString` is kept here
+// for as long as there are preloadings of that path in progress.
+struct PreloadPluginsRequest {
+    path: CString,
+    pending: usize,
+    callbacks: Option<PathCallbacks>,
+}
+thread_local! {
+    static PRELOAD_PLUGINS_REQUESTS: RefCell<HashMap<String, PreloadPluginsRequest>> = RefCell::new(HashMap::new());
+}
+
+// Marks one preloading of the given path as finished, returning its callbacks if they weren't already taken.
+fn finish_preload_plugins_request(path: &str) -> Option<PathCallbacks> {
+    PRELOAD_PLUGINS_REQUESTS.with(|requests_ref| {
+        let mut requests = requests_ref.borrow_mut();
+        let request = requests.get_mut(path)?;
+
+        request.pending -= 1;
+        let callbacks = request.callbacks.take();
+        if request.pending == 0 {
+            requests.remove(path);
+        }
+
+        callbacks
+    })
+}
+
+/// Runs the browser's decoders (e.g. for images and audio) on a file that is already in the emscripten virtual file system,
+/// using the emscripten-defined [`emscripten_run_preload_plugins`].
+/// Afterwards, the file can be used by code that relies on the preloaded data, like SDL_image's `IMG_Load` with no codecs linked in.
+/// When the preloading finishes, either `on_loaded` or `on_error` gets called with the file's path, and both get dropped.
+///
+/// It returns `false`, without calling any of the functions, if the file doesn't exist.
+///
+/// Preloading the same path again before the previous preloading finishes replaces the previous callbacks.
+///
+/// [`emscripten_run_preload_plugins`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_preload_plugins
+///
+/// # Arguments
+/// * `path` - The path of the file in the virtual file system.
+/// * `on_loaded` - The function to call with the file's path once it's preloaded.
+/// * `on_error` - The function to call with the file's path if the preloading fails.
+///
+/// # Examples
+/// ```rust
+/// async_wget(
+///     "https://example.com/photo.jpg",
+///     "photo.jpg",
+///     |file| {
+///         run_preload_plugins(
+///             file,
+///             |file| println!("{} is ready to be loaded", file),
+///             |file| println!("Couldn't decode {}", file),
+///         );
+///     },
+///     |file| println!("Couldn't download {}", file),
+/// );
+/// ```
+pub fn run_preload_plugins<T, F, G>(path: T, on_loaded: F, on_error: G) -> bool
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(&str),
+    G: 'static + FnOnce(&str),
+{
+    let path_cstring = to_cstring_lossy(path.as_ref());
+    // The registry is keyed by the path as emscripten will give it back to the callbacks.
+    let path = path_cstring.to_string_lossy().into_owned();
+
+    // The pointer stays valid while the request is in the registry, as moving a `CString` doesn't move its data.
+    let (path_ptr, old_callbacks) = PRELOAD_PLUGINS_REQUESTS.with(|requests_ref| {
+        let mut requests = requests_ref.borrow_mut();
+        let request = requests
+            .entry(path.clone())
+            .or_insert_with(|| PreloadPluginsRequest {
+                path: path_cstring,
+                pending: 0,
+                callbacks: None,
+            });
+
+        request.pending += 1;
+        let old_callbacks = request
+            .callbacks
+            .replace((Box::new(on_loaded), Box::new(on_error)));
+
+        (request.path.as_ptr(), old_callbacks)
+    });
+    drop(old_callbacks);
+
+    unsafe extern "C" fn onload_wrapper(file: *const c_char) {
+        let file = unsafe { CStr::from_ptr(file) }
+            .to_string_lossy()
+            .into_owned();
+
+        if let Some((on_loaded, _)) = finish_preload_plugins_request(&file) {
+            on_loaded(&file);
+        }
+    }
+
+    unsafe extern "C" fn onerror_wrapper(file: *const c_char) {
+        let file = unsafe { CStr::from_ptr(file) }
+            .to_string_lossy()
+            .into_owned();
+
+        if let Some((_, on_error)) = finish_preload_plugins_request(&file) {
+            on_error(&file);
+        }
+    }
+
+    let result = unsafe {
+        emscripten::emscripten_run_preload_plugins(
+            path_ptr,
+            Some(onload_wrapper),
+            Some(onerror_wrapper),
+        )
+    };
+
+    if result != 0 {
+        // No callback will be called, so we drop them right away.
+        drop(finish_preload_plugins_request(&path));
+        return false;
+    }
+
+    true
+}
+
+/// Runs the browser's decoders (e.g. for images and audio) on the given in-memory data, using the emscripten-defined [`emscripten_run_preload_plugins_data`].
+/// The data gets written to a newly generated file in the virtual file system, whose name ends with the given suffix.
+/// When the preloading finishes, either `on_loaded` gets called with the generated file's path or `on_error` gets called, and both get dropped.
+///
+/// The data is copied, so the given buffer doesn't need to outlive this call.
+///
+/// [`emscripten_run_preload_plugins_data`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_run_preload_plugins_data
+///
+/// # Arguments
+/// * `data` - The contents of the file to preload.
+/// * `suffix` - The file extension the browser uses to pick the decoder, like `png` or `ogg`.
+/// * `on_loaded` - The function to call with the generated file's path once it's preloaded.
+/// * `on_error` - The function to call if the preloading fails.
+///
+/// # Examples
+/// ```rust
+/// run_preload_plugins_data(
+///     include_bytes!("../assets/logo.png"),
+///     "png",
+///     |file| println!("The logo is ready to be loaded from {}", file),
+///     || println!("Couldn't decode the logo"),
+/// );
+/// ```
+pub fn run_preload_plugins_data<T, F, G>(data: &[u8], suffix: T, on_loaded: F, on_error: G)
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(&str),
+    G: 'static + FnOnce(),
+{
+    let suffix_cstring = to_cstring_lossy(suffix.as_ref());
+
+    // Emscripten reads the data only once the preloading actually happens, so our copy of it travels
+    // along with the callbacks through the `arg` pointer, and gets dropped together with them.
+    let request = Box::into_raw(Box::new((data.to_vec(), on_loaded, on_error)));
+    let (data_ptr, data_len) = unsafe { ((*request).0.as_mut_ptr(), (*request).0.len()) };
+
+    unsafe extern "C" fn onload_wrapper<F, G>(arg: *mut c_void, file: *const c_char)
+    where
+        F: 'static + FnOnce(&str),
+        G: 'static + FnOnce(),
+    {
+        let (_, on_loaded, _) = *unsafe { Box::from_raw(arg as *mut (Vec<u8>, F, G)) };
+
+        let file = unsafe { CStr::from_ptr(file) }.to_string_lossy();
+        on_loaded(&file);
+    }
+
+    unsafe extern "C" fn onerror_wrapper<F, G>(arg: *mut c_void)
+    where
+        F: 'static + FnOnce(&str),
+        G: 'static + FnOnce(),
+    {
+        let (_, _, on_error) = *unsafe { Box::from_raw(arg as *mut (Vec<u8>, F, G)) };
+        on_error();
+    }
+
+    unsafe {
+        emscripten::emscripten_run_preload_plugins_data(
+            data_ptr as *mut c_char,
+            data_len as c_int,
+            suffix_cstring.as_ptr(),
+            request as *mut c_void,
+            Some(onload_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+// The C library functions needed to handle the buffers of `emscripten_async_wget2_data` and `emscripten_get_preloaded_image_data*`.
+extern "C" {
+    fn free(ptr: *mut c_void);
+    fn dup(fd: c_int) -> c_int;
+    fn close(fd: c_int) -> c_int;
+    fn fdopen(fd: c_int, mode: *const c_char) -> *mut emscripten::FILE;
+    fn fclose(file: *mut emscripten::FILE) -> c_int;
+}
+
+/// A decoded image, as returned by [`get_preloaded_image_data`] and its variants taking an opened file.
+///
+/// It owns the pixel buffer allocated by emscripten, and frees it when dropped.
+#[derive(Debug)]
+pub struct PreloadedImage {
+    data: *mut c_char,
+    width: c_int,
+    height: c_int,
+}
+impl PreloadedImage {
+    // Takes the ownership of the buffer returned by `emscripten_get_preloaded_image_data*`, if there is one.
+    fn from_raw(data: *mut c_char, width: c_int, height: c_int) -> Option<Self> {
+        if data.is_null() {
+            return None;
+        }
+
+        Some(PreloadedImage {
+            data,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the width of the image, in pixels.
+    pub fn width(&self) -> c_int {
+        self.width
+    }
+
+    /// Returns the height of the image, in pixels.
+    pub fn height(&self) -> c_int {
+        self.height
+    }
+
+    /// Returns the pixels of the image, row by row from the top, with 4 bytes (red, green, blue, alpha) for each pixel.
+    pub fn as_rgba(&self) -> &[u8] {
+        let len = self.width.max(0) as usize * self.height.max(0) as usize * 4;
+        unsafe { std::slice::from_raw_parts(self.data as *const u8, len) }
+    }
+
+    /// Copies the pixels of the image into a `Vec`, in the same layout as [`as_rgba`](Self::as_rgba), freeing the buffer allocated by emscripten.
+    ///
+    /// # Examples
+    /// ```rust
+    /// if let Some(image) = get_preloaded_image_data("tiles.png") {
+    ///     let (width, height) = (image.width(), image.height());
+    ///     let pixels = image.into_rgba();
+    ///     // e.g. upload `pixels` with `glTexImage2D(GL_TEXTURE_2D, 0, GL_RGBA, width, height, 0, GL_RGBA, GL_UNSIGNED_BYTE, ...)`
+    /// }
+    /// ```
+    pub fn into_rgba(self) -> Vec<u8> {
+        self.as_rgba().to_vec()
+    }
+}
+impl Drop for PreloadedImage {
+    fn drop(&mut self) {
+        // Emscripten allocates the buffer with `malloc`.
+        unsafe { free(self.data as *mut c_void) };
+    }
+}
+
+/// Returns the decoded pixels of an image file that was preloaded, using the emscripten-defined [`emscripten_get_preloaded_image_data`].
+/// The file must have been preloaded with e.g. [`run_preload_plugins`] or `--preload-file`.
+///
+/// It returns `None` if there is no preloaded image at that path.
+///
+/// [`emscripten_get_preloaded_image_data`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_preloaded_image_data
+///
+/// # Arguments
+/// * `path` - The path of the image file in the virtual file system.
+///
+/// # Examples
+/// ```rust
+/// run_preload_plugins(
+///     "sprites.png",
+///     |file| {
+///         if let Some(image) = get_preloaded_image_data(file) {
+///             println!("The sprites are {}x{}", image.width(), image.height());
+///         }
+///     },
+///     |file| println!("Couldn't decode {}", file),
+/// );
+/// ```
+pub fn get_preloaded_image_data<T>(path: T) -> Option<PreloadedImage>
+where
+    T: AsRef<str>,
+{
+    let path = to_cstring_lossy(path.as_ref());
+    let mut width: c_int = 0;
+    let mut height: c_int = 0;
+
+    let data = unsafe {
+        emscripten::emscripten_get_preloaded_image_data(path.as_ptr(), &mut width, &mut height)
+    };
+
+    PreloadedImage::from_raw(data, width, height)
+}
+
+/// Returns the decoded pixels of the preloaded image file opened with the given file descriptor,
+/// using the emscripten-defined [`emscripten_get_preloaded_image_data_from_FILE`].
+///
+/// The file must have been preloaded with e.g. [`run_preload_plugins`] or `--preload-file`.
+/// The file descriptor isn't closed, and it's still owned by the caller.
+///
+/// It returns `None` if the file descriptor isn't open, or if it's not a preloaded image.
+///
+/// [`emscripten_get_preloaded_image_data_from_FILE`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_preloaded_image_data_from_FILE
+///
+/// # Arguments
+/// * `fd` - The file descriptor of the image file.
+///
+/// # Examples
+/// ```rust
+/// use std::os::fd::AsRawFd;
+///
+/// let file = std::fs::File::open("sprites.png").unwrap();
+/// if let Some(image) = get_preloaded_image_data_from_fd(file.as_raw_fd()) {
+///     println!("The sprites are {}x{}", image.width(), image.height());
+/// }
+/// ```
+pub fn get_preloaded_image_data_from_fd(fd: RawFd) -> Option<PreloadedImage> {
+    // We make a `FILE` out of a duplicate of the file descriptor, so that closing it leaves the given one open.
+    let file = unsafe {
+        let dup_fd = dup(fd);
+        if dup_fd < 0 {
+            return None;
+        }
+
+        let file = fdopen(dup_fd, c"r".as_ptr());
+        if file.is_null() {
+            close(dup_fd);
+            return None;
+        }
+        file
+    };
+
+    let mut width: c_int = 0;
+    let mut height: c_int = 0;
+    let data = unsafe {
+        emscripten::emscripten_get_preloaded_image_data_from_FILE(file, &mut width, &mut height)
+    };
+
+    unsafe { fclose(file) };
+
+    PreloadedImage::from_raw(data, width, height)
+}
+
+/// Returns the decoded pixels of the given opened preloaded image file, like [`get_preloaded_image_data_from_fd`].
+///
+/// The file must have been preloaded with e.g. [`run_preload_plugins`] or `--preload-file`.
+/// It's left open.
+///
+/// # Arguments
+/// * `file` - The image file, e.g. a [`File`](std::fs::File).
+///
+/// # Examples
+/// ```rust
+/// let file = std::fs::File::open("sprites.png").unwrap();
+/// if let Some(image) = get_preloaded_image_data_from_file(&file) {
+///     println!("The sprites are {}x{}", image.width(), image.height());
+/// }
+/// ```
+pub fn get_preloaded_image_data_from_file<F>(file: &F) -> Option<PreloadedImage>
+where
+    F: AsFd,
+{
+    get_preloaded_image_data_from_fd(file.as_fd().as_raw_fd())
+}
+
+/// Returns the decoded pixels of the preloaded image file opened as the given C `FILE`,
+/// using the emscripten-defined [`emscripten_get_preloaded_image_data_from_FILE`].
+///
+/// The file must have been preloaded with e.g. [`run_preload_plugins`] or `--preload-file`.
+/// The `FILE` isn't closed.
+///
+/// It returns `None` if it's not a preloaded image.
+///
+/// [`emscripten_get_preloaded_image_data_from_FILE`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_preloaded_image_data_from_FILE
+///
+/// # Safety
+/// `file` must be a valid `FILE` pointer, e.g. returned by a C library's `fopen`.
+///
+/// # Arguments
+/// * `file` - The C `FILE` of the image file.
+pub unsafe fn get_preloaded_image_data_from_c_file(
+    file: *mut emscripten::FILE,
+) -> Option<PreloadedImage> {
+    let mut width: c_int = 0;
+    let mut height: c_int = 0;
+    let data = unsafe {
+        emscripten::emscripten_get_preloaded_image_data_from_FILE(file, &mut width, &mut height)
+    };
+
+    PreloadedImage::from_raw(data, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    // Adds one to the given counter when dropped, to tell when a main loop's function or state is dropped.
+    struct DropCounter(Rc<Cell<usize>>);
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    // Registers the given main loop like `set_main_loop_with_control` does, without emscripten,
+    // returning its handle and a function running an iteration of it, like emscripten would.
+    fn register<F, T>(func: F, arg: T) -> (MainLoop, impl Fn() -> MainLoopIteration)
+    where
+        F: 'static + FnMut(&mut T) -> ControlFlow<()>,
+        T: 'static,
+    {
+        let (entry, old_arg) = register_main_loop(func, arg);
+        drop(old_arg);
+
+        let main_loop = MainLoop { id: entry.id };
+        let iterate = move || {
+            assert!(
+                main_loop.is_active(),
+                "emscripten doesn't call a cancelled main loop"
+            );
+            unsafe { run_main_loop_iteration::<F, T>(entry.state) }
+        };
+        (main_loop, iterate)
+    }
+
+    #[test]
+    fn main_loop_panic_stops_the_loop() {
+        let drops = Rc::new(Cell::new(0));
+        let state = DropCounter(drops.clone());
+        let (main_loop, iterate) = register(
+            |frame: &mut (u32, DropCounter)| {
+                frame.0 += 1;
+                if frame.0 == 2 {
+                    panic!("out of ammo");
+                }
+                ControlFlow::Continue(())
+            },
+            (0, state),
+        );
+
+        let first = iterate();
+        assert!(!first.stopped);
+        assert!(first.panic_message.is_none());
+        assert!(main_loop.is_active());
+
+        let second = iterate();
+        assert!(second.stopped);
+        assert!(second.panic_message.unwrap().contains("out of ammo"));
+        assert!(!main_loop.is_active());
+        assert_eq!(drops.get(), 1);
+        assert!(RUNNING_MAIN_LOOP.with(Cell::get).is_null());
+    }
+
+    #[test]
+    fn main_loop_break_on_the_first_iteration_stops_the_loop() {
+        let drops = Rc::new(Cell::new(0));
+        let calls = Rc::new(Cell::new(0));
+        let calls_ref = calls.clone();
+        let (main_loop, iterate) = register(
+            move |_: &mut DropCounter| {
+                calls_ref.set(calls_ref.get() + 1);
+                ControlFlow::Break(())
+            },
+            DropCounter(drops.clone()),
+        );
+
+        let first = iterate();
+        assert!(first.stopped);
+        assert!(first.panic_message.is_none());
+        assert!(!main_loop.is_active());
+        assert_eq!((calls.get(), drops.get()), (1, 1));
+        assert!(RUNNING_MAIN_LOOP.with(Cell::get).is_null());
+    }
+
+    #[test]
+    fn main_loop_break_after_being_cancelled_drops_the_state_once() {
+        let drops = Rc::new(Cell::new(0));
+        let drops_ref = drops.clone();
+        let (main_loop, iterate) = register(
+            move |_: &mut DropCounter| {
+                // What `cancel_main_loop` does, besides telling emscripten.
+                assert!(unregister_main_loop().is_none());
+                // The state is still in use, so it's only dropped once the function returns.
+                assert_eq!(drops_ref.get(), 0);
+                ControlFlow::Break(())
+            },
+            DropCounter(drops.clone()),
+        );
+
+        let first = iterate();
+        // The loop was already cancelled, so there's nothing left to stop.
+        assert!(!first.stopped);
+        assert!(first.panic_message.is_none());
+        assert!(!main_loop.is_active());
+        assert_eq!(drops.get(), 1);
+        assert!(!RUNNING_MAIN_LOOP_DETACHED.with(Cell::get));
+    }
+
+    #[test]
+    fn main_loop_cancelled_from_inside_is_dropped_after_returning() {
+        let drops = Rc::new(Cell::new(0));
+        let drops_ref = drops.clone();
+        let (main_loop, iterate) = register(
+            move |_: &mut DropCounter| {
+                assert!(unregister_main_loop().is_none());
+                assert_eq!(drops_ref.get(), 0);
+                ControlFlow::Continue(())
+            },
+            DropCounter(drops.clone()),
+        );
+
+        let first = iterate();
+        assert!(!first.stopped);
+        assert!(!main_loop.is_active());
+        assert_eq!(drops.get(), 1);
+        assert!(RUNNING_MAIN_LOOP.with(Cell::get).is_null());
+        assert!(MAIN_LOOP.with(Cell::get).is_none());
+    }
+
+    #[test]
+    fn main_loop_registered_again_gives_back_the_previous_state() {
+        let first_drops = Rc::new(Cell::new(0));
+        let (first_loop, _) = register(
+            |_: &mut DropCounter| ControlFlow::Continue(()),
+            DropCounter(first_drops.clone()),
+        );
+
+        let (entry, old_arg) = register_main_loop(|_: &mut u32| ControlFlow::Continue(()), 7_u32);
+        let second_loop = MainLoop { id: entry.id };
+        assert!(!first_loop.is_active());
+        assert!(second_loop.is_active());
+
+        // The previous state is given back whole, and only its function is dropped.
+        let old_arg = old_arg.unwrap().downcast::<DropCounter>().unwrap();
+        assert_eq!(first_drops.get(), 0);
+        drop(old_arg);
+        assert_eq!(first_drops.get(), 1);
+
+        drop(unregister_main_loop());
+    }
+
+    #[test]
+    fn main_loop_replaced_from_inside_is_dropped_after_returning() {
+        let drops = Rc::new(Cell::new(0));
+        let drops_ref = drops.clone();
+        let (first_loop, iterate) = register(
+            move |_: &mut DropCounter| {
+                let (_, old_arg) =
+                    register_main_loop(|_: &mut u32| ControlFlow::Continue(()), 7_u32);
+                // The running function's state can't be given back.
+                assert!(old_arg.is_none());
+                assert_eq!(drops_ref.get(), 0);
+                ControlFlow::Continue(())
+            },
+            DropCounter(drops.clone()),
+        );
+
+        let first = iterate();
+        assert!(!first.stopped);
+        assert!(!first_loop.is_active());
+        assert_eq!(drops.get(), 1);
+        assert!(MAIN_LOOP.with(Cell::get).is_some());
+
+        drop(unregister_main_loop());
+    }
+
+    #[test]
+    fn inactive_main_loop_handle_does_nothing() {
+        let (main_loop, _) = register(|_: &mut ()| ControlFlow::Continue(()), ());
+        drop(unregister_main_loop());
+
+        assert!(!main_loop.is_active());
+        assert_eq!(main_loop.timing(), None);
+        assert!(!main_loop.set_timing(&MainLoopTiming::SetTimeout(33)));
+        assert!(!main_loop.is_paused());
+    }
+
+    #[test]
+    fn main_loop_delta_is_zero_on_the_first_frame() {
+        let max_delta = Duration::from_millis(100);
+
+        assert_eq!(main_loop_delta(None, 12_345.0, max_delta), Duration::ZERO);
+        assert_eq!(
+            main_loop_delta(Some(1000.0), 1016.5, max_delta),
+            Duration::from_secs_f64(0.0165)
+        );
+    }
+
+    #[test]
+    fn main_loop_delta_is_clamped() {
+        let max_delta = Duration::from_millis(100);
+
+        // E.g. after the page was hidden for a minute.
+        assert_eq!(
+            main_loop_delta(Some(1000.0), 61_000.0, max_delta),
+            max_delta
+        );
+        assert_eq!(main_loop_delta(Some(1000.0), 1100.0, max_delta), max_delta);
+        assert_eq!(
+            main_loop_delta(Some(1000.0), 999.0, max_delta),
+            Duration::ZERO
+        );
+    }
+
+    // Applies the given additions (`true`) and removals (`false`) of pause reasons, starting with none,
+    // returning the calls that would be made to emscripten.
+    fn pause_calls(changes: &[(bool, u8)]) -> Vec<&'static str> {
+        let mut reasons = 0;
+        let mut calls = Vec::new();
+        for &(add, reason) in changes {
+            let (new_reasons, call) = if add {
+                let (new_reasons, pause) = pause_reasons_with(reasons, reason);
+                (new_reasons, pause.then_some("pause"))
+            } else {
+                let (new_reasons, resume) = pause_reasons_without(reasons, reason);
+                (new_reasons, resume.then_some("resume"))
+            };
+            reasons = new_reasons;
+            calls.extend(call);
+        }
+        calls
+    }
+
+    #[test]
+    fn manual_pause_outlasts_the_page_being_shown() {
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_MANUAL),
+            (true, PAUSE_REASON_HIDDEN),
+            (false, PAUSE_REASON_HIDDEN),
+        ]);
+        assert_eq!(calls, ["pause"]);
+
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_MANUAL),
+            (true, PAUSE_REASON_HIDDEN),
+            (false, PAUSE_REASON_HIDDEN),
+            (false, PAUSE_REASON_MANUAL),
+        ]);
+        assert_eq!(calls, ["pause", "resume"]);
+    }
+
+    #[test]
+    fn manual_resume_waits_for_the_page_to_be_shown() {
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_HIDDEN),
+            (true, PAUSE_REASON_MANUAL),
+            (false, PAUSE_REASON_MANUAL),
+        ]);
+        assert_eq!(calls, ["pause"]);
+
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_HIDDEN),
+            (true, PAUSE_REASON_MANUAL),
+            (false, PAUSE_REASON_MANUAL),
+            (false, PAUSE_REASON_HIDDEN),
+        ]);
+        assert_eq!(calls, ["pause", "resume"]);
+    }
+
+    #[test]
+    fn repeated_pauses_and_resumes_call_emscripten_once() {
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_MANUAL),
+            (true, PAUSE_REASON_MANUAL),
+            (false, PAUSE_REASON_MANUAL),
+            (false, PAUSE_REASON_MANUAL),
+        ]);
+        assert_eq!(calls, ["pause", "resume"]);
+
+        // A loop that isn't paused isn't resumed, e.g. when the page is shown, or `auto_pause_on_hidden(false)` is called.
+        assert!(pause_calls(&[(false, PAUSE_REASON_HIDDEN)]).is_empty());
+        assert!(pause_calls(&[(false, PAUSE_REASON_MANUAL)]).is_empty());
+    }
+
+    #[test]
+    fn disabling_auto_pause_keeps_the_manual_pause() {
+        let calls = pause_calls(&[
+            (true, PAUSE_REASON_HIDDEN),
+            (true, PAUSE_REASON_MANUAL),
+            // What `auto_pause_on_hidden(false)` does.
+            (false, PAUSE_REASON_HIDDEN),
+        ]);
+        assert_eq!(calls, ["pause"]);
+    }
+
+    #[test]
+    fn page_shown_after_the_loop_is_cancelled_resumes_nothing() {
+        let _ = register(|_: &mut ()| ControlFlow::Continue(()), ());
+        MAIN_LOOP_PAUSE_REASONS.with(|reasons| reasons.set(PAUSE_REASON_HIDDEN));
+
+        drop(unregister_main_loop());
+        let reasons = MAIN_LOOP_PAUSE_REASONS.with(Cell::get);
+        assert_eq!(reasons, 0);
+        assert_eq!(
+            pause_reasons_without(reasons, PAUSE_REASON_HIDDEN),
+            (0, false)
+        );
+    }
+
+    #[test]
+    fn cancelled_main_loop_is_no_longer_set() {
+        assert!(!is_main_loop_registered());
+        let (main_loop, _) = register(|_: &mut ()| ControlFlow::Continue(()), ());
+        assert!(is_main_loop_registered());
+
+        drop(unregister_main_loop());
+        assert!(!is_main_loop_registered());
+        assert!(!main_loop.is_active());
+    }
+
+    #[test]
+    fn paused_main_loop_is_still_set() {
+        let (main_loop, _) = register(|_: &mut ()| ControlFlow::Continue(()), ());
+        MAIN_LOOP_PAUSE_REASONS
+            .with(|reasons| reasons.set(PAUSE_REASON_MANUAL | PAUSE_REASON_HIDDEN));
+
+        assert!(is_main_loop_registered());
+        assert!(main_loop.is_active());
+        assert!(main_loop.is_paused());
+
+        drop(unregister_main_loop());
+        assert!(!is_main_loop_paused());
+    }
+
+    #[test]
+    fn main_loop_cancelled_from_inside_is_no_longer_set() {
+        let (_, iterate) = register(
+            |_: &mut ()| {
+                drop(unregister_main_loop());
+                assert!(!is_main_loop_registered());
+                ControlFlow::Continue(())
+            },
+            (),
+        );
+
+        iterate();
+        assert!(!is_main_loop_registered());
+    }
+
+    // Sets a handler recording the reasons it's given, without installing the JavaScript listener.
+    fn set_recording_rejection_handler<F>(reasons: &Rc<RefCell<Vec<String>>>, then: F)
+    where
+        F: 'static + Fn(),
+    {
+        UNHANDLED_REJECTION_LISTENER_SET.with(|set| set.set(true));
+        let reasons = reasons.clone();
+        set_unhandled_rejection_handler(move |reason| {
+            reasons.borrow_mut().push(reason);
+            then();
+        });
+    }
+
+    #[test]
+    fn rejection_handler_stays_after_being_called() {
+        let reasons = Rc::new(RefCell::new(Vec::new()));
+        set_recording_rejection_handler(&reasons, || {});
+
+        call_unhandled_rejection_handler("first".to_string());
+        call_unhandled_rejection_handler("second".to_string());
+        assert_eq!(*reasons.borrow(), ["first", "second"]);
+
+        remove_unhandled_rejection_handler();
+        call_unhandled_rejection_handler("third".to_string());
+        assert_eq!(reasons.borrow().len(), 2);
+    }
+
+    #[test]
+    fn rejection_handler_removed_from_inside_itself_stays_removed() {
+        let reasons = Rc::new(RefCell::new(Vec::new()));
+        set_recording_rejection_handler(&reasons, remove_unhandled_rejection_handler);
+
+        call_unhandled_rejection_handler("first".to_string());
+        call_unhandled_rejection_handler("second".to_string());
+        assert_eq!(*reasons.borrow(), ["first"]);
+        assert!(UNHANDLED_REJECTION_HANDLER.with(|handler_ref| handler_ref.borrow().is_none()));
+    }
+
+    #[test]
+    fn rejection_handler_replaced_from_inside_itself_is_replaced() {
+        let reasons = Rc::new(RefCell::new(Vec::new()));
+        let new_reasons = Rc::new(RefCell::new(Vec::new()));
+        let new_reasons_ref = new_reasons.clone();
+        set_recording_rejection_handler(&reasons, move || {
+            set_recording_rejection_handler(&new_reasons_ref, || {})
+        });
+
+        call_unhandled_rejection_handler("first".to_string());
+        call_unhandled_rejection_handler("second".to_string());
+        assert_eq!(*reasons.borrow(), ["first"]);
+        assert_eq!(*new_reasons.borrow(), ["second"]);
+    }
+
+    #[test]
+    fn main_loop_panic_throws_only_when_asked() {
+        let message = || "The main loop panicked: out of ammo".to_string();
+
+        assert_eq!(
+            main_loop_panic_exception(message(), MainLoopPanicPolicy::Cancel),
+            None
+        );
+        assert_eq!(
+            main_loop_panic_exception(message(), MainLoopPanicPolicy::Throw),
+            Some(message())
+        );
+    }
+
+    #[test]
+    fn main_loop_panic_throws_the_hook_report_once() {
+        let message = || "The main loop panicked: out of ammo".to_string();
+
+        for policy in [MainLoopPanicPolicy::Cancel, MainLoopPanicPolicy::Throw] {
+            PENDING_PANIC_REPORT
+                .with(|pending| pending.replace(Some("panicked at src/main.rs".to_string())));
+
+            assert_eq!(
+                main_loop_panic_exception(message(), policy).as_deref(),
+                Some("panicked at src/main.rs")
+            );
+            assert!(take_panic_report().is_none());
+        }
+    }
+
+    #[test]
+    fn main_loop_timing_from_raw_keeps_the_invalid_modes() {
+        assert_eq!(
+            MainLoopTiming::from_raw(emscripten::EM_TIMING_SETTIMEOUT as c_int, 33),
+            Ok(MainLoopTiming::SetTimeout(33))
+        );
+        assert_eq!(
+            MainLoopTiming::from_raw(emscripten::EM_TIMING_RAF as c_int, 2),
+            Ok(MainLoopTiming::RequestAnimationFrame(2))
+        );
+        assert_eq!(
+            MainLoopTiming::from_raw(emscripten::EM_TIMING_SETIMMEDIATE as c_int, 7),
+            Ok(MainLoopTiming::SetImmediate)
+        );
+        assert_eq!(
+            MainLoopTiming::from_raw(42, -1).unwrap_err().raw(),
+            (42, -1)
+        );
+        assert_eq!(MainLoopTiming::from_raw(-1, 0).unwrap_err().raw(), (-1, 0));
+    }
+
+    #[test]
+    fn main_loop_timing_from_duration_saturates() {
+        assert_eq!(
+            MainLoopTiming::from_duration(Duration::from_micros(33_900)),
+            MainLoopTiming::SetTimeout(33)
+        );
+        assert_eq!(
+            MainLoopTiming::from_duration(Duration::from_secs(u64::MAX)),
+            MainLoopTiming::SetTimeout(c_int::MAX)
+        );
+    }
+
+    #[test]
+    fn js_string_literal_escapes_quotes_and_line_breaks() {
+        assert_eq!(js_string_literal("plain"), r#""plain""#);
+        assert_eq!(
+            js_string_literal(r#"say "hi" \ bye"#),
+            r#""say \"hi\" \\ bye""#
+        );
+        assert_eq!(
+            js_string_literal("a\nb\u{2028}c\0"),
+            r#""a\u000ab\u2028c\u0000""#
+        );
+        assert_eq!(js_string_literal("\u{1F600}"), "\"\u{1F600}\"");
+    }
+
+    #[test]
+    fn css_string_literal_escapes_quotes_and_control_characters() {
+        assert_eq!(css_string_literal("cursor.png"), r#""cursor.png""#);
+        assert_eq!(
+            css_string_literal(r#"a") ; evil: url("b"#),
+            r#""a\") ; evil: url(\"b""#
+        );
+        assert_eq!(css_string_literal("a\nb"), "\"a\\a b\"");
+        assert_eq!(css_string_literal("\\"), r#""\\""#);
+    }
+
+    #[test]
+    fn emscripten_result_success_and_deferred_are_ok() {
+        assert_eq!(
+            EmscriptenError::from_result(emscripten::EMSCRIPTEN_RESULT_SUCCESS as c_int),
+            Ok(())
+        );
+        assert_eq!(
+            EmscriptenError::from_result(emscripten::EMSCRIPTEN_RESULT_DEFERRED as c_int),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn emscripten_result_unknown_target_is_reported() {
+        // What `emscripten_get_canvas_element_size` returns for a selector matching no element.
+        assert_eq!(
+            EmscriptenError::from_result(emscripten::EMSCRIPTEN_RESULT_UNKNOWN_TARGET),
+            Err(EmscriptenError::UnknownTarget)
+        );
+        assert_eq!(
+            EmscriptenError::from_result(emscripten::EMSCRIPTEN_RESULT_INVALID_TARGET),
+            Err(EmscriptenError::InvalidTarget)
+        );
+    }
+
+    #[test]
+    fn to_cstring_lossy_removes_nul_characters() {
+        assert_eq!(to_cstring_lossy("plain").as_bytes(), b"plain");
+        assert_eq!(to_cstring_lossy("a\0b\0\0c").as_bytes(), b"abc");
+        assert_eq!(to_cstring_lossy("\0").as_bytes(), b"");
+    }
+
+    #[test]
+    fn script_string_is_copied_out_of_the_buffer() {
+        let mut buffer = *b"hi\0";
+        let result = unsafe { copy_script_string(buffer.as_ptr() as *const c_char) };
+
+        // The next script overwrites emscripten's buffer, which mustn't change the previous result.
+        buffer.copy_from_slice(b"yo\0");
+        assert_eq!(result.as_deref(), Some("hi"));
+        assert_eq!(unsafe { copy_script_string(std::ptr::null()) }, None);
+    }
+
+    #[test]
+    fn script_string_with_lone_surrogate_is_copied_lossily() {
+        // Emscripten encodes a lone surrogate, like the one of `'a\ud800b'`, as it is, which isn't valid UTF-8.
+        let buffer = *b"a\xed\xa0\x80b\0";
+        let result = unsafe { copy_script_string(buffer.as_ptr() as *const c_char) }.unwrap();
+
+        assert!(result.starts_with('a'));
+        assert!(result.ends_with('b'));
+        assert!(result.contains('\u{FFFD}'));
+    }
+
+    extern "C" {
+        fn malloc(size: usize) -> *mut c_void;
+    }
+
+    #[test]
+    fn preloaded_image_owns_the_malloc_buffer() {
+        // Emscripten allocates the pixels with `malloc`, so the image must free them with `free`, not the Rust allocator.
+        let pixels: [u8; 8] = [255, 0, 0, 255, 0, 255, 0, 128];
+        let data = unsafe { malloc(pixels.len()) } as *mut c_char;
+        assert!(!data.is_null());
+        unsafe { std::ptr::copy_nonoverlapping(pixels.as_ptr(), data as *mut u8, pixels.len()) };
+
+        let image = PreloadedImage::from_raw(data, 2, 1).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 1));
+        assert_eq!(image.as_rgba(), &pixels);
+        assert_eq!(image.into_rgba(), pixels.to_vec());
+    }
+
+    #[test]
+    fn preloaded_image_from_null_is_none() {
+        assert!(PreloadedImage::from_raw(null_mut(), 0, 0).is_none());
+    }
+
+    // Works like `emscripten_get_callstack` for the given callstacks, one per call, truncating it to the buffer.
+    fn fake_callstacks(stacks: Vec<String>) -> impl FnMut(Option<&mut [u8]>) -> c_int {
+        let mut stacks = stacks.into_iter();
+        move |buffer| {
+            let stack = stacks.next().expect("too many calls");
+            match buffer {
+                None => stack.len() as c_int + 1,
+                Some(buffer) => {
+                    let len = stack.len().min(buffer.len() - 1);
+                    buffer[..len].copy_from_slice(&stack.as_bytes()[..len]);
+                    buffer[len] = 0;
+                    len as c_int + 1
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn callstack_fitting_the_buffer_is_taken_in_two_calls() {
+        let stack = "at main (game.wasm:1:100)\nat start (game.js:2:3)".to_string();
+
+        assert_eq!(
+            callstack_from(fake_callstacks(vec![stack.clone(), stack.clone()])),
+            stack
+        );
+    }
+
+    #[test]
+    fn callstack_growing_past_the_buffer_is_taken_again() {
+        // The second callstack is too long for the buffer sized after the first one, so it's truncated, and taken again.
+        let short = "at main".to_string();
+        let long = "at frame\n".repeat(11);
+
+        assert_eq!(
+            callstack_from(fake_callstacks(vec![short, long.clone(), long.clone()])),
+            long
+        );
+    }
+
+    #[test]
+    fn missing_callstack_is_empty() {
+        assert_eq!(callstack_from(|_| 0), "");
+    }
+
+    #[test]
+    fn emscripten_result_unknown_code_is_kept() {
+        assert_eq!(
+            EmscriptenError::from_result(-42),
+            Err(EmscriptenError::Other(-42))
+        );
+    }
 }
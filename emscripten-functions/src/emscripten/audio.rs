@@ -0,0 +1,63 @@
+//! Helpers for playing audio in the browser, where autoplay policies keep the audio suspended until the user interacts with the page.
+
+use std::cell::Cell;
+
+use super::run_script;
+
+thread_local! {
+    static RESUME_ON_FIRST_GESTURE_SET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Resumes the audio contexts we know about, then removes the listeners that run it.
+// Browsers only allow resuming an audio context from the handler of a user gesture, so this runs right in the DOM event listeners.
+// The `html5` event callbacks aren't used for this, as they would replace the ones the app registers for the same events.
+const RESUME_ON_FIRST_GESTURE_SCRIPT: &str = r#"
+(function () {
+    var events = ["pointerdown", "touchend", "keydown"];
+    var resume = function () {
+        var contexts = [];
+        if (typeof Module != "undefined" && Module["SDL2"] && Module["SDL2"].audioContext) {
+            contexts.push(Module["SDL2"].audioContext);
+        }
+        if (typeof SDL != "undefined" && SDL.audioContext) {
+            contexts.push(SDL.audioContext);
+        }
+        if (typeof EmAudio != "undefined") {
+            for (var handle in EmAudio) {
+                contexts.push(EmAudio[handle]);
+            }
+        }
+        contexts.forEach(function (context) {
+            if (context && context.state == "suspended" && context.resume) {
+                context.resume();
+            }
+        });
+        events.forEach(function (event) {
+            document.removeEventListener(event, resume, true);
+        });
+    };
+    events.forEach(function (event) {
+        document.addEventListener(event, resume, true);
+    });
+})();
+"#;
+
+/// Resumes the page's Web Audio contexts on the user's first click, tap or key press, as browsers block audio until then.
+///
+/// The contexts resumed are the ones of SDL's audio device (with either SDL 1 or SDL 2),
+/// and the ones created with emscripten's Web Audio API, such as with `emscripten_create_audio_context`.
+/// The listeners remove themselves after the first gesture; audio contexts created after it are allowed to play right away.
+///
+/// Calling it more than once does nothing.
+///
+/// # Examples
+/// ```rust
+/// audio::resume_on_first_gesture();
+/// ```
+pub fn resume_on_first_gesture() {
+    if RESUME_ON_FIRST_GESTURE_SET.with(|set| set.replace(true)) {
+        return;
+    }
+
+    run_script(RESUME_ON_FIRST_GESTURE_SCRIPT);
+}
@@ -0,0 +1,103 @@
+//! Functions (with rust-native parameter types) for the main loop blockers of the emscripten [`emscripten.h`] header file.
+//!
+//! A blocker is a function that runs once, before the next iteration of the main loop.
+//! While there are blockers queued, they run one after the other, each in its own browser task, and the main loop waits for them all.
+//! Between them, emscripten reports the progress as `name (done/expected)` with the page's `Module.setStatus`,
+//! which the default HTML shell shows, making them a good fit for loading a level in chunks behind a progress indicator.
+//!
+//! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html
+
+use std::os::raw::{c_int, c_void};
+
+use emscripten_functions_sys::emscripten;
+
+use crate::emscripten::to_cstring_lossy;
+
+// The callback given to emscripten for all the blockers, with the blocker's boxed closure as its argument.
+unsafe extern "C" fn run_blocker<F>(arg: *mut c_void)
+where
+    F: 'static + FnOnce(),
+{
+    let func = unsafe { Box::from_raw(arg as *mut F) };
+    func();
+}
+
+/// Queues the given function as a blocker of the main loop, using the emscripten-defined [`emscripten_push_main_loop_blocker`].
+/// It counts towards the progress reported to the page, as set with [`set_expected`].
+///
+/// The blockers only run while a main loop is set: if there is none, or it gets cancelled, the function is never called, and never dropped either.
+///
+/// [`emscripten_push_main_loop_blocker`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_push_main_loop_blocker
+///
+/// # Arguments
+/// * `name` - The name of the blocker, shown in the progress reported to the page. Any nul characters in it are removed.
+/// * `func` - The function to run before the next iteration of the main loop.
+///
+/// # Examples
+/// ```rust
+/// blockers::set_expected(3);
+/// blockers::push("Loading textures", || load_textures());
+/// blockers::push("Loading sounds", || load_sounds());
+/// blockers::push("Building the level", || build_level());
+/// ```
+pub fn push<T, F>(name: T, func: F)
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(),
+{
+    let name = to_cstring_lossy(name.as_ref());
+    let arg = Box::into_raw(Box::new(func)) as *mut c_void;
+
+    // Emscripten copies the name into a JavaScript string right away, so the `CString` only has to outlive the call.
+    unsafe {
+        emscripten::_emscripten_push_main_loop_blocker(Some(run_blocker::<F>), arg, name.as_ptr());
+    }
+}
+
+/// Queues the given function as a blocker of the main loop, using the emscripten-defined [`emscripten_push_uncounted_main_loop_blocker`].
+/// Unlike with [`push`], it doesn't count towards the progress reported to the page.
+///
+/// [`emscripten_push_uncounted_main_loop_blocker`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_push_uncounted_main_loop_blocker
+///
+/// # Arguments
+/// * `name` - The name of the blocker. Any nul characters in it are removed.
+/// * `func` - The function to run before the next iteration of the main loop.
+///
+/// # Examples
+/// ```rust
+/// blockers::push_uncounted("Saving", || save_game());
+/// ```
+pub fn push_uncounted<T, F>(name: T, func: F)
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(),
+{
+    let name = to_cstring_lossy(name.as_ref());
+    let arg = Box::into_raw(Box::new(func)) as *mut c_void;
+
+    unsafe {
+        emscripten::_emscripten_push_uncounted_main_loop_blocker(
+            Some(run_blocker::<F>),
+            arg,
+            name.as_ptr(),
+        );
+    }
+}
+
+/// Sets the number of counted blockers expected to be queued, using the emscripten-defined [`emscripten_set_main_loop_expected_blockers`].
+/// It's the total of the progress reported to the page, that can be set before queueing the blockers one by one.
+///
+/// [`emscripten_set_main_loop_expected_blockers`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_main_loop_expected_blockers
+///
+/// # Arguments
+/// * `num` - The number of expected blockers.
+///
+/// # Examples
+/// ```rust
+/// blockers::set_expected(10);
+/// ```
+pub fn set_expected(num: c_int) {
+    unsafe {
+        emscripten::emscripten_set_main_loop_expected_blockers(num);
+    }
+}
@@ -0,0 +1,183 @@
+//! Functions (with rust-native parameter and return value types) for loading side modules at runtime,
+//! from the emscripten [`emscripten.h`] header file. They require the `dylink` feature.
+//!
+//! The main program must be linked with `-sMAIN_MODULE=2` (or `-sMAIN_MODULE`, which keeps all the system libraries for the side modules),
+//! and the side modules with `-sSIDE_MODULE=2`, e.g. a `cdylib` crate built for the `wasm32-unknown-emscripten` target.
+//! With `-sMAIN_MODULE=2`, the functions the side modules need from the main program must be exported with `-sEXPORTED_FUNCTIONS`.
+//!
+//! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html
+
+use std::{
+    ops::{BitOr, BitOrAssign},
+    os::raw::{c_char, c_int, c_void},
+};
+
+use emscripten_functions_sys::emscripten;
+
+use super::to_cstring_lossy;
+
+// The C library functions for the loaded libraries.
+extern "C" {
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlclose(handle: *mut c_void) -> c_int;
+}
+
+/// The flags of [`dlopen_async`], which can be combined with `|`.
+///
+/// # Examples
+/// ```rust
+/// let flags = DlopenFlags::NOW | DlopenFlags::GLOBAL;
+/// assert!(flags.contains(DlopenFlags::GLOBAL));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DlopenFlags(c_int);
+impl DlopenFlags {
+    /// Resolves the symbols of the library when they're first used.
+    pub const LAZY: DlopenFlags = DlopenFlags(1);
+    /// Resolves all the symbols of the library when it's loaded.
+    pub const NOW: DlopenFlags = DlopenFlags(2);
+    /// Makes the symbols of the library available to the libraries loaded after it.
+    pub const GLOBAL: DlopenFlags = DlopenFlags(256);
+    /// Keeps the symbols of the library to itself; it's the default.
+    pub const LOCAL: DlopenFlags = DlopenFlags(0);
+    /// Keeps the library loaded when its last handle is closed.
+    pub const NODELETE: DlopenFlags = DlopenFlags(4096);
+
+    /// Returns no flags, which is the same as [`LOCAL`](DlopenFlags::LOCAL).
+    pub const fn empty() -> Self {
+        DlopenFlags(0)
+    }
+
+    /// Returns the raw `RTLD_*` flags.
+    pub const fn bits(&self) -> c_int {
+        self.0
+    }
+
+    /// Returns whether all the given flags are set.
+    pub const fn contains(&self, other: DlopenFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+impl BitOr for DlopenFlags {
+    type Output = DlopenFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        DlopenFlags(self.0 | rhs.0)
+    }
+}
+impl BitOrAssign for DlopenFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// A side module loaded with [`dlopen_async`].
+///
+/// Dropping it closes the library with `dlclose`, after which the symbols taken from it mustn't be used anymore.
+#[derive(Debug)]
+pub struct LibraryHandle {
+    handle: *mut c_void,
+}
+impl LibraryHandle {
+    /// Returns the symbol of the library with the given name, using `dlsym`, or `None` if there is no such symbol.
+    ///
+    /// # Safety
+    /// `T` must be a pointer type matching the symbol, e.g. an `extern "C" fn` with the signature of the function,
+    /// or a raw pointer to the type of the variable.
+    /// The returned value mustn't be used after the library is dropped.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the symbol.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let init: extern "C" fn() -> c_int = unsafe { library.symbol("mod_init") }.unwrap();
+    /// println!("The mod says {}", init());
+    /// ```
+    pub unsafe fn symbol<T>(&self, name: &str) -> Option<T>
+    where
+        T: Copy,
+    {
+        assert_eq!(
+            std::mem::size_of::<T>(),
+            std::mem::size_of::<*mut c_void>(),
+            "A symbol can only be turned into a pointer-sized type"
+        );
+
+        let name = to_cstring_lossy(name);
+        let symbol = unsafe { dlsym(self.handle, name.as_ptr()) };
+        if symbol.is_null() {
+            None
+        } else {
+            Some(unsafe { std::mem::transmute_copy(&symbol) })
+        }
+    }
+}
+impl Drop for LibraryHandle {
+    fn drop(&mut self) {
+        unsafe { dlclose(self.handle) };
+    }
+}
+
+// The callbacks of `dlopen_async`, whose callbacks are passed as a boxed `(on_success, on_error)` pair.
+unsafe extern "C" fn onsuccess_wrapper<F, G>(arg: *mut c_void, handle: *mut c_void)
+where
+    F: 'static + FnOnce(LibraryHandle),
+{
+    let (on_success, _) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+    on_success(LibraryHandle { handle });
+}
+
+unsafe extern "C" fn onerror_wrapper<F, G>(arg: *mut c_void)
+where
+    G: 'static + FnOnce(),
+{
+    let (_, on_error) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+    on_error();
+}
+
+/// Loads the side module at the given path without blocking, using the emscripten-defined [`emscripten_dlopen`].
+/// If the module isn't in the virtual file system, emscripten fetches it from the URL with the same path.
+/// Once it's loaded, `on_success` is called with its handle, otherwise `on_error` is called.
+///
+/// [`emscripten_dlopen`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_dlopen
+///
+/// # Arguments
+/// * `path` - The path of the side module.
+/// * `flags` - How to load the module.
+/// * `on_success` - The function to call with the handle of the loaded module.
+/// * `on_error` - The function to call if the module couldn't be loaded.
+///
+/// # Examples
+/// ```rust
+/// dlopen_async(
+///     "mods/extra_levels.wasm",
+///     DlopenFlags::NOW,
+///     |library| {
+///         if let Some(init) = unsafe { library.symbol::<extern "C" fn()>("mod_init") } {
+///             init();
+///         }
+///         MODS.with(|mods| mods.borrow_mut().push(library));
+///     },
+///     || console::error("Couldn't load the mod"),
+/// );
+/// ```
+pub fn dlopen_async<T, F, G>(path: T, flags: DlopenFlags, on_success: F, on_error: G)
+where
+    T: AsRef<str>,
+    F: 'static + FnOnce(LibraryHandle),
+    G: 'static + FnOnce(),
+{
+    let path = to_cstring_lossy(path.as_ref());
+    let arg = Box::into_raw(Box::new((on_success, on_error))) as *mut c_void;
+
+    unsafe {
+        emscripten::emscripten_dlopen(
+            path.as_ptr(),
+            flags.bits(),
+            arg,
+            Some(onsuccess_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Frame time statistics for the main loop, e.g. for a debug HUD showing the frame rate.
+
+use std::time::Duration;
+
+use super::get_now;
+
+// The number of frames kept for the worst and 95th percentile frame times, about 2 seconds at 60fps.
+const WINDOW: usize = 120;
+
+// The weight of the latest frame in the moving average of the frame time.
+const SMOOTHING: f64 = 0.1;
+
+/// Frame time statistics, fed once per frame from the main loop with [`frame`](FrameStats::frame).
+///
+/// It keeps an exponential moving average of the frame time, for a steady frame rate readout,
+/// and the frame times of the last 120 frames, for the worst and 95th percentile ones.
+/// Feeding it doesn't allocate, so it's fine to keep it in the main loop for good.
+///
+/// The frame times are measured with [`get_now`], between the consecutive calls of [`frame`](FrameStats::frame).
+///
+/// # Examples
+/// ```rust
+/// set_main_loop_with_arg(|stats| {
+///     stats.frame();
+///     draw_game();
+///     draw_text(10, 10, &stats.overlay_string());
+/// }, FrameStats::new(), 0, true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    last_time: Option<f64>,
+    average_ms: f64,
+    frame_times_ms: [f64; WINDOW],
+    next_index: usize,
+    count: usize,
+}
+impl Default for FrameStats {
+    fn default() -> Self {
+        FrameStats::new()
+    }
+}
+impl FrameStats {
+    /// Creates the statistics, with no frames measured yet.
+    pub fn new() -> Self {
+        FrameStats {
+            last_time: None,
+            average_ms: 0.0,
+            frame_times_ms: [0.0; WINDOW],
+            next_index: 0,
+            count: 0,
+        }
+    }
+
+    /// Marks the start of a new frame, measuring the time elapsed since the previous call.
+    /// The first call only starts the measurements.
+    pub fn frame(&mut self) {
+        let now = get_now();
+        if let Some(last_time) = self.last_time {
+            self.add_frame_time(Duration::from_secs_f64(
+                ((now - last_time) / 1000.0).max(0.0),
+            ));
+        }
+        self.last_time = Some(now);
+    }
+
+    /// Adds the given frame time to the statistics, for frame times measured elsewhere,
+    /// e.g. the ones given by [`set_main_loop_timed`](super::set_main_loop_timed).
+    ///
+    /// # Arguments
+    /// * `frame_time` - The time the frame took.
+    pub fn add_frame_time(&mut self, frame_time: Duration) {
+        let frame_time_ms = frame_time.as_secs_f64() * 1000.0;
+
+        self.average_ms = if self.count == 0 {
+            frame_time_ms
+        } else {
+            self.average_ms + SMOOTHING * (frame_time_ms - self.average_ms)
+        };
+        self.frame_times_ms[self.next_index] = frame_time_ms;
+        self.next_index = (self.next_index + 1) % WINDOW;
+        self.count += 1;
+    }
+
+    // The frame times of the last frames, in no particular order.
+    fn window(&self) -> &[f64] {
+        &self.frame_times_ms[..self.count.min(WINDOW)]
+    }
+
+    /// Returns the moving average of the frame time, or zero if no frame was measured yet.
+    pub fn average_frame_time(&self) -> Duration {
+        Duration::from_secs_f64(self.average_ms / 1000.0)
+    }
+
+    /// Returns the frame rate matching the moving average of the frame time, or zero if no frame was measured yet.
+    pub fn fps(&self) -> f64 {
+        if self.average_ms > 0.0 {
+            1000.0 / self.average_ms
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the longest frame time of the last 120 frames, or zero if no frame was measured yet.
+    pub fn worst_frame_time(&self) -> Duration {
+        let worst_ms = self.window().iter().copied().fold(0.0, f64::max);
+        Duration::from_secs_f64(worst_ms / 1000.0)
+    }
+
+    /// Returns the 95th percentile of the frame times of the last 120 frames, or zero if no frame was measured yet.
+    /// It tells how bad the occasional slow frames are, without being thrown off by a single hiccup like the worst frame time.
+    pub fn frame_time_p95(&self) -> Duration {
+        let window = self.window();
+        if window.is_empty() {
+            return Duration::ZERO;
+        }
+
+        // The sorting happens on a copy on the stack, so that it doesn't allocate.
+        let mut sorted = [0.0; WINDOW];
+        let sorted = &mut sorted[..window.len()];
+        sorted.copy_from_slice(window);
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let index = (sorted.len() * 95).div_ceil(100) - 1;
+        Duration::from_secs_f64(sorted[index] / 1000.0)
+    }
+
+    /// Returns the statistics as a short line of text, for a debug HUD, like `60.0 fps | 16.7 ms avg | 18.2 ms p95 | 33.4 ms max`.
+    pub fn overlay_string(&self) -> String {
+        format!(
+            "{:.1} fps | {:.1} ms avg | {:.1} ms p95 | {:.1} ms max",
+            self.fps(),
+            self.average_frame_time().as_secs_f64() * 1000.0,
+            self.frame_time_p95().as_secs_f64() * 1000.0,
+            self.worst_frame_time().as_secs_f64() * 1000.0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(frame_time_ms: u64) -> Duration {
+        Duration::from_millis(frame_time_ms)
+    }
+
+    #[test]
+    fn no_frames_give_zeros() {
+        let stats = FrameStats::new();
+
+        assert_eq!(stats.average_frame_time(), Duration::ZERO);
+        assert_eq!(stats.fps(), 0.0);
+        assert_eq!(stats.worst_frame_time(), Duration::ZERO);
+        assert_eq!(stats.frame_time_p95(), Duration::ZERO);
+    }
+
+    #[test]
+    fn average_starts_at_the_first_frame_and_moves_slowly() {
+        let mut stats = FrameStats::new();
+
+        stats.add_frame_time(ms(20));
+        assert_eq!(stats.average_frame_time(), ms(20));
+        assert_eq!(stats.fps(), 50.0);
+
+        stats.add_frame_time(ms(10));
+        assert!((stats.average_frame_time().as_secs_f64() * 1000.0 - 19.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn worst_and_p95_cover_the_last_120_frames() {
+        let mut stats = FrameStats::new();
+        for frame_time_ms in 1..=100 {
+            stats.add_frame_time(ms(frame_time_ms));
+        }
+        assert_eq!(stats.worst_frame_time(), ms(100));
+        assert_eq!(stats.frame_time_p95(), ms(95));
+
+        // The slow frames leave the window once 120 newer ones are measured.
+        for _ in 0..120 {
+            stats.add_frame_time(ms(16));
+        }
+        assert_eq!(stats.worst_frame_time(), ms(16));
+        assert_eq!(stats.frame_time_p95(), ms(16));
+    }
+
+    #[test]
+    fn overlay_string_has_every_statistic() {
+        let mut stats = FrameStats::new();
+        stats.add_frame_time(ms(20));
+
+        assert_eq!(
+            stats.overlay_string(),
+            "50.0 fps | 20.0 ms avg | 20.0 ms p95 | 20.0 ms max"
+        );
+    }
+}
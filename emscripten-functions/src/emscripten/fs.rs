@@ -0,0 +1,152 @@
+//! Functions for persisting files across sessions with emscripten's [IDBFS] file system,
+//! which keeps a directory of the virtual file system in the browser's IndexedDB.
+//!
+//! Emscripten has no C API for this, so these functions call the JavaScript [`FS`] API with [`run_script`].
+//! They require linking with `-lidbfs.js`, and with `-sFORCE_FILESYSTEM` if nothing else pulls in the file system.
+//!
+//! The files live in memory, and they're only copied to or from IndexedDB with [`syncfs`]:
+//! once at startup to load them, and after each change to save them.
+//!
+//! [IDBFS]: https://emscripten.org/docs/api_reference/Filesystem-API.html#filesystem-api-idbfs
+//! [`FS`]: https://emscripten.org/docs/api_reference/Filesystem-API.html
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    os::raw::c_int,
+};
+
+use super::{js_string_literal, run_script, run_script_string};
+
+/// Mounts an IDBFS file system at the given directory of the virtual file system, creating the directory if needed.
+/// The files already stored in it are only there after calling [`syncfs`] with `populate` set to `true`.
+///
+/// # Arguments
+/// * `mount_point` - The directory to mount the file system at, like `/saves`.
+///
+/// # Return value
+/// Returns the JavaScript error message if the file system couldn't be mounted, e.g. because the program wasn't linked with `-lidbfs.js`.
+///
+/// # Examples
+/// ```rust
+/// fs::mount_idbfs("/saves").unwrap();
+/// fs::syncfs(true, |result| {
+///     if result.is_ok() && std::path::Path::new("/saves/save1").exists() {
+///         load_game("/saves/save1");
+///     }
+/// });
+/// ```
+pub fn mount_idbfs<T>(mount_point: T) -> Result<(), String>
+where
+    T: AsRef<str>,
+{
+    let error = run_script_string(format!(
+        r#"
+            (function (mountPoint) {{
+                try {{
+                    if (!FS.analyzePath(mountPoint).exists) {{
+                        FS.mkdirTree(mountPoint);
+                    }}
+                    FS.mount(IDBFS, {{}}, mountPoint);
+                    return null;
+                }} catch (e) {{
+                    return String(e && e.message || e);
+                }}
+            }})({})
+        "#,
+        js_string_literal(mount_point.as_ref())
+    ));
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+// The function to call once a `syncfs` call finishes.
+type SyncfsCallback = Box<dyn FnOnce(Result<(), String>)>;
+
+// The callbacks of the pending `syncfs` calls, under the id given to the JavaScript callback.
+thread_local! {
+    static SYNCFS_CALLBACKS: RefCell<HashMap<c_int, SyncfsCallback>> = RefCell::new(HashMap::new());
+    static LAST_SYNCFS_ID: Cell<c_int> = const { Cell::new(0) };
+}
+
+// Called by the JavaScript callback of `FS.syncfs`, as started by `syncfs`.
+// Passing a string from JavaScript would need the page to export memory allocation functions too,
+// so the error message, if any, is left on the `Module` object, and it's read from here with a script.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn emscripten_functions_syncfs_done(id: c_int, failed: c_int) {
+    let result = if failed != 0 {
+        Err(run_script_string(format!(
+            r#"
+                (function () {{
+                    var error = Module["emscriptenFunctionsSyncfsErrors"][{id}];
+                    delete Module["emscriptenFunctionsSyncfsErrors"][{id}];
+                    return error;
+                }})()
+            "#
+        ))
+        .unwrap_or_default())
+    } else {
+        Ok(())
+    };
+
+    let callback = SYNCFS_CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().remove(&id));
+    if let Some(callback) = callback {
+        callback(result);
+    }
+}
+
+/// Synchronizes all the mounted IDBFS file systems with the browser's IndexedDB, asynchronously, using the JavaScript [`FS.syncfs`].
+/// Once it's done, `on_done` gets called, with the JavaScript error message if it failed.
+///
+/// The result reaches the function through an exported function,
+/// so `_emscripten_functions_syncfs_done` must be added to the `-sEXPORTED_FUNCTIONS` link argument.
+///
+/// [`FS.syncfs`]: https://emscripten.org/docs/api_reference/Filesystem-API.html#FS.syncfs
+///
+/// # Arguments
+/// * `populate` - If `true`, the files stored in IndexedDB are loaded into the virtual file system, e.g. at startup.
+///   If `false`, the files of the virtual file system are saved to IndexedDB, e.g. after writing a save file.
+/// * `on_done` - The function to call once the synchronization finishes.
+///
+/// # Examples
+/// ```rust
+/// std::fs::write("/saves/save1", &save_data).unwrap();
+/// fs::syncfs(false, |result| {
+///     if let Err(err) = result {
+///         console::error(format!("Couldn't save the game: {}", err));
+///     }
+/// });
+/// ```
+pub fn syncfs<F>(populate: bool, on_done: F)
+where
+    F: 'static + FnOnce(Result<(), String>),
+{
+    let id = LAST_SYNCFS_ID.with(|last_id| last_id.get().wrapping_add(1));
+    LAST_SYNCFS_ID.with(|last_id| last_id.set(id));
+    SYNCFS_CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref.borrow_mut().insert(id, Box::new(on_done));
+    });
+
+    // A failure to even start, e.g. without `-lidbfs.js`, goes through the same callback.
+    run_script(format!(
+        r#"
+            (function (done) {{
+                try {{
+                    FS.syncfs({populate}, done);
+                }} catch (e) {{
+                    done(e);
+                }}
+            }})(function (error) {{
+                if (error) {{
+                    Module["emscriptenFunctionsSyncfsErrors"] = Module["emscriptenFunctionsSyncfsErrors"] || {{}};
+                    Module["emscriptenFunctionsSyncfsErrors"][{id}] = String(error && error.message || error);
+                }}
+                Module["_emscripten_functions_syncfs_done"]({id}, error ? 1 : 0);
+            }});
+        "#
+    ));
+}
@@ -0,0 +1,890 @@
+//! Functions (with rust-native parameter and return value types) for storing data in the browser's IndexedDB,
+//! from the "IndexedDB" section of the emscripten [`emscripten.h`] header file.
+//! Each database is a key-value store, which is a good fit e.g. for save games.
+//!
+//! The functions without the `async_` prefix block until the operation finishes, so they require linking with `-sASYNCIFY`,
+//! and return [`IdbError::AsyncifyUnavailable`] otherwise.
+//! The `async_` ones call the given functions once the operation finishes instead, and work in any build.
+//!
+//! Database names and keys can't contain NUL characters: the blocking functions return [`IdbError::InvalidName`] for them,
+//! and the `async_` ones call their error function.
+//!
+//! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html#indexeddb
+
+use std::{
+    ffi::CString,
+    fmt::Display,
+    os::raw::{c_int, c_void},
+    ptr::null_mut,
+};
+
+use emscripten_functions_sys::emscripten;
+
+use super::{require_asyncify, AsyncifyUnavailable};
+
+/// The error returned by the blocking IndexedDB functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdbError {
+    /// The operation failed, e.g. because the key doesn't exist or the browser blocks storage.
+    ///
+    /// Emscripten doesn't tell the reason of the failure.
+    Failed,
+    /// The program wasn't built with `-sASYNCIFY`, so the operation wasn't attempted.
+    AsyncifyUnavailable,
+    /// A size or an offset is too big for emscripten, which takes them as 32-bit integers,
+    /// so the operation wasn't attempted.
+    TooLarge,
+    /// The name of the database or the key contains a NUL character, which emscripten can't take,
+    /// so the operation wasn't attempted.
+    InvalidName,
+}
+impl Display for IdbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdbError::Failed => write!(f, "IndexedDB operation failed"),
+            IdbError::AsyncifyUnavailable => write!(f, "{}", AsyncifyUnavailable),
+            IdbError::TooLarge => write!(f, "Size or offset too large for IndexedDB"),
+            IdbError::InvalidName => write!(f, "Database name or key contains a NUL character"),
+        }
+    }
+}
+impl From<AsyncifyUnavailable> for IdbError {
+    fn from(_: AsyncifyUnavailable) -> Self {
+        IdbError::AsyncifyUnavailable
+    }
+}
+
+// Turns the given database name and key into C strings.
+// They are rejected if they contain NUL characters, as removing those would address other data.
+fn db_key_cstrings(db: &str, key: &str) -> Result<(CString, CString), IdbError> {
+    match (CString::new(db), CString::new(key)) {
+        (Ok(db), Ok(key)) => Ok((db, key)),
+        _ => Err(IdbError::InvalidName),
+    }
+}
+
+// Turns the `perror` value set by the `emscripten_idb_*` functions into a `Result`.
+fn check_error(error: c_int) -> Result<(), IdbError> {
+    if error != 0 {
+        return Err(IdbError::Failed);
+    }
+    Ok(())
+}
+
+/// Loads the data stored under the given key in the given database, using the emscripten-defined [`emscripten_idb_load`].
+///
+/// [`emscripten_idb_load`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_load
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+///
+/// # Examples
+/// ```rust
+/// match idb::load("mygame", "save1") {
+///     Ok(save) => println!("Loaded a {} bytes save", save.len()),
+///     Err(_) => println!("There's no save yet"),
+/// }
+/// ```
+pub fn load<T, U>(db: T, key: U) -> Result<Vec<u8>, IdbError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    require_asyncify()?;
+
+    let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+    let mut buffer: *mut c_void = null_mut();
+    let mut num: c_int = 0;
+    let mut error: c_int = 0;
+    unsafe {
+        emscripten::emscripten_idb_load(
+            db.as_ptr(),
+            key.as_ptr(),
+            &mut buffer,
+            &mut num,
+            &mut error,
+        );
+    }
+
+    // The buffer, if any, is allocated with `malloc`, and it's ours to free, even on error.
+    let data = if buffer.is_null() || num <= 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(buffer as *const u8, num as usize) }.to_vec()
+    };
+    if !buffer.is_null() {
+        unsafe { super::free(buffer) };
+    }
+
+    check_error(error)?;
+    Ok(data)
+}
+
+/// Stores the given data under the given key in the given database, replacing any previous data, using the emscripten-defined [`emscripten_idb_store`].
+///
+/// [`emscripten_idb_store`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_store
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+/// * `data` - The data to store.
+///
+/// # Examples
+/// ```rust
+/// idb::store("mygame", "save1", b"level=3").unwrap();
+/// ```
+pub fn store<T, U>(db: T, key: U, data: &[u8]) -> Result<(), IdbError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    require_asyncify()?;
+    let len = c_int::try_from(data.len()).map_err(|_| IdbError::TooLarge)?;
+
+    let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+    let mut error: c_int = 0;
+    unsafe {
+        // Emscripten only reads the buffer, despite the `*mut` pointer.
+        emscripten::emscripten_idb_store(
+            db.as_ptr(),
+            key.as_ptr(),
+            data.as_ptr() as *mut c_void,
+            len,
+            &mut error,
+        );
+    }
+
+    check_error(error)
+}
+
+/// Deletes the data stored under the given key in the given database, using the emscripten-defined [`emscripten_idb_delete`].
+///
+/// [`emscripten_idb_delete`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_delete
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+///
+/// # Examples
+/// ```rust
+/// idb::delete("mygame", "save1").unwrap();
+/// ```
+pub fn delete<T, U>(db: T, key: U) -> Result<(), IdbError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    require_asyncify()?;
+
+    let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+    let mut error: c_int = 0;
+    unsafe {
+        emscripten::emscripten_idb_delete(db.as_ptr(), key.as_ptr(), &mut error);
+    }
+
+    check_error(error)
+}
+
+/// Returns whether there is data stored under the given key in the given database, using the emscripten-defined [`emscripten_idb_exists`].
+///
+/// [`emscripten_idb_exists`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_exists
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+///
+/// # Examples
+/// ```rust
+/// if idb::exists("mygame", "save1").unwrap_or(false) {
+///     println!("Continue your game?");
+/// }
+/// ```
+pub fn exists<T, U>(db: T, key: U) -> Result<bool, IdbError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+{
+    require_asyncify()?;
+
+    let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+    let mut exists: c_int = 0;
+    let mut error: c_int = 0;
+    unsafe {
+        emscripten::emscripten_idb_exists(db.as_ptr(), key.as_ptr(), &mut exists, &mut error);
+    }
+
+    check_error(error)?;
+    Ok(exists != 0)
+}
+
+/// Data stored in IndexedDB as a browser blob, that can be read in parts, instead of being copied whole into memory.
+/// It's useful for large values, like multi-megabyte saves or cached downloads.
+///
+/// Like the other blocking functions here, its functions require linking with `-sASYNCIFY`.
+///
+/// The blob is freed when dropped, using the emscripten-defined [`emscripten_idb_free_blob`].
+///
+/// [`emscripten_idb_free_blob`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_free_blob
+///
+/// # Examples
+/// ```rust
+/// idb::Blob::store("mygame", "world", &vec![0; 16 * 1024 * 1024]).unwrap();
+///
+/// let blob = idb::Blob::load("mygame", "world").unwrap();
+/// let mut chunk = [0; 4096];
+/// let read = blob.read(8192, &mut chunk).unwrap();
+/// println!("Got {} bytes", read);
+/// ```
+#[derive(Debug)]
+pub struct Blob {
+    handle: c_int,
+}
+impl Blob {
+    /// Loads the blob stored under the given key in the given database, using the emscripten-defined [`emscripten_idb_load_blob`].
+    ///
+    /// [`emscripten_idb_load_blob`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_load_blob
+    ///
+    /// # Arguments
+    /// * `db` - The name of the database.
+    /// * `key` - The key of the blob.
+    pub fn load<T, U>(db: T, key: U) -> Result<Self, IdbError>
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        require_asyncify()?;
+
+        let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+        let mut handle: c_int = 0;
+        let mut error: c_int = 0;
+        unsafe {
+            emscripten::emscripten_idb_load_blob(
+                db.as_ptr(),
+                key.as_ptr(),
+                &mut handle,
+                &mut error,
+            );
+        }
+
+        check_error(error)?;
+        Ok(Blob { handle })
+    }
+
+    /// Stores the given data as a blob under the given key in the given database, replacing any previous data,
+    /// using the emscripten-defined [`emscripten_idb_store_blob`].
+    ///
+    /// [`emscripten_idb_store_blob`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_store_blob
+    ///
+    /// # Arguments
+    /// * `db` - The name of the database.
+    /// * `key` - The key of the blob.
+    /// * `data` - The data to store.
+    pub fn store<T, U>(db: T, key: U, data: &[u8]) -> Result<(), IdbError>
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        require_asyncify()?;
+        let len = c_int::try_from(data.len()).map_err(|_| IdbError::TooLarge)?;
+
+        let (db, key) = db_key_cstrings(db.as_ref(), key.as_ref())?;
+
+        let mut error: c_int = 0;
+        unsafe {
+            // Emscripten only reads the buffer, despite the `*mut` pointer.
+            emscripten::emscripten_idb_store_blob(
+                db.as_ptr(),
+                key.as_ptr(),
+                data.as_ptr() as *mut c_void,
+                len,
+                &mut error,
+            );
+        }
+
+        check_error(error)
+    }
+
+    /// Reads the blob's data starting at the given offset into the given buffer,
+    /// using the emscripten-defined [`emscripten_idb_read_from_blob`].
+    ///
+    /// It returns the number of bytes read, which is less than the buffer's length if the blob ends before filling it,
+    /// and 0 if the offset is at or past the end of the blob.
+    /// Only that many bytes at the start of the buffer are written.
+    ///
+    /// Emscripten doesn't report reads going past the end of the blob, it just leaves the buffer untouched,
+    /// so such reads are found out by probing single bytes first, which makes them slower.
+    ///
+    /// It returns [`IdbError::TooLarge`], without reading anything, if the offset or the end of the read
+    /// doesn't fit in the 32-bit integers used by emscripten, i.e. it's past 2 GiB.
+    ///
+    /// [`emscripten_idb_read_from_blob`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_read_from_blob
+    ///
+    /// # Arguments
+    /// * `offset` - The position in the blob to start reading from.
+    /// * `buf` - The buffer to fill.
+    pub fn read(&self, offset: usize, buf: &mut [u8]) -> Result<usize, IdbError> {
+        read_checked(offset, buf, |start, chunk| unsafe {
+            emscripten::emscripten_idb_read_from_blob(
+                self.handle,
+                start,
+                chunk.len() as c_int,
+                chunk.as_mut_ptr() as *mut c_void,
+            );
+        })
+    }
+}
+
+// Reads with the given function, which fills the whole buffer if the range is in the blob, and does nothing otherwise,
+// like `emscripten_idb_read_from_blob`, returning the number of bytes read.
+fn read_checked<F>(offset: usize, buf: &mut [u8], mut read_raw: F) -> Result<usize, IdbError>
+where
+    F: FnMut(c_int, &mut [u8]),
+{
+    let start = c_int::try_from(offset).map_err(|_| IdbError::TooLarge)?;
+    let len = c_int::try_from(buf.len()).map_err(|_| IdbError::TooLarge)?;
+    // Emscripten checks the range by adding them up.
+    start.checked_add(len).ok_or(IdbError::TooLarge)?;
+
+    // Whether the blob has a byte at the given position, told by reading it into a probe byte
+    // that must change if the read happens: first a 0, and if it's still 0, a 1.
+    let mut has_byte = |position: c_int| {
+        let mut probe = [0];
+        read_raw(position, &mut probe);
+        if probe[0] != 0 {
+            return true;
+        }
+        probe[0] = 1;
+        read_raw(position, &mut probe);
+        probe[0] == 0
+    };
+
+    // The number of bytes that can be read, with a binary search if the blob ends before the end of the buffer.
+    let available = if len == 0 || has_byte(start + len - 1) {
+        len
+    } else {
+        let mut found = 0;
+        let mut missing = len;
+        while missing - found > 1 {
+            let middle = found + (missing - found) / 2;
+            if has_byte(start + middle - 1) {
+                found = middle;
+            } else {
+                missing = middle;
+            }
+        }
+        found
+    };
+
+    if available > 0 {
+        read_raw(start, &mut buf[..available as usize]);
+    }
+    Ok(available as usize)
+}
+impl Drop for Blob {
+    fn drop(&mut self) {
+        unsafe { emscripten::emscripten_idb_free_blob(self.handle) };
+    }
+}
+
+/// Loads the data stored under the given key in the given database, asynchronously, using the emscripten-defined [`emscripten_idb_async_load`].
+/// When the operation finishes, either `on_load` gets called with the data or `on_error` gets called, and both get dropped.
+///
+/// [`emscripten_idb_async_load`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_async_load
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+/// * `on_load` - The function to call with the loaded data.
+/// * `on_error` - The function to call if the data couldn't be loaded.
+///
+/// # Examples
+/// ```rust
+/// idb::async_store(
+///     "mygame",
+///     "save1",
+///     b"level=3",
+///     || {
+///         idb::async_load(
+///             "mygame",
+///             "save1",
+///             |save| println!("The save is: {}", String::from_utf8_lossy(&save)),
+///             || println!("Couldn't load the save"),
+///         );
+///     },
+///     || println!("Couldn't store the save"),
+/// );
+/// ```
+pub fn async_load<T, U, F, G>(db: T, key: U, on_load: F, on_error: G)
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    F: 'static + FnOnce(Vec<u8>),
+    G: 'static + FnOnce(),
+{
+    let Ok((db, key)) = db_key_cstrings(db.as_ref(), key.as_ref()) else {
+        // The operation isn't attempted, and fails from the event loop, like it does otherwise.
+        super::set_timeout(0.0, on_error);
+        return;
+    };
+
+    // Both callbacks travel together through the `arg` pointer, and whichever gets called takes back the ownership of both.
+    let callbacks = Box::into_raw(Box::new((on_load, on_error)));
+
+    unsafe extern "C" fn onload_wrapper<F, G>(arg: *mut c_void, buffer: *mut c_void, num: c_int)
+    where
+        F: 'static + FnOnce(Vec<u8>),
+        G: 'static + FnOnce(),
+    {
+        let (on_load, _) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+
+        // Emscripten frees the buffer after this call, so we copy it.
+        let data = if buffer.is_null() || num <= 0 {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(buffer as *const u8, num as usize) }.to_vec()
+        };
+        on_load(data);
+    }
+
+    unsafe {
+        emscripten::emscripten_idb_async_load(
+            db.as_ptr(),
+            key.as_ptr(),
+            callbacks as *mut c_void,
+            Some(onload_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+/// Stores the given data under the given key in the given database, replacing any previous data, asynchronously,
+/// using the emscripten-defined [`emscripten_idb_async_store`].
+/// When the operation finishes, either `on_store` or `on_error` gets called, and both get dropped.
+///
+/// The data is copied before this function returns.
+///
+/// [`emscripten_idb_async_store`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_async_store
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+/// * `data` - The data to store.
+/// * `on_store` - The function to call once the data is stored.
+/// * `on_error` - The function to call if the data couldn't be stored.
+///
+/// # Examples
+/// ```rust
+/// idb::async_store(
+///     "mygame",
+///     "save1",
+///     b"level=3",
+///     || println!("Saved"),
+///     || println!("Couldn't save"),
+/// );
+/// ```
+pub fn async_store<T, U, F, G>(db: T, key: U, data: &[u8], on_store: F, on_error: G)
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    F: 'static + FnOnce(),
+    G: 'static + FnOnce(),
+{
+    let Ok((db, key)) = db_key_cstrings(db.as_ref(), key.as_ref()) else {
+        // The operation isn't attempted, and fails from the event loop, like it does otherwise.
+        super::set_timeout(0.0, on_error);
+        return;
+    };
+    let Ok(len) = c_int::try_from(data.len()) else {
+        // Same for data whose length doesn't fit emscripten's 32-bit integer.
+        super::set_timeout(0.0, on_error);
+        return;
+    };
+
+    let callbacks = Box::into_raw(Box::new((on_store, on_error)));
+
+    unsafe {
+        // Emscripten only reads the buffer, despite the `*mut` pointer.
+        emscripten::emscripten_idb_async_store(
+            db.as_ptr(),
+            key.as_ptr(),
+            data.as_ptr() as *mut c_void,
+            len,
+            callbacks as *mut c_void,
+            Some(ondone_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+/// Deletes the data stored under the given key in the given database, asynchronously, using the emscripten-defined [`emscripten_idb_async_delete`].
+/// When the operation finishes, either `on_delete` or `on_error` gets called, and both get dropped.
+///
+/// [`emscripten_idb_async_delete`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_async_delete
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+/// * `on_delete` - The function to call once the data is deleted.
+/// * `on_error` - The function to call if the data couldn't be deleted.
+///
+/// # Examples
+/// ```rust
+/// idb::async_delete(
+///     "mygame",
+///     "save1",
+///     || println!("Deleted the save"),
+///     || println!("Couldn't delete the save"),
+/// );
+/// ```
+pub fn async_delete<T, U, F, G>(db: T, key: U, on_delete: F, on_error: G)
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    F: 'static + FnOnce(),
+    G: 'static + FnOnce(),
+{
+    let Ok((db, key)) = db_key_cstrings(db.as_ref(), key.as_ref()) else {
+        // The operation isn't attempted, and fails from the event loop, like it does otherwise.
+        super::set_timeout(0.0, on_error);
+        return;
+    };
+
+    let callbacks = Box::into_raw(Box::new((on_delete, on_error)));
+
+    unsafe {
+        emscripten::emscripten_idb_async_delete(
+            db.as_ptr(),
+            key.as_ptr(),
+            callbacks as *mut c_void,
+            Some(ondone_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+/// Checks whether there is data stored under the given key in the given database, asynchronously,
+/// using the emscripten-defined [`emscripten_idb_async_exists`].
+/// When the operation finishes, either `on_check` gets called with the answer or `on_error` gets called, and both get dropped.
+///
+/// [`emscripten_idb_async_exists`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_idb_async_exists
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the data.
+/// * `on_check` - The function to call with whether the data exists.
+/// * `on_error` - The function to call if the check failed.
+///
+/// # Examples
+/// ```rust
+/// idb::async_exists(
+///     "mygame",
+///     "save1",
+///     |exists| {
+///         if exists {
+///             println!("Continue your game?");
+///         }
+///     },
+///     || println!("Couldn't look for a save"),
+/// );
+/// ```
+pub fn async_exists<T, U, F, G>(db: T, key: U, on_check: F, on_error: G)
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    F: 'static + FnOnce(bool),
+    G: 'static + FnOnce(),
+{
+    let Ok((db, key)) = db_key_cstrings(db.as_ref(), key.as_ref()) else {
+        // The operation isn't attempted, and fails from the event loop, like it does otherwise.
+        super::set_timeout(0.0, on_error);
+        return;
+    };
+
+    let callbacks = Box::into_raw(Box::new((on_check, on_error)));
+
+    unsafe extern "C" fn oncheck_wrapper<F, G>(arg: *mut c_void, exists: c_int)
+    where
+        F: 'static + FnOnce(bool),
+        G: 'static + FnOnce(),
+    {
+        let (on_check, _) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+        on_check(exists != 0);
+    }
+
+    unsafe {
+        emscripten::emscripten_idb_async_exists(
+            db.as_ptr(),
+            key.as_ptr(),
+            callbacks as *mut c_void,
+            Some(oncheck_wrapper::<F, G>),
+            Some(onerror_wrapper::<F, G>),
+        );
+    }
+}
+
+// The success callback of the `async_` functions whose success function takes no arguments.
+unsafe extern "C" fn ondone_wrapper<F, G>(arg: *mut c_void)
+where
+    F: 'static + FnOnce(),
+    G: 'static + FnOnce(),
+{
+    let (on_done, _) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+    on_done();
+}
+
+// The error callback of all the `async_` functions, whose callbacks are passed as a boxed `(on_success, on_error)` pair.
+unsafe extern "C" fn onerror_wrapper<F, G>(arg: *mut c_void)
+where
+    G: 'static + FnOnce(),
+{
+    let (_, on_error) = *unsafe { Box::from_raw(arg as *mut (F, G)) };
+    on_error();
+}
+
+// The first byte of the values stored by `store_value`, to be bumped when their format changes.
+#[cfg(feature = "serde")]
+const VALUE_FORMAT_VERSION: u8 = 1;
+
+/// The error returned by [`store_value`] and [`load_value`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdbValueError {
+    /// There is no value stored under the key.
+    Missing,
+    /// The stored value wasn't written by [`store_value`], was written by an incompatible version of it,
+    /// or it doesn't fit the requested type.
+    Corrupt,
+    /// The value couldn't be serialized.
+    Serialize,
+    /// The IndexedDB operation failed.
+    Backend(IdbError),
+}
+#[cfg(feature = "serde")]
+impl Display for IdbValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IdbValueError::Missing => write!(f, "No value stored under the key"),
+            IdbValueError::Corrupt => write!(f, "The stored value is corrupt"),
+            IdbValueError::Serialize => write!(f, "The value couldn't be serialized"),
+            IdbValueError::Backend(err) => write!(f, "{}", err),
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl From<IdbError> for IdbValueError {
+    fn from(err: IdbError) -> Self {
+        IdbValueError::Backend(err)
+    }
+}
+
+/// Serializes the given value and stores it under the given key in the given database, replacing any previous data.
+/// The value can be loaded back with [`load_value`].
+///
+/// Like [`store`], it requires linking with `-sASYNCIFY`.
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the value.
+/// * `value` - The value to store.
+///
+/// # Examples
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct Settings {
+///     volume: u8,
+///     fullscreen: bool,
+/// }
+///
+/// idb::store_value("mygame", "settings", &Settings { volume: 80, fullscreen: true }).unwrap();
+/// ```
+#[cfg(feature = "serde")]
+pub fn store_value<T, U, V>(db: T, key: U, value: &V) -> Result<(), IdbValueError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    V: serde::Serialize + ?Sized,
+{
+    let data = postcard::to_extend(value, vec![VALUE_FORMAT_VERSION])
+        .map_err(|_| IdbValueError::Serialize)?;
+
+    store(db, key, &data)?;
+    Ok(())
+}
+
+/// Loads and deserializes the value stored under the given key in the given database with [`store_value`].
+///
+/// Like [`load`], it requires linking with `-sASYNCIFY`.
+///
+/// # Arguments
+/// * `db` - The name of the database.
+/// * `key` - The key of the value.
+///
+/// # Examples
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     volume: u8,
+///     fullscreen: bool,
+/// }
+///
+/// match idb::load_value::<_, _, Settings>("mygame", "settings") {
+///     Ok(settings) => println!("The volume is {}", settings.volume),
+///     Err(idb::IdbValueError::Missing) => println!("Using the default settings"),
+///     Err(err) => println!("Couldn't load the settings: {}", err),
+/// }
+/// ```
+#[cfg(feature = "serde")]
+pub fn load_value<T, U, V>(db: T, key: U) -> Result<V, IdbValueError>
+where
+    T: AsRef<str>,
+    U: AsRef<str>,
+    V: serde::de::DeserializeOwned,
+{
+    // Emscripten reports a missing key as any other failure, so we check for it first.
+    if !exists(db.as_ref(), key.as_ref())? {
+        return Err(IdbValueError::Missing);
+    }
+
+    let data = load(db, key)?;
+    match data.split_first() {
+        Some((&VALUE_FORMAT_VERSION, value)) => {
+            postcard::from_bytes(value).map_err(|_| IdbValueError::Corrupt)
+        }
+        _ => Err(IdbValueError::Corrupt),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads from the given data like `emscripten_idb_read_from_blob` does, counting the reads.
+    fn fake_blob<'a>(data: &'a [u8], reads: &'a mut usize) -> impl FnMut(c_int, &mut [u8]) + 'a {
+        move |start, buf| {
+            *reads += 1;
+            let start = start as usize;
+            if let Some(range) = data.get(start..start + buf.len()) {
+                buf.copy_from_slice(range);
+            }
+        }
+    }
+
+    #[test]
+    fn names_with_nul_are_rejected() {
+        assert_eq!(
+            db_key_cstrings("mygame", "save1"),
+            Ok((
+                CString::new("mygame").unwrap(),
+                CString::new("save1").unwrap()
+            ))
+        );
+        assert_eq!(
+            db_key_cstrings("my\0game", "save1"),
+            Err(IdbError::InvalidName)
+        );
+        assert_eq!(
+            db_key_cstrings("mygame", "save1\0"),
+            Err(IdbError::InvalidName)
+        );
+    }
+
+    #[test]
+    fn read_inside_the_blob_fills_the_buffer() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut reads = 0;
+        let mut buf = [0xAA; 10];
+
+        assert_eq!(
+            read_checked(20, &mut buf, fake_blob(&data, &mut reads)),
+            Ok(10)
+        );
+        assert_eq!(buf, data[20..30]);
+        // The last byte is 29, so a single probe is enough.
+        assert_eq!(reads, 2);
+    }
+
+    #[test]
+    fn read_of_zeros_is_told_apart_from_a_missing_range() {
+        let data = [0; 16];
+        let mut reads = 0;
+        let mut buf = [0xAA; 16];
+
+        assert_eq!(
+            read_checked(0, &mut buf, fake_blob(&data, &mut reads)),
+            Ok(16)
+        );
+        assert_eq!(buf, [0; 16]);
+    }
+
+    #[test]
+    fn read_past_the_end_is_partial() {
+        let data: Vec<u8> = (1..=100).collect();
+        let mut reads = 0;
+        let mut buf = [0; 10];
+
+        assert_eq!(
+            read_checked(95, &mut buf, fake_blob(&data, &mut reads)),
+            Ok(5)
+        );
+        assert_eq!(buf[..5], data[95..]);
+        assert_eq!(buf[5..], [0; 5]);
+    }
+
+    #[test]
+    fn read_at_or_after_the_end_reads_nothing() {
+        let data = [7; 8];
+        let mut reads = 0;
+        let mut buf = [0xAA; 4];
+
+        assert_eq!(
+            read_checked(8, &mut buf, fake_blob(&data, &mut reads)),
+            Ok(0)
+        );
+        assert_eq!(
+            read_checked(1000, &mut buf, fake_blob(&data, &mut reads)),
+            Ok(0)
+        );
+        assert_eq!(buf, [0xAA; 4]);
+    }
+
+    #[test]
+    fn empty_read_doesnt_touch_the_blob() {
+        let mut reads = 0;
+
+        assert_eq!(
+            read_checked(3, &mut [], fake_blob(&[1, 2], &mut reads)),
+            Ok(0)
+        );
+        assert_eq!(reads, 0);
+    }
+
+    #[test]
+    fn read_beyond_2_gib_is_too_large() {
+        let mut reads = 0;
+        let mut buf = [0; 4];
+
+        assert_eq!(
+            read_checked(
+                c_int::MAX as usize + 1,
+                &mut buf,
+                fake_blob(&[], &mut reads)
+            ),
+            Err(IdbError::TooLarge)
+        );
+        assert_eq!(
+            read_checked(
+                c_int::MAX as usize - 1,
+                &mut buf,
+                fake_blob(&[], &mut reads)
+            ),
+            Err(IdbError::TooLarge)
+        );
+        assert_eq!(reads, 0);
+    }
+}
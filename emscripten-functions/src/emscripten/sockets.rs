@@ -0,0 +1,378 @@
+//! Functions (with rust-native parameter types) for the socket event callbacks of the emscripten [`emscripten.h`] header file.
+//!
+//! Emscripten implements the POSIX sockets on top of WebSockets, so they can't block while waiting for data.
+//! These callbacks tell when a socket is ready instead, from the browser's event loop,
+//! e.g. to call `recv` only once there's something to read.
+//!
+//! There is a single function for each kind of event, shared by all the sockets, which is given the socket's file descriptor.
+//!
+//! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html#socket-event-registration
+
+use std::{
+    cell::RefCell,
+    ffi::CStr,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    os::raw::{c_char, c_int, c_void},
+};
+
+use emscripten_functions_sys::emscripten;
+
+/// The error given to the function set with [`set_error_callback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SocketError {
+    /// The file descriptor of the socket.
+    pub fd: c_int,
+    /// The `errno` code of the error.
+    pub errno: c_int,
+    /// The description of the error.
+    pub message: String,
+}
+
+type SocketCallback = Box<dyn FnMut(c_int)>;
+type SocketErrorCallback = Box<dyn FnMut(&SocketError)>;
+
+// The kinds of events with a `em_socket_callback`, given to `socket_wrapper` as its argument.
+#[derive(Clone, Copy)]
+enum SocketEvent {
+    Open,
+    Listen,
+    Connection,
+    Message,
+    Close,
+}
+
+#[derive(Default)]
+struct SocketCallbacks {
+    open: Option<SocketCallback>,
+    listen: Option<SocketCallback>,
+    connection: Option<SocketCallback>,
+    message: Option<SocketCallback>,
+    close: Option<SocketCallback>,
+    error: Option<SocketErrorCallback>,
+}
+impl SocketCallbacks {
+    fn slot(&mut self, event: SocketEvent) -> &mut Option<SocketCallback> {
+        match event {
+            SocketEvent::Open => &mut self.open,
+            SocketEvent::Listen => &mut self.listen,
+            SocketEvent::Connection => &mut self.connection,
+            SocketEvent::Message => &mut self.message,
+            SocketEvent::Close => &mut self.close,
+        }
+    }
+}
+
+// The functions set for the socket events.
+// Each one is `None` while it runs, so that it can be replaced from inside itself.
+thread_local! {
+    static CALLBACKS: RefCell<SocketCallbacks> = RefCell::new(SocketCallbacks::default());
+}
+
+unsafe extern "C" fn socket_wrapper(fd: c_int, user_data: *mut c_void) {
+    let event = match user_data as usize {
+        0 => SocketEvent::Open,
+        1 => SocketEvent::Listen,
+        2 => SocketEvent::Connection,
+        3 => SocketEvent::Message,
+        _ => SocketEvent::Close,
+    };
+
+    let callback = CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().slot(event).take());
+    let Some(mut callback) = callback else {
+        return;
+    };
+
+    callback(fd);
+
+    // The function goes back in its place, unless it got replaced while it ran.
+    CALLBACKS.with(|callbacks_ref| {
+        let mut callbacks = callbacks_ref.borrow_mut();
+        let slot = callbacks.slot(event);
+        if slot.is_none() {
+            *slot = Some(callback);
+        }
+    });
+}
+
+unsafe extern "C" fn error_wrapper(
+    fd: c_int,
+    err: c_int,
+    msg: *const c_char,
+    _user_data: *mut c_void,
+) {
+    let callback = CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().error.take());
+    let Some(mut callback) = callback else {
+        return;
+    };
+
+    let message = if msg.is_null() {
+        String::new()
+    } else {
+        unsafe { CStr::from_ptr(msg) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    callback(&SocketError {
+        fd,
+        errno: err,
+        message,
+    });
+
+    CALLBACKS.with(|callbacks_ref| {
+        let mut callbacks = callbacks_ref.borrow_mut();
+        if callbacks.error.is_none() {
+            callbacks.error = Some(callback);
+        }
+    });
+}
+
+// Stores the function for the given event, and registers `socket_wrapper` for it with the given emscripten function.
+fn set_socket_callback<F>(
+    event: SocketEvent,
+    callback: F,
+    register: unsafe extern "C" fn(*mut c_void, emscripten::em_socket_callback),
+) where
+    F: 'static + FnMut(c_int),
+{
+    let previous = CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref
+            .borrow_mut()
+            .slot(event)
+            .replace(Box::new(callback))
+    });
+    // The previous function is dropped outside of the borrow, in case its destructor touches the registry.
+    drop(previous);
+
+    unsafe {
+        register(event as usize as *mut c_void, Some(socket_wrapper));
+    }
+}
+
+/// Sets the function to be called when a socket connects to its server, using the emscripten-defined [`emscripten_set_socket_open_callback`].
+/// It replaces the function previously set.
+///
+/// [`emscripten_set_socket_open_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_open_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the file descriptor of the socket.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_open_callback(|fd| {
+///     println!("Socket {} is connected", fd);
+/// });
+/// ```
+pub fn set_open_callback<F>(callback: F)
+where
+    F: 'static + FnMut(c_int),
+{
+    set_socket_callback(
+        SocketEvent::Open,
+        callback,
+        emscripten::emscripten_set_socket_open_callback,
+    );
+}
+
+/// Sets the function to be called when a socket starts listening with `listen`, using the emscripten-defined [`emscripten_set_socket_listen_callback`].
+/// It replaces the function previously set.
+///
+/// [`emscripten_set_socket_listen_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_listen_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the file descriptor of the socket.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_listen_callback(|fd| {
+///     println!("Socket {} is listening", fd);
+/// });
+/// ```
+pub fn set_listen_callback<F>(callback: F)
+where
+    F: 'static + FnMut(c_int),
+{
+    set_socket_callback(
+        SocketEvent::Listen,
+        callback,
+        emscripten::emscripten_set_socket_listen_callback,
+    );
+}
+
+/// Sets the function to be called when a listening socket gets a new connection, using the emscripten-defined [`emscripten_set_socket_connection_callback`].
+/// It replaces the function previously set.
+///
+/// [`emscripten_set_socket_connection_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_connection_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the file descriptor of the listening socket, on which `accept` can then be called.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_connection_callback(|fd| {
+///     println!("Socket {} has a connection to accept", fd);
+/// });
+/// ```
+pub fn set_connection_callback<F>(callback: F)
+where
+    F: 'static + FnMut(c_int),
+{
+    set_socket_callback(
+        SocketEvent::Connection,
+        callback,
+        emscripten::emscripten_set_socket_connection_callback,
+    );
+}
+
+/// Sets the function to be called when a socket has data to read, using the emscripten-defined [`emscripten_set_socket_message_callback`].
+/// It replaces the function previously set.
+///
+/// The function isn't given the data: it's only told that the socket is readable, and it reads the data with the socket API as usual.
+///
+/// [`emscripten_set_socket_message_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_message_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the file descriptor of the socket, which can then be read without blocking.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_message_callback(|fd| {
+///     let mut buf = [0u8; 1024];
+///     let len = unsafe { libc::recv(fd, buf.as_mut_ptr() as *mut _, buf.len(), 0) };
+///     println!("Socket {} got {} bytes", fd, len);
+/// });
+/// ```
+pub fn set_message_callback<F>(callback: F)
+where
+    F: 'static + FnMut(c_int),
+{
+    set_socket_callback(
+        SocketEvent::Message,
+        callback,
+        emscripten::emscripten_set_socket_message_callback,
+    );
+}
+
+/// Sets the function to be called when a socket gets closed, using the emscripten-defined [`emscripten_set_socket_close_callback`].
+/// It replaces the function previously set.
+///
+/// [`emscripten_set_socket_close_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_close_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the file descriptor of the socket.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_close_callback(|fd| {
+///     println!("Socket {} got closed", fd);
+/// });
+/// ```
+pub fn set_close_callback<F>(callback: F)
+where
+    F: 'static + FnMut(c_int),
+{
+    set_socket_callback(
+        SocketEvent::Close,
+        callback,
+        emscripten::emscripten_set_socket_close_callback,
+    );
+}
+
+/// Sets the function to be called when a socket fails, using the emscripten-defined [`emscripten_set_socket_error_callback`].
+/// It replaces the function previously set.
+///
+/// [`emscripten_set_socket_error_callback`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_set_socket_error_callback
+///
+/// # Arguments
+/// * `callback` - The function to call with the error.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_error_callback(|err| {
+///     println!("Socket {} failed with errno {}: {}", err.fd, err.errno, err.message);
+/// });
+/// ```
+pub fn set_error_callback<F>(callback: F)
+where
+    F: 'static + FnMut(&SocketError),
+{
+    let previous = CALLBACKS
+        .with(|callbacks_ref| callbacks_ref.borrow_mut().error.replace(Box::new(callback)));
+    drop(previous);
+
+    unsafe {
+        emscripten::emscripten_set_socket_error_callback(std::ptr::null_mut(), Some(error_wrapper));
+    }
+}
+
+// The address families and the `sockaddr` layouts of emscripten's libc.
+const AF_INET: u16 = 2;
+const AF_INET6: u16 = 10;
+
+#[repr(C)]
+struct SockaddrIn {
+    family: u16,
+    port: u16,
+    addr: [u8; 4],
+    zero: [u8; 8],
+}
+
+#[repr(C)]
+struct SockaddrIn6 {
+    family: u16,
+    port: u16,
+    flowinfo: u32,
+    addr: [u8; 16],
+    scope_id: u32,
+}
+
+// Big enough for any `sockaddr`, like the C `sockaddr_storage`.
+#[repr(C, align(8))]
+struct SockaddrStorage([u8; 128]);
+
+extern "C" {
+    fn getpeername(fd: c_int, addr: *mut SockaddrStorage, len: *mut u32) -> c_int;
+}
+
+/// Returns the address of the host the given socket is connected to, using the C `getpeername` function.
+/// It's meant for telling the sockets apart in the event callbacks, which are only given their file descriptor.
+///
+/// It returns `None` if the socket isn't connected, or if it isn't an IPv4 or IPv6 one.
+///
+/// # Arguments
+/// * `fd` - The file descriptor of the socket.
+///
+/// # Examples
+/// ```rust
+/// sockets::set_open_callback(|fd| match sockets::peer_address(fd) {
+///     Some(address) => println!("Socket {} is connected to {}", fd, address),
+///     None => println!("Socket {} is connected", fd),
+/// });
+/// ```
+pub fn peer_address(fd: c_int) -> Option<SocketAddr> {
+    let mut storage = SockaddrStorage([0; 128]);
+    let mut len = std::mem::size_of::<SockaddrStorage>() as u32;
+    if unsafe { getpeername(fd, &mut storage, &mut len) } != 0 {
+        return None;
+    }
+
+    let family = u16::from_ne_bytes([storage.0[0], storage.0[1]]);
+    match family {
+        AF_INET => {
+            let addr = unsafe { &*(&storage as *const SockaddrStorage as *const SockaddrIn) };
+            Some(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::from(addr.addr),
+                u16::from_be(addr.port),
+            )))
+        }
+        AF_INET6 => {
+            let addr = unsafe { &*(&storage as *const SockaddrStorage as *const SockaddrIn6) };
+            Some(SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(addr.addr),
+                u16::from_be(addr.port),
+                u32::from_be(addr.flowinfo),
+                addr.scope_id,
+            )))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,230 @@
+//! Functions (with rust-native parameter and return value types) for creating and managing WebGL contexts,
+//! from the emscripten [`html5_webgl.h`] header file.
+//! They are useful for apps that render with their own GL code instead of going through SDL.
+//!
+//! [`html5_webgl.h`]: https://emscripten.org/docs/api_reference/html5.h.html#webgl-context
+
+use std::os::raw::c_int;
+
+use emscripten_functions_sys::html5;
+
+use super::{to_cstring_lossy, EmscriptenError};
+
+/// The power preference hint given to the browser when creating a WebGL context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebGLPowerPreference {
+    /// Let the browser decide.
+    Default,
+    /// Prefer a GPU configuration that uses less power, e.g. an integrated GPU.
+    LowPower,
+    /// Prefer a GPU configuration with more rendering performance, e.g. a discrete GPU.
+    HighPerformance,
+}
+
+/// The attributes of a WebGL context to create with [`create_context`].
+///
+/// It starts with the emscripten defaults (those set by [`emscripten_webgl_init_context_attributes`]),
+/// which can be changed with its builder-style methods.
+///
+/// [`emscripten_webgl_init_context_attributes`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_webgl_init_context_attributes
+///
+/// # Examples
+/// ```rust
+/// let attrs = WebGLContextAttributes::new()
+///     .major_version(2)
+///     .antialias(false)
+///     .stencil(true);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WebGLContextAttributes {
+    attributes: html5::EmscriptenWebGLContextAttributes,
+}
+impl WebGLContextAttributes {
+    /// Returns the default WebGL context attributes.
+    pub fn new() -> Self {
+        let mut attributes = std::mem::MaybeUninit::uninit();
+        unsafe {
+            html5::emscripten_webgl_init_context_attributes(attributes.as_mut_ptr());
+        }
+
+        WebGLContextAttributes {
+            attributes: unsafe { attributes.assume_init() },
+        }
+    }
+
+    /// Sets whether the drawing buffer has an alpha channel.
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.attributes.alpha = alpha;
+        self
+    }
+
+    /// Sets whether the drawing buffer has a depth buffer of at least 16 bits.
+    pub fn depth(mut self, depth: bool) -> Self {
+        self.attributes.depth = depth;
+        self
+    }
+
+    /// Sets whether the drawing buffer has a stencil buffer of at least 8 bits.
+    pub fn stencil(mut self, stencil: bool) -> Self {
+        self.attributes.stencil = stencil;
+        self
+    }
+
+    /// Sets whether antialiasing is performed, if the browser supports it.
+    pub fn antialias(mut self, antialias: bool) -> Self {
+        self.attributes.antialias = antialias;
+        self
+    }
+
+    /// Sets whether the colors in the drawing buffer have premultiplied alpha.
+    pub fn premultiplied_alpha(mut self, premultiplied_alpha: bool) -> Self {
+        self.attributes.premultipliedAlpha = premultiplied_alpha;
+        self
+    }
+
+    /// Sets whether the contents of the drawing buffer are kept after they are presented.
+    pub fn preserve_drawing_buffer(mut self, preserve_drawing_buffer: bool) -> Self {
+        self.attributes.preserveDrawingBuffer = preserve_drawing_buffer;
+        self
+    }
+
+    /// Sets the GPU configuration hint given to the browser.
+    pub fn power_preference(mut self, power_preference: WebGLPowerPreference) -> Self {
+        self.attributes.powerPreference = match power_preference {
+            WebGLPowerPreference::Default => html5::EM_WEBGL_POWER_PREFERENCE_DEFAULT,
+            WebGLPowerPreference::LowPower => html5::EM_WEBGL_POWER_PREFERENCE_LOW_POWER,
+            WebGLPowerPreference::HighPerformance => {
+                html5::EM_WEBGL_POWER_PREFERENCE_HIGH_PERFORMANCE
+            }
+        } as c_int;
+        self
+    }
+
+    /// Sets whether the context creation fails if the browser considers the system's performance to be low.
+    pub fn fail_if_major_performance_caveat(mut self, fail: bool) -> Self {
+        self.attributes.failIfMajorPerformanceCaveat = fail;
+        self
+    }
+
+    /// Sets the major WebGL version of the context: `1` for WebGL 1, `2` for WebGL 2.
+    pub fn major_version(mut self, major_version: c_int) -> Self {
+        self.attributes.majorVersion = major_version;
+        self
+    }
+
+    /// Sets the minor WebGL version of the context.
+    pub fn minor_version(mut self, minor_version: c_int) -> Self {
+        self.attributes.minorVersion = minor_version;
+        self
+    }
+
+    /// Sets whether all the WebGL extensions that don't affect performance get enabled at context creation.
+    pub fn enable_extensions_by_default(mut self, enable: bool) -> Self {
+        self.attributes.enableExtensionsByDefault = enable;
+        self
+    }
+
+    /// Sets whether the drawing buffer gets presented only on explicit swaps, instead of after every frame.
+    pub fn explicit_swap_control(mut self, explicit_swap_control: bool) -> Self {
+        self.attributes.explicitSwapControl = explicit_swap_control;
+        self
+    }
+
+    /// Sets whether the context renders into an offscreen back buffer.
+    pub fn render_via_offscreen_back_buffer(mut self, render: bool) -> Self {
+        self.attributes.renderViaOffscreenBackBuffer = render;
+        self
+    }
+}
+impl Default for WebGLContextAttributes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A handle to a WebGL context created with [`create_context`].
+///
+/// The context is destroyed when this value is dropped.
+#[derive(Debug)]
+pub struct WebGLContext {
+    handle: html5::EMSCRIPTEN_WEBGL_CONTEXT_HANDLE,
+}
+impl WebGLContext {
+    /// Makes this context the current WebGL context of the calling thread, using the emscripten-defined [`emscripten_webgl_make_context_current`].
+    /// All the GL functions called afterwards operate on it.
+    ///
+    /// [`emscripten_webgl_make_context_current`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_webgl_make_context_current
+    pub fn make_current(&self) -> Result<(), EmscriptenError> {
+        EmscriptenError::from_result(unsafe {
+            html5::emscripten_webgl_make_context_current(self.handle)
+        })
+    }
+
+    /// Returns `true` if this context is the current WebGL context of the calling thread.
+    pub fn is_current(&self) -> bool {
+        get_current_context() == Some(self.handle)
+    }
+
+    /// Returns the raw emscripten handle of this context.
+    pub fn handle(&self) -> html5::EMSCRIPTEN_WEBGL_CONTEXT_HANDLE {
+        self.handle
+    }
+}
+impl Drop for WebGLContext {
+    fn drop(&mut self) {
+        unsafe {
+            html5::emscripten_webgl_destroy_context(self.handle);
+        }
+    }
+}
+
+/// Creates a WebGL context on the canvas matching the given target, using the emscripten-defined [`emscripten_webgl_create_context`].
+///
+/// The created context isn't made current, use [`WebGLContext::make_current`] for that.
+///
+/// [`emscripten_webgl_create_context`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_webgl_create_context
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas element, e.g. `"#canvas"`.
+/// * `attrs` - The attributes of the context to create.
+///
+/// # Examples
+/// ```rust
+/// let context = create_context("#canvas", WebGLContextAttributes::new().major_version(2))?;
+/// context.make_current()?;
+///
+/// // Here you call your GL functions.
+/// ```
+pub fn create_context<T>(
+    target: T,
+    attrs: WebGLContextAttributes,
+) -> Result<WebGLContext, EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+
+    let handle =
+        unsafe { html5::emscripten_webgl_create_context(target.as_ptr(), &attrs.attributes) };
+
+    // On failure we get either 0 or a negative `EMSCRIPTEN_RESULT`.
+    match handle as isize {
+        0 => Err(EmscriptenError::Failed),
+        result if result < 0 => Err(EmscriptenError::from_result(result as c_int)
+            .err()
+            .unwrap_or(EmscriptenError::Failed)),
+        _ => Ok(WebGLContext { handle }),
+    }
+}
+
+/// Returns the raw emscripten handle of the current WebGL context of the calling thread,
+/// using the emscripten-defined [`emscripten_webgl_get_current_context`].
+/// It returns `None` if there is no current context.
+///
+/// [`emscripten_webgl_get_current_context`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_webgl_get_current_context
+pub fn get_current_context() -> Option<html5::EMSCRIPTEN_WEBGL_CONTEXT_HANDLE> {
+    match unsafe { html5::emscripten_webgl_get_current_context() } {
+        0 => None,
+        handle => Some(handle),
+    }
+}
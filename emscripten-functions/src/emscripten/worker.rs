@@ -0,0 +1,363 @@
+//! Functions (with rust-native parameter and return value types) for offloading work to web workers,
+//! from the "Worker API" section of the emscripten [`emscripten.h`] header file.
+//!
+//! The worker is a separate program, built with `-sBUILD_AS_WORKER` and exporting the functions to call in it,
+//! e.g. with `-sEXPORTED_FUNCTIONS=_main,_compute`. Such a function has the signature of an `extern "C" fn(*mut c_char, c_int)`,
+//! receiving the data sent by [`Worker::call`], and it answers with [`respond`].
+//!
+//! Unlike the pthreads support, the workers don't need `SharedArrayBuffer`, so they work without the COOP and COEP headers.
+//!
+//! With the `serde` feature, [`TypedWorker`] and the [`worker_handler`](crate::worker_handler) macro
+//! exchange values of any `serde` type instead of raw bytes.
+//!
+//! [`emscripten.h`]: https://emscripten.org/docs/api_reference/emscripten.h.html#worker-api
+
+#[cfg(feature = "serde")]
+use std::fmt::Display;
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    marker::PhantomData,
+    os::raw::{c_char, c_int, c_void},
+};
+
+use emscripten_functions_sys::emscripten;
+
+use super::to_cstring_lossy;
+
+type ReplyCallback = Box<dyn FnOnce(Vec<u8>)>;
+
+// The functions waiting for the reply of a worker, keyed by an id that emscripten gives back to `reply_wrapper`,
+// along with the worker they wait on, so that they can be dropped when it's destroyed.
+thread_local! {
+    static PENDING_CALLS: RefCell<HashMap<usize, (emscripten::worker_handle, ReplyCallback)>> =
+        RefCell::new(HashMap::new());
+    static NEXT_CALL_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+unsafe extern "C" fn reply_wrapper(data: *mut c_char, size: c_int, arg: *mut c_void) {
+    let pending = PENDING_CALLS.with(|calls_ref| calls_ref.borrow_mut().remove(&(arg as usize)));
+    let Some((_, on_reply)) = pending else {
+        return;
+    };
+
+    // The data is freed by emscripten once we return, so it's copied out.
+    let data = if data.is_null() || size <= 0 {
+        Vec::new()
+    } else {
+        unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) }.to_vec()
+    };
+    on_reply(data);
+}
+
+/// A web worker running a program built with `-sBUILD_AS_WORKER`, created with [`Worker::create`].
+///
+/// Dropping it destroys the worker, using the emscripten-defined [`emscripten_destroy_worker`],
+/// along with the functions still waiting for its replies, which never get called.
+///
+/// The replies are handled by the thread that created the worker, so it can't be sent to another thread.
+///
+/// [`emscripten_destroy_worker`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_destroy_worker
+///
+/// # Examples
+/// ```rust
+/// let worker = Worker::create("worker.js");
+/// worker.call("compute", &[1, 2, 3], |reply| {
+///     println!("The worker replied with {} bytes", reply.len());
+/// });
+/// ```
+#[derive(Debug)]
+pub struct Worker {
+    handle: emscripten::worker_handle,
+    // The functions waiting for its replies live in a thread-local registry, which dropping it on another thread would miss.
+    _not_send: PhantomData<*const ()>,
+}
+impl Worker {
+    /// Starts the worker program at the given URL, using the emscripten-defined [`emscripten_create_worker`].
+    ///
+    /// [`emscripten_create_worker`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_create_worker
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the JavaScript file of the worker program.
+    pub fn create<T>(url: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        let url = to_cstring_lossy(url.as_ref());
+        let handle = unsafe { emscripten::emscripten_create_worker(url.as_ptr()) };
+
+        Worker {
+            handle,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Calls the given function of the worker with the given data, using the emscripten-defined [`emscripten_call_worker`].
+    /// The call is queued, and `on_reply` is called with a copy of the data the function responds with using [`respond`].
+    ///
+    /// The worker function must respond exactly once, otherwise `on_reply` is never called.
+    ///
+    /// [`emscripten_call_worker`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_call_worker
+    ///
+    /// # Arguments
+    /// * `func_name` - The name of the worker's exported function to call, without the `_` prefix added by emscripten.
+    /// * `data` - The data to give to the function. It's copied, so it can be dropped after this call.
+    /// * `on_reply` - The function to call with the response.
+    pub fn call<T, F>(&self, func_name: T, data: &[u8], on_reply: F)
+    where
+        T: AsRef<str>,
+        F: 'static + FnOnce(Vec<u8>),
+    {
+        let func_name = to_cstring_lossy(func_name.as_ref());
+
+        let id = NEXT_CALL_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+        PENDING_CALLS.with(|calls_ref| {
+            calls_ref
+                .borrow_mut()
+                .insert(id, (self.handle, Box::new(on_reply)));
+        });
+
+        unsafe {
+            // Emscripten only reads the buffer, despite the `*mut` pointer.
+            emscripten::emscripten_call_worker(
+                self.handle,
+                func_name.as_ptr(),
+                data.as_ptr() as *mut c_char,
+                data.len() as c_int,
+                Some(reply_wrapper),
+                id as *mut c_void,
+            );
+        }
+    }
+
+    /// Returns the number of calls to this worker that haven't been answered yet,
+    /// using the emscripten-defined [`emscripten_get_worker_queue_size`].
+    ///
+    /// [`emscripten_get_worker_queue_size`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_get_worker_queue_size
+    pub fn queue_size(&self) -> c_int {
+        unsafe { emscripten::emscripten_get_worker_queue_size(self.handle) }
+    }
+}
+impl Drop for Worker {
+    fn drop(&mut self) {
+        unsafe { emscripten::emscripten_destroy_worker(self.handle) };
+
+        let callbacks: Vec<ReplyCallback> = PENDING_CALLS.with(|calls_ref| {
+            let mut calls = calls_ref.borrow_mut();
+            let ids: Vec<usize> = calls
+                .iter()
+                .filter(|(_, (handle, _))| *handle == self.handle)
+                .map(|(id, _)| *id)
+                .collect();
+            ids.iter()
+                .filter_map(|id| calls.remove(id))
+                .map(|(_, callback)| callback)
+                .collect()
+        });
+        // The closures get dropped outside of the borrow, in case their destructors touch the registry.
+        drop(callbacks);
+    }
+}
+
+/// Sends the given data as the response to the current call, from the worker side, using the emscripten-defined [`emscripten_worker_respond`].
+/// It must be called exactly once by the worker function called with [`Worker::call`].
+///
+/// [`emscripten_worker_respond`]: https://emscripten.org/docs/api_reference/emscripten.h.html#c.emscripten_worker_respond
+///
+/// # Arguments
+/// * `data` - The data to send back. It's copied, so it can be dropped after this call.
+///
+/// # Examples
+/// ```rust
+/// #[no_mangle]
+/// pub extern "C" fn compute(data: *mut c_char, size: c_int) {
+///     let data = unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) };
+///     let sum: u8 = data.iter().sum();
+///     worker::respond(&[sum]);
+/// }
+/// ```
+pub fn respond(data: &[u8]) {
+    unsafe {
+        // Emscripten only reads the buffer, despite the `*mut` pointer.
+        emscripten::emscripten_worker_respond(data.as_ptr() as *mut c_char, data.len() as c_int);
+    }
+}
+
+/// The error given by [`TypedWorker::call`], or to its reply function.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerMessageError {
+    /// The request couldn't be serialized.
+    Serialize,
+    /// The response couldn't be deserialized into the expected type,
+    /// or the worker function doesn't handle the request type, e.g. it isn't declared with [`worker_handler`](crate::worker_handler).
+    Corrupt,
+}
+#[cfg(feature = "serde")]
+impl Display for WorkerMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkerMessageError::Serialize => write!(f, "The request couldn't be serialized"),
+            WorkerMessageError::Corrupt => write!(f, "The response of the worker is corrupt"),
+        }
+    }
+}
+
+// The id put before each request of a `TypedWorker`, that the worker sends back before its response.
+// Emscripten already matches the responses with their calls, so it only serves at catching a worker function
+// that answers with something other than what `worker_handler` would.
+#[cfg(feature = "serde")]
+thread_local! {
+    static NEXT_TYPED_CALL_ID: Cell<u32> = const { Cell::new(1) };
+}
+
+/// A [`Worker`] whose functions take requests of type `Req` and respond with values of type `Resp`,
+/// serialized with `postcard`. The worker side declares such functions with the [`worker_handler`](crate::worker_handler) macro.
+///
+/// # Examples
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct PathRequest {
+///     from: (u32, u32),
+///     to: (u32, u32),
+/// }
+///
+/// let worker: TypedWorker<PathRequest, Option<Vec<(u32, u32)>>> = TypedWorker::create("pathfinder.js");
+/// worker
+///     .call("find_path", &PathRequest { from: (0, 0), to: (7, 3) }, |path| match path {
+///         Ok(Some(path)) => println!("The path has {} steps", path.len()),
+///         Ok(None) => println!("There is no path"),
+///         Err(err) => println!("The worker failed: {}", err),
+///     })
+///     .unwrap();
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct TypedWorker<Req, Resp> {
+    worker: Worker,
+    _types: PhantomData<fn(&Req) -> Resp>,
+}
+#[cfg(feature = "serde")]
+impl<Req, Resp> TypedWorker<Req, Resp>
+where
+    Req: serde::Serialize,
+    Resp: 'static + serde::de::DeserializeOwned,
+{
+    /// Starts the worker program at the given URL, like [`Worker::create`].
+    ///
+    /// # Arguments
+    /// * `url` - The URL of the JavaScript file of the worker program.
+    pub fn create<T>(url: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        TypedWorker {
+            worker: Worker::create(url),
+            _types: PhantomData,
+        }
+    }
+
+    /// Calls the given function of the worker with the given request, like [`Worker::call`].
+    /// Once the function responds, `on_reply` is called with its deserialized response.
+    ///
+    /// # Arguments
+    /// * `func_name` - The name of the worker function, declared with [`worker_handler`](crate::worker_handler).
+    /// * `request` - The request to give to the function.
+    /// * `on_reply` - The function to call with the response.
+    ///
+    /// # Return value
+    /// Returns an error if the request couldn't be serialized, in which case the worker isn't called.
+    pub fn call<T, F>(
+        &self,
+        func_name: T,
+        request: &Req,
+        on_reply: F,
+    ) -> Result<(), WorkerMessageError>
+    where
+        T: AsRef<str>,
+        F: 'static + FnOnce(Result<Resp, WorkerMessageError>),
+    {
+        let id = NEXT_TYPED_CALL_ID.with(|next_id| next_id.replace(next_id.get().wrapping_add(1)));
+        let data = postcard::to_extend(request, id.to_le_bytes().to_vec())
+            .map_err(|_| WorkerMessageError::Serialize)?;
+
+        self.worker.call(func_name, &data, move |reply| {
+            on_reply(match reply.split_first_chunk::<4>() {
+                Some((reply_id, response)) if u32::from_le_bytes(*reply_id) == id => {
+                    postcard::from_bytes(response).map_err(|_| WorkerMessageError::Corrupt)
+                }
+                _ => Err(WorkerMessageError::Corrupt),
+            });
+        });
+        Ok(())
+    }
+
+    /// Returns the number of calls to this worker that haven't been answered yet, like [`Worker::queue_size`].
+    pub fn queue_size(&self) -> c_int {
+        self.worker.queue_size()
+    }
+}
+
+// The body of the functions declared with `worker_handler`, that decodes the request, calls the handler and responds with its result.
+// Whatever happens, it responds exactly once, so that the caller's reply function always gets called:
+// with just the request id if there is no valid request or the response can't be serialized, which the caller sees as corrupt.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub unsafe fn handle_typed_call<Req, Resp, F>(data: *mut c_char, size: c_int, handler: F)
+where
+    Req: serde::de::DeserializeOwned,
+    Resp: serde::Serialize,
+    F: FnOnce(Req) -> Resp,
+{
+    let data = if data.is_null() || size <= 0 {
+        &[]
+    } else {
+        unsafe { std::slice::from_raw_parts(data as *const u8, size as usize) }
+    };
+    let Some((id, request)) = data.split_first_chunk::<4>() else {
+        respond(&[]);
+        return;
+    };
+
+    let response = postcard::from_bytes(request)
+        .ok()
+        .and_then(|request| postcard::to_extend(&handler(request), id.to_vec()).ok());
+    respond(response.as_deref().unwrap_or(id));
+}
+
+/// Declares a worker function callable with [`TypedWorker::call`], that calls the given rust function
+/// with the deserialized request and responds with its serialized return value.
+///
+/// It requires the `serde` feature, and the function must be exported by the worker program,
+/// e.g. with `-sEXPORTED_FUNCTIONS=_main,_find_path`.
+///
+/// # Arguments
+/// * The name of the exported worker function.
+/// * The rust function handling the requests, taking the request type of the [`TypedWorker`] and returning its response type.
+///
+/// # Examples
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct PathRequest {
+///     from: (u32, u32),
+///     to: (u32, u32),
+/// }
+///
+/// fn compute_path(request: PathRequest) -> Option<Vec<(u32, u32)>> {
+///     // ...
+/// }
+///
+/// // The exported function can't have the same name as the handler, as they're in the same module.
+/// emscripten_functions::worker_handler!(find_path, compute_path);
+/// ```
+#[cfg(feature = "serde")]
+#[macro_export]
+macro_rules! worker_handler {
+    ($name:ident, $handler:expr) => {
+        #[no_mangle]
+        extern "C" fn $name(data: *mut ::std::os::raw::c_char, size: ::std::os::raw::c_int) {
+            let handler = $handler;
+            unsafe { $crate::emscripten::worker::handle_typed_call(data, size, handler) }
+        }
+    };
+}
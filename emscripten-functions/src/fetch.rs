@@ -0,0 +1,560 @@
+//! Select functions (with rust-native parameter and return value types) from the emscripten [`fetch.h`] header file, and helper functions and types for them.
+//!
+//! Using them requires linking with `-sFETCH`.
+//!
+//! [`fetch.h`]: https://emscripten.org/docs/api_reference/fetch.html
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    ffi::{CStr, CString},
+    fmt::Display,
+    os::raw::{c_char, c_void},
+    ptr::{null, null_mut},
+    rc::Rc,
+};
+
+use emscripten_functions_sys::fetch;
+
+use crate::emscripten::{to_cstring_lossy, EmscriptenError};
+
+/// The response to a request made with [`Fetch`].
+///
+/// Implements [`Display`] as `{status} {status_text}`.
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchResponse {
+    /// The HTTP status code, or 0 if the request didn't reach the server.
+    pub status: u16,
+    /// The HTTP status text, like `OK` or `Not Found`.
+    pub status_text: String,
+    /// The body of the response. It's empty unless the request was made with [`Fetch::load_to_memory`] on.
+    pub data: Vec<u8>,
+    /// The response headers as `(name, value)` pairs, in the order the server sent them.
+    pub headers: Vec<(String, String)>,
+}
+impl FetchResponse {
+    /// Returns the value of the first response header with the given name, which is compared case-insensitively.
+    ///
+    /// # Examples
+    /// ```rust
+    /// Fetch::get("data.json")
+    ///     .on_success(|response| {
+    ///         println!("Got a {:?} file", response.header("Content-Type"));
+    ///     })
+    ///     .send();
+    /// ```
+    pub fn header<T>(&self, name: T) -> Option<&str>
+    where
+        T: AsRef<str>,
+    {
+        let name = name.as_ref();
+
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    // Copies everything out of an emscripten fetch, which must still be open.
+    unsafe fn from_fetch(fetch_ptr: *mut fetch::emscripten_fetch_t) -> Self {
+        let fetch_ref = unsafe { &*fetch_ptr };
+
+        let status_text = unsafe { CStr::from_ptr(fetch_ref.statusText.as_ptr()) }
+            .to_string_lossy()
+            .into_owned();
+
+        let data = if fetch_ref.data.is_null() || fetch_ref.numBytes == 0 {
+            Vec::new()
+        } else {
+            unsafe {
+                std::slice::from_raw_parts(fetch_ref.data as *const u8, fetch_ref.numBytes as usize)
+            }
+            .to_vec()
+        };
+
+        // The length doesn't count the NUL terminator, which gets written too.
+        let headers_length =
+            unsafe { fetch::emscripten_fetch_get_response_headers_length(fetch_ptr) };
+        let mut headers_buffer = vec![0u8; headers_length + 1];
+        unsafe {
+            fetch::emscripten_fetch_get_response_headers(
+                fetch_ptr,
+                headers_buffer.as_mut_ptr() as *mut c_char,
+                headers_buffer.len(),
+            );
+        }
+        let headers = parse_headers(&String::from_utf8_lossy(&headers_buffer[..headers_length]));
+
+        FetchResponse {
+            status: fetch_ref.status,
+            status_text,
+            data,
+            headers,
+        }
+    }
+}
+impl Display for FetchResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.status, self.status_text)
+    }
+}
+
+// Splits the raw `name: value` lines given by `emscripten_fetch_get_response_headers` into pairs.
+fn parse_headers(headers: &str) -> Vec<(String, String)> {
+    headers
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            Some((name.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// The error returned by [`get_sync`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchError {
+    /// The request couldn't be started, e.g. because its URL contains a NUL character,
+    /// or because it was made on the main browser thread.
+    NotStarted,
+    /// The request failed, with the given response.
+    /// Its status is 0 if the request didn't reach the server, otherwise it's an HTTP error status.
+    Failed(FetchResponse),
+}
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::NotStarted => write!(f, "The request couldn't be started"),
+            FetchError::Failed(response) => write!(f, "The request failed: {}", response),
+        }
+    }
+}
+
+type FetchCallback = Box<dyn FnOnce(FetchResponse)>;
+type FetchProgressCallback = Box<dyn FnMut(u64, u64)>;
+
+// The callbacks of a request, which stay together until the request finishes or gets aborted.
+struct FetchCallbacks {
+    on_success: Option<FetchCallback>,
+    on_error: Option<FetchCallback>,
+    on_progress: Option<FetchProgressCallback>,
+}
+
+/// An HTTP request made with the emscripten fetch API, using the emscripten-defined [`emscripten_fetch`].
+/// It's set up with builder methods, then started with [`send`](Fetch::send).
+///
+/// By default, the response is loaded to memory, and no callbacks are called.
+///
+/// [`emscripten_fetch`]: https://emscripten.org/docs/api_reference/fetch.html
+///
+/// # Examples
+/// ```rust
+/// Fetch::get("levels/1.json")
+///     .header("Accept", "application/json")
+///     .on_success(|response| {
+///         println!("Level 1: {}", String::from_utf8_lossy(&response.data));
+///     })
+///     .on_error(|response| {
+///         println!("Couldn't get level 1: {}", response);
+///     })
+///     .send();
+/// ```
+pub struct Fetch {
+    method: &'static CStr,
+    // `None` if the URL contains a NUL character, in which case the request can't be started.
+    url: Option<CString>,
+    body: Option<Vec<u8>>,
+    headers: Vec<CString>,
+    attributes: u32,
+    callbacks: FetchCallbacks,
+}
+impl Fetch {
+    fn new<T>(method: &'static CStr, url: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Fetch {
+            method,
+            url: CString::new(url.as_ref()).ok(),
+            body: None,
+            headers: Vec::new(),
+            attributes: fetch::EMSCRIPTEN_FETCH_LOAD_TO_MEMORY,
+            callbacks: FetchCallbacks {
+                on_success: None,
+                on_error: None,
+                on_progress: None,
+            },
+        }
+    }
+
+    /// Creates a `GET` request to the given URL.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to request.
+    pub fn get<T>(url: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        Self::new(c"GET", url)
+    }
+
+    /// Creates a `POST` request to the given URL, with the given body.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to request.
+    /// * `body` - The data to send.
+    ///
+    /// # Examples
+    /// ```rust
+    /// Fetch::post("scores", r#"{"score": 42}"#)
+    ///     .header("Content-Type", "application/json")
+    ///     .send();
+    /// ```
+    pub fn post<T, U>(url: T, body: U) -> Self
+    where
+        T: AsRef<str>,
+        U: AsRef<[u8]>,
+    {
+        let mut request = Self::new(c"POST", url);
+        request.body = Some(body.as_ref().to_vec());
+        request
+    }
+
+    /// Adds a header to the request.
+    ///
+    /// # Arguments
+    /// * `name` - The header's name.
+    /// * `value` - The header's value.
+    pub fn header<T, U>(mut self, name: T, value: U) -> Self
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        self.headers.push(to_cstring_lossy(name.as_ref()));
+        self.headers.push(to_cstring_lossy(value.as_ref()));
+        self
+    }
+
+    fn set_attribute(mut self, attribute: u32, on: bool) -> Self {
+        if on {
+            self.attributes |= attribute;
+        } else {
+            self.attributes &= !attribute;
+        }
+        self
+    }
+
+    /// Sets whether the response body gets loaded to memory, to be found in [`FetchResponse::data`].
+    /// It's on by default.
+    pub fn load_to_memory(self, on: bool) -> Self {
+        self.set_attribute(fetch::EMSCRIPTEN_FETCH_LOAD_TO_MEMORY, on)
+    }
+
+    /// Sets whether the response body gets stored in the browser's IndexedDB, under the request's URL.
+    /// It's off by default.
+    pub fn persist_file(self, on: bool) -> Self {
+        self.set_attribute(fetch::EMSCRIPTEN_FETCH_PERSIST_FILE, on)
+    }
+
+    /// Sets the function to call with the response once the request succeeds.
+    pub fn on_success<F>(mut self, on_success: F) -> Self
+    where
+        F: 'static + FnOnce(FetchResponse),
+    {
+        self.callbacks.on_success = Some(Box::new(on_success));
+        self
+    }
+
+    /// Sets the function to call with the response if the request fails.
+    /// The response's status is 0 if the request didn't reach the server, otherwise it's an HTTP error status.
+    pub fn on_error<F>(mut self, on_error: F) -> Self
+    where
+        F: 'static + FnOnce(FetchResponse),
+    {
+        self.callbacks.on_error = Some(Box::new(on_error));
+        self
+    }
+
+    /// Sets the function to call each time the request progresses, with the number of bytes downloaded so far
+    /// and the total number of bytes to download, which is 0 if the server didn't tell it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// Fetch::get("assets.zip")
+    ///     .on_progress(|loaded, total| {
+    ///         if total > 0 {
+    ///             println!("{}% done", loaded * 100 / total);
+    ///         }
+    ///     })
+    ///     .send();
+    /// ```
+    pub fn on_progress<F>(mut self, on_progress: F) -> Self
+    where
+        F: 'static + FnMut(u64, u64),
+    {
+        self.callbacks.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Starts the request, returning a [`FetchHandle`] that can abort it.
+    /// When it finishes, either the `on_success` or the `on_error` function gets called, and all the callbacks get dropped.
+    ///
+    /// If the request can't be started, e.g. because its URL contains a NUL character,
+    /// the callbacks get dropped without being called.
+    pub fn send(self) -> FetchHandle {
+        // Removing the NUL characters would request another URL, so the request isn't started instead.
+        // No request gets the id 0, so aborting the handle fails like aborting a finished request.
+        let Some(url) = &self.url else {
+            return FetchHandle { id: 0 };
+        };
+
+        let mut attr = unsafe {
+            let mut attr = std::mem::zeroed::<fetch::emscripten_fetch_attr_t>();
+            fetch::emscripten_fetch_attr_init(&mut attr);
+            attr
+        };
+
+        let method = self.method.to_bytes_with_nul();
+        for (dest, src) in attr.requestMethod.iter_mut().zip(method) {
+            *dest = *src as c_char;
+        }
+        attr.attributes = self.attributes;
+
+        // Emscripten copies the headers, so they only need to live during the `emscripten_fetch` call.
+        // The body isn't copied: it's read once the request actually starts, which can be after this function returns,
+        // so it's kept along with the callbacks until the request finishes or gets aborted.
+        let mut header_ptrs: Vec<*const c_char> =
+            self.headers.iter().map(|header| header.as_ptr()).collect();
+        header_ptrs.push(null());
+        attr.requestHeaders = header_ptrs.as_ptr();
+        if let Some(body) = &self.body {
+            attr.requestData = body.as_ptr() as *const c_char;
+            attr.requestDataSize = body.len();
+        }
+
+        unsafe extern "C" fn onsuccess_wrapper(fetch_ptr: *mut fetch::emscripten_fetch_t) {
+            if let Some((callbacks, response)) = unsafe { finish_fetch(fetch_ptr) } {
+                if let Some(on_success) = callbacks.on_success {
+                    on_success(response);
+                }
+            }
+        }
+
+        unsafe extern "C" fn onerror_wrapper(fetch_ptr: *mut fetch::emscripten_fetch_t) {
+            if let Some((callbacks, response)) = unsafe { finish_fetch(fetch_ptr) } {
+                if let Some(on_error) = callbacks.on_error {
+                    on_error(response);
+                }
+            }
+        }
+
+        unsafe extern "C" fn onprogress_wrapper(fetch_ptr: *mut fetch::emscripten_fetch_t) {
+            let fetch_ref = unsafe { &*fetch_ptr };
+            let id = fetch_ref.userData as usize;
+
+            // We take the function out of the registry while it runs, so that it can e.g. abort its own request.
+            let on_progress = PENDING_FETCHES.with(|fetches_ref| {
+                fetches_ref
+                    .borrow_mut()
+                    .get_mut(&id)
+                    .and_then(|pending| pending.callbacks.on_progress.take())
+            });
+            let Some(mut on_progress) = on_progress else {
+                return;
+            };
+
+            on_progress(
+                fetch_ref.dataOffset + fetch_ref.numBytes,
+                fetch_ref.totalBytes,
+            );
+
+            PENDING_FETCHES.with(|fetches_ref| {
+                if let Some(pending) = fetches_ref.borrow_mut().get_mut(&id) {
+                    pending.callbacks.on_progress = Some(on_progress);
+                }
+            });
+        }
+
+        let has_progress = self.callbacks.on_progress.is_some();
+        let id = NEXT_FETCH_ID.with(|next_id| {
+            let id = next_id.get();
+            next_id.set(id.wrapping_add(1));
+            id
+        });
+        PENDING_FETCHES.with(|fetches_ref| {
+            fetches_ref.borrow_mut().insert(
+                id,
+                PendingFetch {
+                    fetch: null_mut(),
+                    callbacks: self.callbacks,
+                    // Moving the `Vec` doesn't move its data, which `attr` points to.
+                    body: self.body,
+                },
+            )
+        });
+
+        attr.userData = id as *mut c_void;
+        attr.onsuccess = Some(onsuccess_wrapper);
+        attr.onerror = Some(onerror_wrapper);
+        if has_progress {
+            attr.onprogress = Some(onprogress_wrapper);
+        }
+
+        let fetch_ptr = unsafe { fetch::emscripten_fetch(&mut attr, url.as_ptr()) };
+
+        // Synchronous requests have already finished and left the registry by now.
+        let unstarted = PENDING_FETCHES.with(|fetches_ref| {
+            let mut fetches = fetches_ref.borrow_mut();
+            if fetch_ptr.is_null() {
+                // The request never started, so no callback will take the callbacks out.
+                return fetches.remove(&id);
+            }
+            if let Some(pending) = fetches.get_mut(&id) {
+                pending.fetch = fetch_ptr;
+            }
+            None
+        });
+        drop(unstarted);
+
+        FetchHandle { id }
+    }
+}
+
+// The requests started with `Fetch::send` that haven't finished yet, keyed by the id that we pass as their `userData`.
+// Whoever takes a request out of here, be it its callback or `FetchHandle::abort`, is the one to close it,
+// so its callbacks are dropped exactly once, and a closed fetch is never touched again.
+// The request's body is dropped along with them, as emscripten reads it until then.
+struct PendingFetch {
+    fetch: *mut fetch::emscripten_fetch_t,
+    callbacks: FetchCallbacks,
+    body: Option<Vec<u8>>,
+}
+thread_local! {
+    static PENDING_FETCHES: RefCell<HashMap<usize, PendingFetch>> = RefCell::new(HashMap::new());
+    static NEXT_FETCH_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+// Takes back the callbacks of a finished fetch and copies its response, then closes it and frees its body.
+// It returns `None` if the fetch is being aborted, in which case it's closed by `FetchHandle::abort`.
+unsafe fn finish_fetch(
+    fetch_ptr: *mut fetch::emscripten_fetch_t,
+) -> Option<(FetchCallbacks, FetchResponse)> {
+    let id = unsafe { (*fetch_ptr).userData } as usize;
+    let pending = PENDING_FETCHES.with(|fetches_ref| fetches_ref.borrow_mut().remove(&id))?;
+
+    let response = unsafe { FetchResponse::from_fetch(fetch_ptr) };
+    unsafe {
+        fetch::emscripten_fetch_close(fetch_ptr);
+    }
+    drop(pending.body);
+
+    Some((pending.callbacks, response))
+}
+
+/// A handle to a request started with [`Fetch::send`], that can be used to abort it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchHandle {
+    id: usize,
+}
+impl FetchHandle {
+    /// Aborts the request, using the emscripten-defined [`emscripten_fetch_close`].
+    /// None of its callbacks will be called afterwards, and they get dropped.
+    ///
+    /// It returns [`EmscriptenError::InvalidParam`] if the request has already finished, like closing an already closed fetch does.
+    /// The request isn't aborted when the handle is dropped.
+    ///
+    /// [`emscripten_fetch_close`]: https://emscripten.org/docs/api_reference/fetch.html
+    ///
+    /// # Examples
+    /// ```rust
+    /// let download = Fetch::get("assets.zip")
+    ///     .on_success(|response| println!("Got {} bytes", response.data.len()))
+    ///     .send();
+    ///
+    /// // The user left the loading screen.
+    /// download.abort().ok();
+    /// ```
+    pub fn abort(&self) -> Result<(), EmscriptenError> {
+        let pending = PENDING_FETCHES
+            .with(|fetches_ref| fetches_ref.borrow_mut().remove(&self.id))
+            .ok_or(EmscriptenError::InvalidParam)?;
+
+        // Emscripten calls the `onerror` callback of an aborted fetch while closing it,
+        // which does nothing, as the fetch isn't in the registry anymore.
+        // The body can only be freed once the fetch is closed.
+        let result = unsafe { fetch::emscripten_fetch_close(pending.fetch) };
+        drop(pending);
+
+        EmscriptenError::from_result(result)
+    }
+}
+
+/// Makes a `GET` request to the given URL and waits for its response, using the emscripten-defined [`emscripten_fetch`]
+/// with the `EMSCRIPTEN_FETCH_SYNCHRONOUS` attribute.
+///
+/// It only works outside the main browser thread, e.g. in a pthread or a worker.
+/// On the main browser thread emscripten refuses synchronous requests, even with `-sASYNCIFY`,
+/// so it returns [`FetchError::NotStarted`] there.
+///
+/// [`emscripten_fetch`]: https://emscripten.org/docs/api_reference/fetch.html#synchronous-fetches
+///
+/// # Arguments
+/// * `url` - The URL to request.
+///
+/// # Examples
+/// ```rust
+/// match fetch::get_sync("levels/1.json") {
+///     Ok(response) => println!("Level 1: {}", String::from_utf8_lossy(&response.data)),
+///     Err(err) => println!("Couldn't get level 1: {}", err),
+/// }
+/// ```
+pub fn get_sync<T>(url: T) -> Result<FetchResponse, FetchError>
+where
+    T: AsRef<str>,
+{
+    // The callbacks get called before `emscripten_fetch` returns, so they can hand us the result right away.
+    let result = Rc::new(RefCell::new(None));
+    let success_result = result.clone();
+    let error_result = result.clone();
+
+    Fetch::get(url)
+        .set_attribute(fetch::EMSCRIPTEN_FETCH_SYNCHRONOUS, true)
+        .on_success(move |response| {
+            success_result.replace(Some(Ok(response)));
+        })
+        .on_error(move |response| {
+            error_result.replace(Some(Err(FetchError::Failed(response))));
+        })
+        .send();
+
+    result.take().unwrap_or(Err(FetchError::NotStarted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_with_nul_is_not_requested() {
+        let called = Rc::new(Cell::new(false));
+        let called_ref = called.clone();
+
+        let handle = Fetch::get("levels/1.json\0")
+            .on_error(move |_| called_ref.set(true))
+            .send();
+
+        assert_eq!(handle.abort(), Err(EmscriptenError::InvalidParam));
+        assert!(!called.get());
+        // The callbacks were dropped along with the request.
+        assert_eq!(Rc::strong_count(&called), 1);
+    }
+
+    #[test]
+    fn header_nul_characters_are_removed() {
+        let request = Fetch::get("data.json").header("Acc\0ept", "application/\0json");
+
+        assert_eq!(request.headers[0].as_bytes(), b"Accept");
+        assert_eq!(request.headers[1].as_bytes(), b"application/json");
+    }
+}
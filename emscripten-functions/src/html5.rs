@@ -0,0 +1,2415 @@
+//! Select functions (with rust-native parameter and return value types) from the emscripten [`html5.h`] header file, and helper functions and types for them.
+//!
+//! [`html5.h`]: https://emscripten.org/docs/api_reference/html5.h.html
+
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    ffi::CString,
+    fmt::Display,
+    os::raw::{c_char, c_int, c_uint, c_void},
+    ptr::null_mut,
+    rc::Rc,
+};
+
+use emscripten_functions_sys::html5;
+
+use crate::emscripten::{run_script, to_cstring_lossy, EmscriptenError};
+
+/// The CSS selector of the canvas element that emscripten's default HTML shell creates.
+///
+/// Every other target is a CSS selector of your own element, e.g. `"#my-canvas"`,
+/// or, for the event callbacks, one of the special [`Target`]s.
+/// If the selector doesn't match any element, the canvas functions return [`EmscriptenError::UnknownTarget`].
+pub const DEFAULT_CANVAS_TARGET: &str = "#canvas";
+
+/// The size of a canvas element's drawing buffer, as returned by [`get_canvas_element_size`].
+///
+/// Implements [`Display`] as `{width}x{height}`.
+///
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanvasSize {
+    pub width: c_int,
+    pub height: c_int,
+}
+impl Display for CanvasSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+/// Returns the size of the drawing buffer of the canvas matching the given target,
+/// using the emscripten-defined [`emscripten_get_canvas_element_size`].
+///
+/// This is the size in pixels the canvas renders at, which may differ from its CSS size on the page.
+/// It works with `OffscreenCanvas` targets too, if the build has OffscreenCanvas support.
+///
+/// [`emscripten_get_canvas_element_size`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_get_canvas_element_size
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+///
+/// # Examples
+/// ```rust
+/// let size = get_canvas_element_size(DEFAULT_CANVAS_TARGET)?;
+/// println!("The canvas is {}", size);
+///
+/// assert_eq!(
+///     get_canvas_element_size("#there-is-no-such-canvas"),
+///     Err(EmscriptenError::UnknownTarget)
+/// );
+/// ```
+pub fn get_canvas_element_size<T>(target: T) -> Result<CanvasSize, EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+    let mut width = 0;
+    let mut height = 0;
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_get_canvas_element_size(target.as_ptr(), &mut width, &mut height)
+    })?;
+
+    Ok(CanvasSize { width, height })
+}
+
+/// Sets the size of the drawing buffer of the canvas matching the given target,
+/// using the emscripten-defined [`emscripten_set_canvas_element_size`].
+///
+/// This doesn't change the CSS size of the canvas on the page.
+/// It works with `OffscreenCanvas` targets too, if the build has OffscreenCanvas support.
+///
+/// [`emscripten_set_canvas_element_size`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_canvas_element_size
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `width` - The new width of the drawing buffer, in pixels.
+/// * `height` - The new height of the drawing buffer, in pixels.
+///
+/// # Examples
+/// ```rust
+/// set_canvas_element_size(DEFAULT_CANVAS_TARGET, 800, 600)?;
+/// ```
+pub fn set_canvas_element_size<T>(
+    target: T,
+    width: c_int,
+    height: c_int,
+) -> Result<(), EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_set_canvas_element_size(target.as_ptr(), width, height)
+    })
+}
+
+/// The size of an element on the page, in CSS pixels, as returned by [`get_element_css_size`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementCssSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Returns the size of the element matching the given target on the page, in CSS pixels,
+/// using the emscripten-defined [`emscripten_get_element_css_size`].
+///
+/// [`emscripten_get_element_css_size`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_get_element_css_size
+///
+/// # Arguments
+/// * `target` - The CSS selector of the element, e.g. [`DEFAULT_CANVAS_TARGET`].
+///
+/// # Examples
+/// ```rust
+/// let size = get_element_css_size(DEFAULT_CANVAS_TARGET)?;
+/// println!("The canvas takes {}x{} CSS pixels", size.width, size.height);
+/// ```
+pub fn get_element_css_size<T>(target: T) -> Result<ElementCssSize, EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+    let mut width = 0.0;
+    let mut height = 0.0;
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_get_element_css_size(target.as_ptr(), &mut width, &mut height)
+    })?;
+
+    Ok(ElementCssSize { width, height })
+}
+
+/// Sets the size of the element matching the given target on the page, in CSS pixels,
+/// using the emscripten-defined [`emscripten_set_element_css_size`].
+///
+/// For a canvas, this doesn't change the size of its drawing buffer, as set with [`set_canvas_element_size`].
+///
+/// [`emscripten_set_element_css_size`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_element_css_size
+///
+/// # Arguments
+/// * `target` - The CSS selector of the element, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `width` - The new width of the element, in CSS pixels.
+/// * `height` - The new height of the element, in CSS pixels.
+///
+/// # Examples
+/// ```rust
+/// set_element_css_size(DEFAULT_CANVAS_TARGET, 640.0, 480.0)?;
+/// ```
+pub fn set_element_css_size<T>(target: T, width: f64, height: f64) -> Result<(), EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_set_element_css_size(target.as_ptr(), width, height)
+    })
+}
+
+/// Sets the size of the drawing buffer of the canvas matching the given target to its size in physical pixels,
+/// i.e. its CSS size times the [device pixel ratio](crate::emscripten::get_device_pixel_ratio), and returns that size.
+/// This way, the canvas isn't blurry on high-DPI screens.
+///
+/// The drawing buffer is only resized if its size differs, as resizing it clears the canvas.
+/// It's at least 1x1, even for a hidden canvas.
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+///
+/// # Examples
+/// ```rust
+/// let size = fit_canvas_to_display(DEFAULT_CANVAS_TARGET)?;
+/// renderer.set_viewport(size.width, size.height);
+/// ```
+pub fn fit_canvas_to_display<T>(target: T) -> Result<CanvasSize, EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = target.as_ref();
+    let css_size = get_element_css_size(target)?;
+    let ratio = crate::emscripten::get_device_pixel_ratio();
+
+    // The `as` casts saturate, so huge sizes can't wrap around.
+    let size = CanvasSize {
+        width: ((css_size.width * ratio).round() as c_int).max(1),
+        height: ((css_size.height * ratio).round() as c_int).max(1),
+    };
+    if get_canvas_element_size(target)? != size {
+        set_canvas_element_size(target, size.width, size.height)?;
+    }
+
+    Ok(size)
+}
+
+/// Fits the drawing buffer of the canvas matching the given target to the display like [`fit_canvas_to_display`],
+/// now and whenever the window gets resized, calling the given function with the new size each time.
+/// It stops when the returned [`CallbackGuard`] gets dropped.
+///
+/// It sets the resize callback of the window, so it replaces the one of an [`EventQueue`], and vice versa.
+/// Zooming the page changes the device pixel ratio, which the browsers report as a resize of the window.
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `on_resize` - The function to call with the size of the drawing buffer, now and after each resize.
+///
+/// # Examples
+/// ```rust
+/// let _fit = fit_canvas_to_display_on_resize(DEFAULT_CANVAS_TARGET, |size| {
+///     renderer.set_viewport(size.width, size.height);
+/// })?;
+/// ```
+pub fn fit_canvas_to_display_on_resize<T, F>(
+    target: T,
+    mut on_resize: F,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: AsRef<str>,
+    F: 'static + FnMut(CanvasSize),
+{
+    let target = target.as_ref().to_string();
+    on_resize(fit_canvas_to_display(&target)?);
+
+    set_callback(
+        EventTarget::Window,
+        html5::EMSCRIPTEN_EVENT_RESIZE,
+        html5::emscripten_set_resize_callback_on_thread,
+        move |_: &UiEvent| {
+            if let Ok(size) = fit_canvas_to_display(&target) {
+                on_resize(size);
+            }
+            false
+        },
+    )
+}
+
+/// How the element is scaled to fill the screen in fullscreen mode, as part of a [`FullscreenStrategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FullscreenScaleMode {
+    /// The element is left at its size, like with [`request_fullscreen`].
+    #[default]
+    Default,
+    /// The element is stretched to fill the whole screen, ignoring its aspect ratio.
+    Stretch,
+    /// The element is scaled to fill the screen as much as possible while keeping its aspect ratio, with black bars around it.
+    Aspect,
+    /// The element is centered on the screen, without being scaled.
+    Center,
+}
+impl FullscreenScaleMode {
+    fn raw(self) -> c_int {
+        (match self {
+            FullscreenScaleMode::Default => html5::EMSCRIPTEN_FULLSCREEN_SCALE_DEFAULT,
+            FullscreenScaleMode::Stretch => html5::EMSCRIPTEN_FULLSCREEN_SCALE_STRETCH,
+            FullscreenScaleMode::Aspect => html5::EMSCRIPTEN_FULLSCREEN_SCALE_ASPECT,
+            FullscreenScaleMode::Center => html5::EMSCRIPTEN_FULLSCREEN_SCALE_CENTER,
+        }) as c_int
+    }
+}
+
+/// How the drawing buffer of the canvas is resized in fullscreen mode, as part of a [`FullscreenStrategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FullscreenCanvasScale {
+    /// The drawing buffer keeps its size, and only the canvas element gets scaled.
+    #[default]
+    None,
+    /// The drawing buffer is resized to the CSS size of the canvas on the screen.
+    StdDef,
+    /// The drawing buffer is resized to the size of the canvas in device pixels, i.e. its CSS size times the device pixel ratio.
+    HiDef,
+}
+impl FullscreenCanvasScale {
+    fn raw(self) -> c_int {
+        (match self {
+            FullscreenCanvasScale::None => html5::EMSCRIPTEN_FULLSCREEN_CANVAS_SCALE_NONE,
+            FullscreenCanvasScale::StdDef => html5::EMSCRIPTEN_FULLSCREEN_CANVAS_SCALE_STDDEF,
+            FullscreenCanvasScale::HiDef => html5::EMSCRIPTEN_FULLSCREEN_CANVAS_SCALE_HIDEF,
+        }) as c_int
+    }
+}
+
+/// How the canvas is filtered when it's scaled up in fullscreen mode, as part of a [`FullscreenStrategy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FullscreenFiltering {
+    /// The browser's default filtering, usually a blurry bilinear one.
+    #[default]
+    Default,
+    /// The canvas pixels become sharp squares, which suits pixel art.
+    Nearest,
+    /// The canvas pixels are blended together.
+    Bilinear,
+}
+impl FullscreenFiltering {
+    fn raw(self) -> c_int {
+        (match self {
+            FullscreenFiltering::Default => html5::EMSCRIPTEN_FULLSCREEN_FILTERING_DEFAULT,
+            FullscreenFiltering::Nearest => html5::EMSCRIPTEN_FULLSCREEN_FILTERING_NEAREST,
+            FullscreenFiltering::Bilinear => html5::EMSCRIPTEN_FULLSCREEN_FILTERING_BILINEAR,
+        }) as c_int
+    }
+}
+
+/// How a canvas is shown in fullscreen mode, as given to [`request_fullscreen_strategy`] and [`enter_soft_fullscreen`].
+///
+/// # Examples
+/// ```rust
+/// let strategy = FullscreenStrategy {
+///     scale_mode: FullscreenScaleMode::Aspect,
+///     filtering: FullscreenFiltering::Nearest,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Default)]
+pub struct FullscreenStrategy {
+    /// How the canvas element is scaled to fill the screen.
+    pub scale_mode: FullscreenScaleMode,
+    /// How the drawing buffer of the canvas is resized.
+    pub canvas_resolution_scale_mode: FullscreenCanvasScale,
+    /// How the canvas is filtered when it's scaled up.
+    pub filtering: FullscreenFiltering,
+    /// The function to call with the new size of the drawing buffer of the canvas, whenever it gets resized
+    /// on entering or leaving fullscreen mode, or on the resize of the window while in it.
+    pub canvas_resized_callback: Option<Box<dyn FnMut(CanvasSize)>>,
+}
+
+// The canvas resized callback of the latest fullscreen strategy, along with the target whose size it's given.
+type CanvasResizedCallback = (CString, Box<dyn FnMut(CanvasSize)>);
+
+// Emscripten keeps calling the callback while the canvas is in fullscreen mode and on leaving it, so it's kept here until it gets replaced.
+// It's `None` while it runs, so that it can be replaced from inside itself.
+thread_local! {
+    static CANVAS_RESIZED_CALLBACK: RefCell<Option<CanvasResizedCallback>> = const { RefCell::new(None) };
+}
+
+unsafe extern "C" fn canvas_resized_wrapper(
+    _event_type: c_int,
+    _reserved: *const c_void,
+    _user_data: *mut c_void,
+) -> bool {
+    let callback = CANVAS_RESIZED_CALLBACK.with(|callback_ref| callback_ref.borrow_mut().take());
+    let Some((target, mut callback)) = callback else {
+        return false;
+    };
+
+    let mut size = CanvasSize {
+        width: 0,
+        height: 0,
+    };
+    let result = unsafe {
+        html5::emscripten_get_canvas_element_size(
+            target.as_ptr(),
+            &mut size.width,
+            &mut size.height,
+        )
+    };
+    if EmscriptenError::from_result(result).is_ok() {
+        callback(size);
+    }
+
+    // The function goes back in its place, unless it got replaced while it ran.
+    CANVAS_RESIZED_CALLBACK.with(|callback_ref| {
+        let mut stored = callback_ref.borrow_mut();
+        if stored.is_none() {
+            *stored = Some((target, callback));
+        }
+    });
+
+    false
+}
+
+// Turns the given strategy into emscripten's, keeping its canvas resized callback for the given target.
+fn raw_fullscreen_strategy(
+    target: &CString,
+    strategy: FullscreenStrategy,
+) -> html5::EmscriptenFullscreenStrategy {
+    let has_callback = strategy.canvas_resized_callback.is_some();
+    let old_callback = CANVAS_RESIZED_CALLBACK.with(|callback_ref| {
+        callback_ref.replace(
+            strategy
+                .canvas_resized_callback
+                .map(|callback| (target.clone(), callback)),
+        )
+    });
+    drop(old_callback);
+
+    html5::EmscriptenFullscreenStrategy {
+        scaleMode: strategy.scale_mode.raw(),
+        canvasResolutionScaleMode: strategy.canvas_resolution_scale_mode.raw(),
+        filteringMode: strategy.filtering.raw(),
+        canvasResizedCallback: if has_callback {
+            Some(canvas_resized_wrapper)
+        } else {
+            None
+        },
+        canvasResizedCallbackUserData: null_mut(),
+        canvasResizedCallbackTargetThread: CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+    }
+}
+
+/// Makes the element matching the given target fill the screen, using the emscripten-defined [`emscripten_request_fullscreen`].
+///
+/// Browsers only allow this from the handler of a user input event, like a click or a key press.
+/// With `defer_until_in_event_handler` set to `true`, a request made elsewhere is deferred until the next such event handled by emscripten.
+///
+/// The element is left at its size. Use [`request_fullscreen_strategy`] to scale it to fill the screen.
+///
+/// [`emscripten_request_fullscreen`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_request_fullscreen
+///
+/// # Arguments
+/// * `target` - The CSS selector of the element, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `defer_until_in_event_handler` - Whether to defer the request until the next user input event, if it can't be done now.
+///
+/// # Examples
+/// ```rust
+/// let _keydown = set_keydown_callback(Target::Window, |event| {
+///     if event.key_code() == KeyCode::F {
+///         request_fullscreen(DEFAULT_CANVAS_TARGET, false).ok();
+///     }
+///     false
+/// })?;
+/// ```
+pub fn request_fullscreen<T>(
+    target: T,
+    defer_until_in_event_handler: bool,
+) -> Result<(), EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_request_fullscreen(target.as_ptr(), defer_until_in_event_handler)
+    })
+}
+
+/// Makes the canvas matching the given target fill the screen the way the given strategy says,
+/// using the emscripten-defined [`emscripten_request_fullscreen_strategy`].
+///
+/// Like with [`request_fullscreen`], browsers only allow this from the handler of a user input event.
+/// The strategy's canvas resized callback replaces the one of the previous fullscreen request, and it's kept after leaving fullscreen mode,
+/// as emscripten calls it once more when restoring the canvas.
+///
+/// [`emscripten_request_fullscreen_strategy`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_request_fullscreen_strategy
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `defer_until_in_event_handler` - Whether to defer the request until the next user input event, if it can't be done now.
+/// * `strategy` - How the canvas is shown in fullscreen mode.
+///
+/// # Examples
+/// ```rust
+/// // Sharp pixel art, as big as the screen allows without distorting it.
+/// request_fullscreen_strategy(
+///     DEFAULT_CANVAS_TARGET,
+///     true,
+///     FullscreenStrategy {
+///         scale_mode: FullscreenScaleMode::Aspect,
+///         canvas_resolution_scale_mode: FullscreenCanvasScale::None,
+///         filtering: FullscreenFiltering::Nearest,
+///         canvas_resized_callback: Some(Box::new(|size| {
+///             println!("The canvas is now {}", size);
+///         })),
+///     },
+/// )?;
+/// ```
+pub fn request_fullscreen_strategy<T>(
+    target: T,
+    defer_until_in_event_handler: bool,
+    strategy: FullscreenStrategy,
+) -> Result<(), EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+    // Emscripten copies the strategy right away, so it only has to outlive the call.
+    let strategy = raw_fullscreen_strategy(&target, strategy);
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_request_fullscreen_strategy(
+            target.as_ptr(),
+            defer_until_in_event_handler,
+            &strategy,
+        )
+    })
+}
+
+/// Leaves fullscreen mode, using the emscripten-defined [`emscripten_exit_fullscreen`].
+///
+/// [`emscripten_exit_fullscreen`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_exit_fullscreen
+///
+/// # Examples
+/// ```rust
+/// exit_fullscreen()?;
+/// ```
+pub fn exit_fullscreen() -> Result<(), EmscriptenError> {
+    EmscriptenError::from_result(unsafe { html5::emscripten_exit_fullscreen() })
+}
+
+/// Makes the canvas matching the given target fill the browser window, without using the browser's fullscreen mode,
+/// using the emscripten-defined [`emscripten_enter_soft_fullscreen`].
+///
+/// The browser's toolbars stay visible, and unlike with [`request_fullscreen_strategy`], this works outside user input event handlers too,
+/// and where the browser's fullscreen mode is blocked, e.g. in some iframes.
+/// The strategy's canvas resized callback replaces the one of the previous fullscreen request, soft or not.
+///
+/// [`emscripten_enter_soft_fullscreen`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_enter_soft_fullscreen
+///
+/// # Arguments
+/// * `target` - The CSS selector of the canvas, e.g. [`DEFAULT_CANVAS_TARGET`].
+/// * `strategy` - How the canvas is shown while it fills the window.
+///
+/// # Examples
+/// ```rust
+/// // An in-page "maximize" button.
+/// let _maximize = set_mousedown_callback("#maximize", |_| {
+///     enter_soft_fullscreen(
+///         DEFAULT_CANVAS_TARGET,
+///         FullscreenStrategy {
+///             scale_mode: FullscreenScaleMode::Aspect,
+///             filtering: FullscreenFiltering::Nearest,
+///             ..Default::default()
+///         },
+///     )
+///     .is_ok()
+/// })?;
+/// ```
+pub fn enter_soft_fullscreen<T>(
+    target: T,
+    strategy: FullscreenStrategy,
+) -> Result<(), EmscriptenError>
+where
+    T: AsRef<str>,
+{
+    let target = to_cstring_lossy(target.as_ref());
+    let strategy = raw_fullscreen_strategy(&target, strategy);
+
+    EmscriptenError::from_result(unsafe {
+        html5::emscripten_enter_soft_fullscreen(target.as_ptr(), &strategy)
+    })
+}
+
+/// Restores the canvas that was made to fill the browser window by [`enter_soft_fullscreen`],
+/// using the emscripten-defined [`emscripten_exit_soft_fullscreen`].
+///
+/// [`emscripten_exit_soft_fullscreen`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_exit_soft_fullscreen
+///
+/// # Examples
+/// ```rust
+/// exit_soft_fullscreen()?;
+/// ```
+pub fn exit_soft_fullscreen() -> Result<(), EmscriptenError> {
+    EmscriptenError::from_result(unsafe { html5::emscripten_exit_soft_fullscreen() })
+}
+
+// The special targets that emscripten represents with small pointer values instead of strings.
+const EMSCRIPTEN_EVENT_TARGET_DOCUMENT: usize = 1;
+const EMSCRIPTEN_EVENT_TARGET_WINDOW: usize = 2;
+const EMSCRIPTEN_EVENT_TARGET_SCREEN: usize = 3;
+
+/// Where an event callback is registered, as given to the `set_*_callback` functions.
+///
+/// A string converts into a [`Target::Selector`], so e.g. [`DEFAULT_CANVAS_TARGET`] or `"#my-canvas"` can be given as is.
+///
+/// # Examples
+/// ```rust
+/// let _keydown = set_keydown_callback(Target::Window, |event| {
+///     println!("{} pressed", event.key);
+///     false
+/// })?;
+/// assert_eq!(Target::from(DEFAULT_CANVAS_TARGET), Target::default_canvas());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// The browser window, e.g. for the keyboard events wherever the focus is.
+    Window,
+    /// The whole document.
+    Document,
+    /// The screen, for the events of the `screen` JS object, like the orientation changes.
+    Screen,
+    /// The element matching the given CSS selector.
+    Selector(String),
+}
+impl Target {
+    /// Returns the canvas element of emscripten's default HTML shell, matched by [`DEFAULT_CANVAS_TARGET`].
+    pub fn default_canvas() -> Self {
+        Target::Selector(DEFAULT_CANVAS_TARGET.to_string())
+    }
+}
+impl From<&str> for Target {
+    fn from(selector: &str) -> Self {
+        Target::Selector(selector.to_string())
+    }
+}
+impl From<String> for Target {
+    fn from(selector: String) -> Self {
+        Target::Selector(selector)
+    }
+}
+impl From<&String> for Target {
+    fn from(selector: &String) -> Self {
+        Target::Selector(selector.clone())
+    }
+}
+
+// Makes emscripten run the callbacks on the thread that registers them.
+const CALLBACK_THREAD_CONTEXT_CALLING_THREAD: html5::pthread_t = 2 as html5::pthread_t;
+
+// Where an event callback gets registered: either an element found with a CSS selector,
+// or one of the special targets that emscripten represents with small pointer values instead of strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum EventTarget {
+    Selector(CString),
+    Document,
+    Window,
+    Screen,
+}
+impl EventTarget {
+    fn as_ptr(&self) -> *const c_char {
+        match self {
+            EventTarget::Selector(selector) => selector.as_ptr(),
+            EventTarget::Document => EMSCRIPTEN_EVENT_TARGET_DOCUMENT as *const c_char,
+            EventTarget::Window => EMSCRIPTEN_EVENT_TARGET_WINDOW as *const c_char,
+            EventTarget::Screen => EMSCRIPTEN_EVENT_TARGET_SCREEN as *const c_char,
+        }
+    }
+}
+impl From<Target> for EventTarget {
+    fn from(target: Target) -> Self {
+        match target {
+            Target::Window => EventTarget::Window,
+            Target::Document => EventTarget::Document,
+            Target::Screen => EventTarget::Screen,
+            Target::Selector(selector) => EventTarget::Selector(to_cstring_lossy(&selector)),
+        }
+    }
+}
+
+// A closure given to one of the `set_*_callback` functions, along with where it's registered.
+struct RegisteredCallback {
+    target: EventTarget,
+    event_type: c_int,
+    // It's `None` while the closure runs, so that the callback can be replaced or removed from inside itself.
+    callback: Option<Box<dyn Any>>,
+}
+
+// The closures of the event callbacks sit in this thread-local registry, keyed by an id that emscripten gives back to our
+// `extern "C"` trampolines as user data. Keeping them here lets us drop a closure once its callback gets replaced.
+thread_local! {
+    static CALLBACKS: RefCell<HashMap<usize, RegisteredCallback>> = RefCell::new(HashMap::new());
+    static NEXT_CALLBACK_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+// Stores the given closure in the registry, dropping the one previously registered for the same target and event type.
+// The returned id is the user data to give to emscripten.
+fn store_callback<E>(
+    target: &EventTarget,
+    event_type: c_int,
+    callback: Box<dyn FnMut(&E) -> bool>,
+) -> usize
+where
+    E: 'static,
+{
+    let id = NEXT_CALLBACK_ID.with(|next_id| next_id.replace(next_id.get() + 1));
+
+    remove_target_callbacks(target, event_type);
+    CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref.borrow_mut().insert(
+            id,
+            RegisteredCallback {
+                target: target.clone(),
+                event_type,
+                callback: Some(Box::new(callback)),
+            },
+        );
+    });
+
+    id
+}
+
+// Runs the closure with the given id, returning whether the event got consumed.
+fn run_callback<E>(id: usize, event: &E) -> bool
+where
+    E: 'static,
+{
+    let callback = CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref
+            .borrow_mut()
+            .get_mut(&id)
+            .and_then(|registered| registered.callback.take())
+    });
+    let Some(mut callback) = callback else {
+        return false;
+    };
+
+    let consumed = match callback.downcast_mut::<Box<dyn FnMut(&E) -> bool>>() {
+        Some(function) => function(event),
+        None => false,
+    };
+
+    // The closure goes back in its place, unless it got replaced or removed while it ran, in which case it's dropped here.
+    CALLBACKS.with(|callbacks_ref| {
+        if let Some(registered) = callbacks_ref.borrow_mut().get_mut(&id) {
+            registered.callback = Some(callback);
+        }
+    });
+
+    consumed
+}
+
+// Removes the closure with the given id from the registry.
+fn remove_callback_id(id: usize) {
+    let registered = CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().remove(&id));
+    drop(registered);
+}
+
+/// The handle of an event callback, returned by the `set_*_callback` functions, which removes the callback when it's dropped.
+///
+/// Keep it for as long as the callback is needed, e.g. in the struct of the screen handling the events.
+/// To keep the callback for the rest of the program, use [`std::mem::forget`] on it.
+///
+/// If the callback got replaced by another one set for the same target, or removed with a `remove_*_callback` function,
+/// dropping the handle does nothing.
+///
+/// # Examples
+/// ```rust
+/// struct MenuScreen {
+///     _click: CallbackGuard,
+/// }
+///
+/// let menu = MenuScreen {
+///     _click: set_mousedown_callback(DEFAULT_CANVAS_TARGET, |_| {
+///         println!("Menu clicked");
+///         true
+///     })?,
+/// };
+/// // The menu stops getting clicks once it's dropped.
+/// drop(menu);
+/// ```
+#[must_use = "the callback is removed as soon as the guard is dropped"]
+pub struct CallbackGuard {
+    id: usize,
+    // Tells emscripten to stop calling our trampoline for the callback's target and event type.
+    unregister: Option<Box<dyn FnOnce()>>,
+}
+impl CallbackGuard {
+    fn new<F>(id: usize, unregister: F) -> Self
+    where
+        F: 'static + FnOnce(),
+    {
+        CallbackGuard {
+            id,
+            unregister: Some(Box::new(unregister)),
+        }
+    }
+}
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        // A callback replaced or removed since is no longer in the registry, and its target may have a newer callback to keep.
+        let registered =
+            CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow().contains_key(&self.id));
+        if !registered {
+            return;
+        }
+
+        if let Some(unregister) = self.unregister.take() {
+            unregister();
+        }
+        remove_callback_id(self.id);
+    }
+}
+
+// Removes the closures registered for the given target and event type from the registry.
+fn remove_target_callbacks(target: &EventTarget, event_type: c_int) {
+    let old_callbacks = CALLBACKS.with(|callbacks_ref| {
+        let mut callbacks = callbacks_ref.borrow_mut();
+        let old_ids: Vec<usize> = callbacks
+            .iter()
+            .filter(|(_, registered)| {
+                registered.target == *target && registered.event_type == event_type
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        old_ids
+            .iter()
+            .filter_map(|id| callbacks.remove(id))
+            .collect::<Vec<RegisteredCallback>>()
+    });
+    // The old closures get dropped outside of the borrow, in case their destructors touch the registry.
+    drop(old_callbacks);
+}
+
+// The signature of the emscripten functions that set callbacks on a target, for events described by `R`.
+type CallbackSetter<R> = unsafe extern "C" fn(
+    *const c_char,
+    *mut c_void,
+    bool,
+    Option<unsafe extern "C" fn(c_int, *const R, *mut c_void) -> bool>,
+    html5::pthread_t,
+) -> c_int;
+
+// Registers the given closure as the callback of the given target and event type,
+// with the raw emscripten event `R` turned into the rust-native event `E` before calling it.
+fn set_callback<R, E, F>(
+    target: EventTarget,
+    event_type: u32,
+    setter: CallbackSetter<R>,
+    callback: F,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    R: 'static,
+    E: 'static + for<'a> From<&'a R>,
+    F: 'static + FnMut(&E) -> bool,
+{
+    unsafe extern "C" fn trampoline<R, E>(
+        _event_type: c_int,
+        event: *const R,
+        user_data: *mut c_void,
+    ) -> bool
+    where
+        R: 'static,
+        E: 'static + for<'a> From<&'a R>,
+    {
+        let event = E::from(unsafe { &*event });
+        run_callback(user_data as usize, &event)
+    }
+
+    // Emscripten would keep calling the previous handler as well, so we remove it first.
+    unsafe {
+        setter(
+            target.as_ptr(),
+            null_mut(),
+            false,
+            None,
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        );
+    }
+
+    let id = store_callback::<E>(&target, event_type as c_int, Box::new(callback));
+    let result = EmscriptenError::from_result(unsafe {
+        setter(
+            target.as_ptr(),
+            id as *mut c_void,
+            false,
+            Some(trampoline::<R, E>),
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        )
+    });
+    if result.is_err() {
+        remove_callback_id(id);
+    }
+
+    result.map(|()| {
+        CallbackGuard::new(id, move || unsafe {
+            setter(
+                target.as_ptr(),
+                null_mut(),
+                false,
+                None,
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            );
+        })
+    })
+}
+
+// Unregisters the callback of the given target and event type, and drops its closure.
+fn remove_callback<R>(
+    target: EventTarget,
+    event_type: u32,
+    setter: CallbackSetter<R>,
+) -> Result<(), EmscriptenError> {
+    let result = EmscriptenError::from_result(unsafe {
+        setter(
+            target.as_ptr(),
+            null_mut(),
+            false,
+            None,
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        )
+    });
+    remove_target_callbacks(&target, event_type as c_int);
+
+    result
+}
+
+/// A mouse event, as given to the mouse event callbacks, such as the one set by [`set_mousemove_callback`].
+///
+/// It's converted from emscripten's [`EmscriptenMouseEvent`](html5::EmscriptenMouseEvent), with its `EM_BOOL` fields as `bool`s
+/// and its timestamp as an `f64`, like all the other events of this module.
+///
+/// # Examples
+/// ```rust
+/// let mut raw: EmscriptenMouseEvent = unsafe { std::mem::zeroed() };
+/// raw.timestamp = 1500.0;
+/// raw.clientX = 20;
+/// raw.shiftKey = true;
+///
+/// let event = MouseEvent::from(&raw);
+/// assert_eq!(event.timestamp, 1500.0);
+/// assert_eq!(event.client_x, 20);
+/// assert!(event.shift_key && !event.ctrl_key);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MouseEvent {
+    /// The time when the event happened, in milliseconds.
+    pub timestamp: f64,
+    /// The pointer's x coordinate, relative to the screen.
+    pub screen_x: c_int,
+    /// The pointer's y coordinate, relative to the screen.
+    pub screen_y: c_int,
+    /// The pointer's x coordinate, relative to the browser's viewport.
+    pub client_x: c_int,
+    /// The pointer's y coordinate, relative to the browser's viewport.
+    pub client_y: c_int,
+    /// Whether the Ctrl key was pressed.
+    pub ctrl_key: bool,
+    /// Whether the Shift key was pressed.
+    pub shift_key: bool,
+    /// Whether the Alt key was pressed.
+    pub alt_key: bool,
+    /// Whether the Meta key was pressed.
+    pub meta_key: bool,
+    /// The button that changed its state: 0 for the left button, 1 for the middle one, 2 for the right one.
+    pub button: u16,
+    /// A bitmask of the buttons held down: 1 for the left button, 2 for the right one, 4 for the middle one.
+    pub buttons: u16,
+    /// The pointer's x movement since the previous mouse event.
+    pub movement_x: c_int,
+    /// The pointer's y movement since the previous mouse event.
+    pub movement_y: c_int,
+    /// The pointer's x coordinate, relative to the element the callback is registered on.
+    pub target_x: c_int,
+    /// The pointer's y coordinate, relative to the element the callback is registered on.
+    pub target_y: c_int,
+}
+impl From<&html5::EmscriptenMouseEvent> for MouseEvent {
+    fn from(event: &html5::EmscriptenMouseEvent) -> Self {
+        MouseEvent {
+            timestamp: event.timestamp,
+            screen_x: event.screenX,
+            screen_y: event.screenY,
+            client_x: event.clientX,
+            client_y: event.clientY,
+            ctrl_key: event.ctrlKey,
+            shift_key: event.shiftKey,
+            alt_key: event.altKey,
+            meta_key: event.metaKey,
+            button: event.button,
+            buttons: event.buttons,
+            movement_x: event.movementX,
+            movement_y: event.movementY,
+            target_x: event.targetX,
+            target_y: event.targetY,
+        }
+    }
+}
+
+// The mouse callbacks can be either scoped to their element, or to the whole document when capturing.
+fn mouse_event_target<T>(target: T, capture: bool) -> EventTarget
+where
+    T: Into<Target>,
+{
+    if capture {
+        EventTarget::Document
+    } else {
+        EventTarget::from(target.into())
+    }
+}
+
+/// Sets the function to be called when a mouse button is pressed over the element matching the given target,
+/// using the emscripten-defined [`emscripten_set_mousedown_callback_on_thread`].
+/// It replaces the function previously set for the same target.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it.
+///
+/// [`emscripten_set_mousedown_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_mousedown_callback_on_thread
+///
+/// # Arguments
+/// * `target` - The target of the callback, e.g. [`DEFAULT_CANVAS_TARGET`] or [`Target::Window`].
+/// * `callback` - The function to call with the mouse event.
+///
+/// # Examples
+/// ```rust
+/// let _mousedown = set_mousedown_callback(DEFAULT_CANVAS_TARGET, |event| {
+///     println!("Button {} pressed at {},{}", event.button, event.target_x, event.target_y);
+///     true
+/// })?;
+/// ```
+pub fn set_mousedown_callback<T, F>(
+    target: T,
+    callback: F,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: Into<Target>,
+    F: 'static + FnMut(&MouseEvent) -> bool,
+{
+    set_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_MOUSEDOWN,
+        html5::emscripten_set_mousedown_callback_on_thread,
+        callback,
+    )
+}
+
+/// Removes the function set with [`set_mousedown_callback`] for the given target, which doesn't get called anymore.
+///
+/// # Arguments
+/// * `target` - The target of the callback.
+///
+/// # Examples
+/// ```rust
+/// remove_mousedown_callback(DEFAULT_CANVAS_TARGET)?;
+/// ```
+pub fn remove_mousedown_callback<T>(target: T) -> Result<(), EmscriptenError>
+where
+    T: Into<Target>,
+{
+    remove_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_MOUSEDOWN,
+        html5::emscripten_set_mousedown_callback_on_thread,
+    )
+}
+
+/// Sets the function to be called when a mouse button is released,
+/// using the emscripten-defined [`emscripten_set_mouseup_callback_on_thread`].
+/// It replaces the function previously set for the same target.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it.
+///
+/// With `capture` set to `false`, the function is called only for the releases happening over the element matching `target`.
+/// With `capture` set to `true`, the function is registered on the whole document instead,
+/// so that it gets called even if a drag that started over the element ends outside of it.
+///
+/// [`emscripten_set_mouseup_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_mouseup_callback_on_thread
+///
+/// # Arguments
+/// * `target` - The target of the callback, e.g. [`DEFAULT_CANVAS_TARGET`] or [`Target::Window`]. It's not used if `capture` is `true`.
+/// * `capture` - Whether to register the function on the whole document instead of `target`.
+/// * `callback` - The function to call with the mouse event.
+///
+/// # Examples
+/// ```rust
+/// let _mouseup = set_mouseup_callback(DEFAULT_CANVAS_TARGET, true, |_| {
+///     println!("The drag has ended, wherever the pointer is");
+///     true
+/// })?;
+/// ```
+pub fn set_mouseup_callback<T, F>(
+    target: T,
+    capture: bool,
+    callback: F,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: Into<Target>,
+    F: 'static + FnMut(&MouseEvent) -> bool,
+{
+    set_callback(
+        mouse_event_target(target, capture),
+        html5::EMSCRIPTEN_EVENT_MOUSEUP,
+        html5::emscripten_set_mouseup_callback_on_thread,
+        callback,
+    )
+}
+
+/// Removes the function set with [`set_mouseup_callback`] for the given target, which doesn't get called anymore.
+///
+/// # Arguments
+/// * `target` - The target of the callback. It's not used if `capture` is `true`.
+/// * `capture` - Whether the function was registered on the whole document instead of `target`.
+///
+/// # Examples
+/// ```rust
+/// remove_mouseup_callback(DEFAULT_CANVAS_TARGET, true)?;
+/// ```
+pub fn remove_mouseup_callback<T>(target: T, capture: bool) -> Result<(), EmscriptenError>
+where
+    T: Into<Target>,
+{
+    remove_callback(
+        mouse_event_target(target, capture),
+        html5::EMSCRIPTEN_EVENT_MOUSEUP,
+        html5::emscripten_set_mouseup_callback_on_thread,
+    )
+}
+
+/// Sets the function to be called when the mouse pointer moves,
+/// using the emscripten-defined [`emscripten_set_mousemove_callback_on_thread`].
+/// It replaces the function previously set for the same target.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it.
+///
+/// With `capture` set to `false`, the function is called only while the pointer is over the element matching `target`.
+/// With `capture` set to `true`, the function is registered on the whole document instead,
+/// so that the movement deltas keep flowing during a drag that leaves the element.
+/// In that case the `target_x` and `target_y` coordinates of the events equal `client_x` and `client_y`, relative to the viewport rather than to the element.
+///
+/// [`emscripten_set_mousemove_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_mousemove_callback_on_thread
+///
+/// # Arguments
+/// * `target` - The target of the callback, e.g. [`DEFAULT_CANVAS_TARGET`] or [`Target::Window`]. It's not used if `capture` is `true`.
+/// * `capture` - Whether to register the function on the whole document instead of `target`.
+/// * `callback` - The function to call with the mouse event.
+///
+/// # Examples
+/// ```rust
+/// let _mousemove = set_mousemove_callback(DEFAULT_CANVAS_TARGET, true, |event| {
+///     if event.buttons & 1 != 0 {
+///         println!("Dragged by {},{}", event.movement_x, event.movement_y);
+///     }
+///     true
+/// })?;
+/// ```
+pub fn set_mousemove_callback<T, F>(
+    target: T,
+    capture: bool,
+    callback: F,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: Into<Target>,
+    F: 'static + FnMut(&MouseEvent) -> bool,
+{
+    set_callback(
+        mouse_event_target(target, capture),
+        html5::EMSCRIPTEN_EVENT_MOUSEMOVE,
+        html5::emscripten_set_mousemove_callback_on_thread,
+        callback,
+    )
+}
+
+/// Removes the function set with [`set_mousemove_callback`] for the given target, which doesn't get called anymore.
+///
+/// # Arguments
+/// * `target` - The target of the callback. It's not used if `capture` is `true`.
+/// * `capture` - Whether the function was registered on the whole document instead of `target`.
+///
+/// # Examples
+/// ```rust
+/// remove_mousemove_callback(DEFAULT_CANVAS_TARGET, true)?;
+/// ```
+pub fn remove_mousemove_callback<T>(target: T, capture: bool) -> Result<(), EmscriptenError>
+where
+    T: Into<Target>,
+{
+    remove_callback(
+        mouse_event_target(target, capture),
+        html5::EMSCRIPTEN_EVENT_MOUSEMOVE,
+        html5::emscripten_set_mousemove_callback_on_thread,
+    )
+}
+
+// Turns the fixed-size string fields of the emscripten events into strings, up to their NUL terminator.
+fn string_from_chars(chars: &[c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// A keyboard event, as given to the keyboard event callbacks, such as the one set by [`set_keydown_callback`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyEvent {
+    /// The time when the event happened, in milliseconds.
+    pub timestamp: f64,
+    /// Where the key is on the keyboard: 0 for most keys, 1 for the left one of a pair (e.g. the left Shift),
+    /// 2 for the right one of a pair, 3 for the numeric keypad.
+    pub location: c_uint,
+    /// Whether the Ctrl key was pressed.
+    pub ctrl_key: bool,
+    /// Whether the Shift key was pressed.
+    pub shift_key: bool,
+    /// Whether the Alt key was pressed.
+    pub alt_key: bool,
+    /// Whether the Meta key was pressed.
+    pub meta_key: bool,
+    /// Whether the event comes from the key being held down.
+    pub repeat: bool,
+    /// The value of the key, taking the keyboard layout and the modifier keys into account, like `a`, `A` or `Enter`.
+    /// See [`KeyboardEvent.key`].
+    ///
+    /// [`KeyboardEvent.key`]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key
+    pub key: String,
+    /// The physical key, like `KeyA` or `Enter`. See [`KeyboardEvent.code`] and [`key_code`](KeyEvent::key_code).
+    ///
+    /// [`KeyboardEvent.code`]: https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code
+    pub code: String,
+    /// The character typed by the key, if any.
+    pub char_value: String,
+    /// The locale of the keyboard, if the browser tells it.
+    pub locale: String,
+}
+impl KeyEvent {
+    /// Returns the physical key of the event, parsed from its [`code`](KeyEvent::code).
+    ///
+    /// # Examples
+    /// ```rust
+    /// let _keydown = set_keydown_callback(DEFAULT_CANVAS_TARGET, |event| match event.key_code() {
+    ///     KeyCode::W | KeyCode::ArrowUp => {
+    ///         println!("Forward!");
+    ///         true
+    ///     }
+    ///     _ => false,
+    /// })?;
+    /// ```
+    pub fn key_code(&self) -> KeyCode {
+        KeyCode::from_code(&self.code)
+    }
+}
+impl From<&html5::EmscriptenKeyboardEvent> for KeyEvent {
+    fn from(event: &html5::EmscriptenKeyboardEvent) -> Self {
+        KeyEvent {
+            timestamp: event.timestamp,
+            location: event.location,
+            ctrl_key: event.ctrlKey,
+            shift_key: event.shiftKey,
+            alt_key: event.altKey,
+            meta_key: event.metaKey,
+            repeat: event.repeat,
+            key: string_from_chars(&event.key),
+            code: string_from_chars(&event.code),
+            char_value: string_from_chars(&event.charValue),
+            locale: string_from_chars(&event.locale),
+        }
+    }
+}
+
+/// A physical key of the keyboard, as given by a [`KeyEvent`]'s [`key_code`](KeyEvent::key_code).
+///
+/// The keys are named after their position on a US QWERTY keyboard, regardless of the user's layout,
+/// e.g. the key left of `S` is always [`KeyCode::A`], even on an AZERTY keyboard. This makes them fit for game controls like WASD.
+/// Use [`KeyEvent::key`] for the character the key types.
+///
+/// The variants are the values of the [`KeyboardEvent.code`] property, except for the letter keys, which drop their `Key` prefix.
+///
+/// [`KeyboardEvent.code`]: https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum KeyCode {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Space,
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    ShiftLeft,
+    ShiftRight,
+    ControlLeft,
+    ControlRight,
+    AltLeft,
+    AltRight,
+    MetaLeft,
+    MetaRight,
+    CapsLock,
+    Minus,
+    Equal,
+    BracketLeft,
+    BracketRight,
+    Backslash,
+    Semicolon,
+    Quote,
+    Backquote,
+    Comma,
+    Period,
+    Slash,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadEnter,
+    /// A key that isn't known by this crate, with its [`KeyboardEvent.code`] value.
+    ///
+    /// [`KeyboardEvent.code`]: https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values
+    Other(String),
+}
+impl KeyCode {
+    /// Returns the key with the given [`KeyboardEvent.code`] value, or [`KeyCode::Other`] if it isn't known by this crate.
+    ///
+    /// [`KeyboardEvent.code`]: https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values
+    ///
+    /// # Examples
+    /// ```rust
+    /// assert_eq!(KeyCode::from_code("KeyW"), KeyCode::W);
+    /// assert_eq!(KeyCode::from_code("Lang1"), KeyCode::Other("Lang1".to_string()));
+    /// ```
+    pub fn from_code<T>(code: T) -> Self
+    where
+        T: AsRef<str>,
+    {
+        match code.as_ref() {
+            "KeyA" => KeyCode::A,
+            "KeyB" => KeyCode::B,
+            "KeyC" => KeyCode::C,
+            "KeyD" => KeyCode::D,
+            "KeyE" => KeyCode::E,
+            "KeyF" => KeyCode::F,
+            "KeyG" => KeyCode::G,
+            "KeyH" => KeyCode::H,
+            "KeyI" => KeyCode::I,
+            "KeyJ" => KeyCode::J,
+            "KeyK" => KeyCode::K,
+            "KeyL" => KeyCode::L,
+            "KeyM" => KeyCode::M,
+            "KeyN" => KeyCode::N,
+            "KeyO" => KeyCode::O,
+            "KeyP" => KeyCode::P,
+            "KeyQ" => KeyCode::Q,
+            "KeyR" => KeyCode::R,
+            "KeyS" => KeyCode::S,
+            "KeyT" => KeyCode::T,
+            "KeyU" => KeyCode::U,
+            "KeyV" => KeyCode::V,
+            "KeyW" => KeyCode::W,
+            "KeyX" => KeyCode::X,
+            "KeyY" => KeyCode::Y,
+            "KeyZ" => KeyCode::Z,
+            "Digit0" => KeyCode::Digit0,
+            "Digit1" => KeyCode::Digit1,
+            "Digit2" => KeyCode::Digit2,
+            "Digit3" => KeyCode::Digit3,
+            "Digit4" => KeyCode::Digit4,
+            "Digit5" => KeyCode::Digit5,
+            "Digit6" => KeyCode::Digit6,
+            "Digit7" => KeyCode::Digit7,
+            "Digit8" => KeyCode::Digit8,
+            "Digit9" => KeyCode::Digit9,
+            "F1" => KeyCode::F1,
+            "F2" => KeyCode::F2,
+            "F3" => KeyCode::F3,
+            "F4" => KeyCode::F4,
+            "F5" => KeyCode::F5,
+            "F6" => KeyCode::F6,
+            "F7" => KeyCode::F7,
+            "F8" => KeyCode::F8,
+            "F9" => KeyCode::F9,
+            "F10" => KeyCode::F10,
+            "F11" => KeyCode::F11,
+            "F12" => KeyCode::F12,
+            "ArrowUp" => KeyCode::ArrowUp,
+            "ArrowDown" => KeyCode::ArrowDown,
+            "ArrowLeft" => KeyCode::ArrowLeft,
+            "ArrowRight" => KeyCode::ArrowRight,
+            "Space" => KeyCode::Space,
+            "Enter" => KeyCode::Enter,
+            "Escape" => KeyCode::Escape,
+            "Tab" => KeyCode::Tab,
+            "Backspace" => KeyCode::Backspace,
+            "Delete" => KeyCode::Delete,
+            "Insert" => KeyCode::Insert,
+            "Home" => KeyCode::Home,
+            "End" => KeyCode::End,
+            "PageUp" => KeyCode::PageUp,
+            "PageDown" => KeyCode::PageDown,
+            "ShiftLeft" => KeyCode::ShiftLeft,
+            "ShiftRight" => KeyCode::ShiftRight,
+            "ControlLeft" => KeyCode::ControlLeft,
+            "ControlRight" => KeyCode::ControlRight,
+            "AltLeft" => KeyCode::AltLeft,
+            "AltRight" => KeyCode::AltRight,
+            "MetaLeft" => KeyCode::MetaLeft,
+            "MetaRight" => KeyCode::MetaRight,
+            "CapsLock" => KeyCode::CapsLock,
+            "Minus" => KeyCode::Minus,
+            "Equal" => KeyCode::Equal,
+            "BracketLeft" => KeyCode::BracketLeft,
+            "BracketRight" => KeyCode::BracketRight,
+            "Backslash" => KeyCode::Backslash,
+            "Semicolon" => KeyCode::Semicolon,
+            "Quote" => KeyCode::Quote,
+            "Backquote" => KeyCode::Backquote,
+            "Comma" => KeyCode::Comma,
+            "Period" => KeyCode::Period,
+            "Slash" => KeyCode::Slash,
+            "Numpad0" => KeyCode::Numpad0,
+            "Numpad1" => KeyCode::Numpad1,
+            "Numpad2" => KeyCode::Numpad2,
+            "Numpad3" => KeyCode::Numpad3,
+            "Numpad4" => KeyCode::Numpad4,
+            "Numpad5" => KeyCode::Numpad5,
+            "Numpad6" => KeyCode::Numpad6,
+            "Numpad7" => KeyCode::Numpad7,
+            "Numpad8" => KeyCode::Numpad8,
+            "Numpad9" => KeyCode::Numpad9,
+            "NumpadAdd" => KeyCode::NumpadAdd,
+            "NumpadSubtract" => KeyCode::NumpadSubtract,
+            "NumpadMultiply" => KeyCode::NumpadMultiply,
+            "NumpadDivide" => KeyCode::NumpadDivide,
+            "NumpadDecimal" => KeyCode::NumpadDecimal,
+            "NumpadEnter" => KeyCode::NumpadEnter,
+            other => KeyCode::Other(other.to_string()),
+        }
+    }
+
+    /// Returns the [`KeyboardEvent.code`] value of the key.
+    ///
+    /// [`KeyboardEvent.code`]: https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values
+    pub fn code(&self) -> &str {
+        match self {
+            KeyCode::A => "KeyA",
+            KeyCode::B => "KeyB",
+            KeyCode::C => "KeyC",
+            KeyCode::D => "KeyD",
+            KeyCode::E => "KeyE",
+            KeyCode::F => "KeyF",
+            KeyCode::G => "KeyG",
+            KeyCode::H => "KeyH",
+            KeyCode::I => "KeyI",
+            KeyCode::J => "KeyJ",
+            KeyCode::K => "KeyK",
+            KeyCode::L => "KeyL",
+            KeyCode::M => "KeyM",
+            KeyCode::N => "KeyN",
+            KeyCode::O => "KeyO",
+            KeyCode::P => "KeyP",
+            KeyCode::Q => "KeyQ",
+            KeyCode::R => "KeyR",
+            KeyCode::S => "KeyS",
+            KeyCode::T => "KeyT",
+            KeyCode::U => "KeyU",
+            KeyCode::V => "KeyV",
+            KeyCode::W => "KeyW",
+            KeyCode::X => "KeyX",
+            KeyCode::Y => "KeyY",
+            KeyCode::Z => "KeyZ",
+            KeyCode::Digit0 => "Digit0",
+            KeyCode::Digit1 => "Digit1",
+            KeyCode::Digit2 => "Digit2",
+            KeyCode::Digit3 => "Digit3",
+            KeyCode::Digit4 => "Digit4",
+            KeyCode::Digit5 => "Digit5",
+            KeyCode::Digit6 => "Digit6",
+            KeyCode::Digit7 => "Digit7",
+            KeyCode::Digit8 => "Digit8",
+            KeyCode::Digit9 => "Digit9",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::ArrowUp => "ArrowUp",
+            KeyCode::ArrowDown => "ArrowDown",
+            KeyCode::ArrowLeft => "ArrowLeft",
+            KeyCode::ArrowRight => "ArrowRight",
+            KeyCode::Space => "Space",
+            KeyCode::Enter => "Enter",
+            KeyCode::Escape => "Escape",
+            KeyCode::Tab => "Tab",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::Delete => "Delete",
+            KeyCode::Insert => "Insert",
+            KeyCode::Home => "Home",
+            KeyCode::End => "End",
+            KeyCode::PageUp => "PageUp",
+            KeyCode::PageDown => "PageDown",
+            KeyCode::ShiftLeft => "ShiftLeft",
+            KeyCode::ShiftRight => "ShiftRight",
+            KeyCode::ControlLeft => "ControlLeft",
+            KeyCode::ControlRight => "ControlRight",
+            KeyCode::AltLeft => "AltLeft",
+            KeyCode::AltRight => "AltRight",
+            KeyCode::MetaLeft => "MetaLeft",
+            KeyCode::MetaRight => "MetaRight",
+            KeyCode::CapsLock => "CapsLock",
+            KeyCode::Minus => "Minus",
+            KeyCode::Equal => "Equal",
+            KeyCode::BracketLeft => "BracketLeft",
+            KeyCode::BracketRight => "BracketRight",
+            KeyCode::Backslash => "Backslash",
+            KeyCode::Semicolon => "Semicolon",
+            KeyCode::Quote => "Quote",
+            KeyCode::Backquote => "Backquote",
+            KeyCode::Comma => "Comma",
+            KeyCode::Period => "Period",
+            KeyCode::Slash => "Slash",
+            KeyCode::Numpad0 => "Numpad0",
+            KeyCode::Numpad1 => "Numpad1",
+            KeyCode::Numpad2 => "Numpad2",
+            KeyCode::Numpad3 => "Numpad3",
+            KeyCode::Numpad4 => "Numpad4",
+            KeyCode::Numpad5 => "Numpad5",
+            KeyCode::Numpad6 => "Numpad6",
+            KeyCode::Numpad7 => "Numpad7",
+            KeyCode::Numpad8 => "Numpad8",
+            KeyCode::Numpad9 => "Numpad9",
+            KeyCode::NumpadAdd => "NumpadAdd",
+            KeyCode::NumpadSubtract => "NumpadSubtract",
+            KeyCode::NumpadMultiply => "NumpadMultiply",
+            KeyCode::NumpadDivide => "NumpadDivide",
+            KeyCode::NumpadDecimal => "NumpadDecimal",
+            KeyCode::NumpadEnter => "NumpadEnter",
+            KeyCode::Other(code) => code,
+        }
+    }
+}
+impl Display for KeyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// Sets the function to be called when a key is pressed while the element matching the given target has the focus,
+/// using the emscripten-defined [`emscripten_set_keydown_callback_on_thread`].
+/// It replaces the function previously set for the same target.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it,
+/// e.g. scrolling the page on the arrow keys.
+///
+/// Elements like the canvas can get the focus only if they have a `tabindex` attribute.
+///
+/// [`emscripten_set_keydown_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_keydown_callback_on_thread
+///
+/// # Arguments
+/// * `target` - The target of the callback, e.g. [`DEFAULT_CANVAS_TARGET`] or [`Target::Window`].
+/// * `callback` - The function to call with the keyboard event.
+///
+/// # Examples
+/// ```rust
+/// let _keydown = set_keydown_callback(DEFAULT_CANVAS_TARGET, |event| {
+///     println!("{} pressed", event.key);
+///     true
+/// })?;
+/// ```
+pub fn set_keydown_callback<T, F>(target: T, callback: F) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: Into<Target>,
+    F: 'static + FnMut(&KeyEvent) -> bool,
+{
+    set_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_KEYDOWN,
+        html5::emscripten_set_keydown_callback_on_thread,
+        callback,
+    )
+}
+
+/// Removes the function set with [`set_keydown_callback`] for the given target, which doesn't get called anymore.
+///
+/// # Arguments
+/// * `target` - The target of the callback.
+///
+/// # Examples
+/// ```rust
+/// remove_keydown_callback(DEFAULT_CANVAS_TARGET)?;
+/// ```
+pub fn remove_keydown_callback<T>(target: T) -> Result<(), EmscriptenError>
+where
+    T: Into<Target>,
+{
+    remove_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_KEYDOWN,
+        html5::emscripten_set_keydown_callback_on_thread,
+    )
+}
+
+/// Sets the function to be called when a key is released while the element matching the given target has the focus,
+/// using the emscripten-defined [`emscripten_set_keyup_callback_on_thread`].
+/// It replaces the function previously set for the same target.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it.
+///
+/// Elements like the canvas can get the focus only if they have a `tabindex` attribute.
+///
+/// [`emscripten_set_keyup_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_keyup_callback_on_thread
+///
+/// # Arguments
+/// * `target` - The target of the callback, e.g. [`DEFAULT_CANVAS_TARGET`] or [`Target::Window`].
+/// * `callback` - The function to call with the keyboard event.
+///
+/// # Examples
+/// ```rust
+/// let _keyup = set_keyup_callback(DEFAULT_CANVAS_TARGET, |event| {
+///     println!("{} released", event.key);
+///     true
+/// })?;
+/// ```
+pub fn set_keyup_callback<T, F>(target: T, callback: F) -> Result<CallbackGuard, EmscriptenError>
+where
+    T: Into<Target>,
+    F: 'static + FnMut(&KeyEvent) -> bool,
+{
+    set_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_KEYUP,
+        html5::emscripten_set_keyup_callback_on_thread,
+        callback,
+    )
+}
+
+/// Removes the function set with [`set_keyup_callback`] for the given target, which doesn't get called anymore.
+///
+/// # Arguments
+/// * `target` - The target of the callback.
+///
+/// # Examples
+/// ```rust
+/// remove_keyup_callback(DEFAULT_CANVAS_TARGET)?;
+/// ```
+pub fn remove_keyup_callback<T>(target: T) -> Result<(), EmscriptenError>
+where
+    T: Into<Target>,
+{
+    remove_callback(
+        EventTarget::from(target.into()),
+        html5::EMSCRIPTEN_EVENT_KEYUP,
+        html5::emscripten_set_keyup_callback_on_thread,
+    )
+}
+
+// A blur event, of which `KeyboardState` only needs to know that it happened.
+struct BlurEvent;
+impl From<&html5::EmscriptenFocusEvent> for BlurEvent {
+    fn from(_event: &html5::EmscriptenFocusEvent) -> Self {
+        BlurEvent
+    }
+}
+
+// The keys tracked by a `KeyboardState`, shared with its event callbacks.
+#[derive(Default)]
+struct KeyboardStateKeys {
+    pressed: HashSet<KeyCode>,
+    just_pressed: HashSet<KeyCode>,
+}
+
+/// Tracks which keys are held down, for games that check the keys on each frame instead of reacting to keyboard events.
+///
+/// It listens for the keyboard events on the whole window, so it replaces the keyboard callbacks previously set on the window,
+/// and creating another `KeyboardState` takes them over.
+/// The events aren't consumed, so the browser still runs its default actions for them.
+///
+/// All keys are released when the window loses the focus, as the browser doesn't report the keys released while it's away.
+///
+/// # Examples
+/// ```rust
+/// let keyboard = KeyboardState::new()?;
+///
+/// set_main_loop(move || {
+///     if keyboard.is_pressed(&KeyCode::D) {
+///         println!("Walking right");
+///     }
+///     if keyboard.just_pressed(&KeyCode::Space) {
+///         println!("Jump!");
+///     }
+///     keyboard.new_frame();
+/// }, 0, true);
+/// ```
+pub struct KeyboardState {
+    keys: Rc<RefCell<KeyboardStateKeys>>,
+    // The keys stop being tracked once the state is dropped.
+    _callbacks: Vec<CallbackGuard>,
+}
+impl KeyboardState {
+    /// Starts tracking the keys, by setting keydown, keyup and blur callbacks on the window, which are removed when it's dropped.
+    pub fn new() -> Result<Self, EmscriptenError> {
+        let keys = Rc::new(RefCell::new(KeyboardStateKeys::default()));
+        let mut callbacks = Vec::new();
+
+        let keydown_keys = keys.clone();
+        callbacks.push(set_callback(
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_KEYDOWN,
+            html5::emscripten_set_keydown_callback_on_thread,
+            move |event: &KeyEvent| {
+                let mut keys = keydown_keys.borrow_mut();
+                let key_code = event.key_code();
+                if keys.pressed.insert(key_code.clone()) {
+                    keys.just_pressed.insert(key_code);
+                }
+                false
+            },
+        )?);
+
+        let keyup_keys = keys.clone();
+        callbacks.push(set_callback(
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_KEYUP,
+            html5::emscripten_set_keyup_callback_on_thread,
+            move |event: &KeyEvent| {
+                keyup_keys.borrow_mut().pressed.remove(&event.key_code());
+                false
+            },
+        )?);
+
+        let blur_keys = keys.clone();
+        callbacks.push(set_callback(
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_BLUR,
+            html5::emscripten_set_blur_callback_on_thread,
+            move |_: &BlurEvent| {
+                blur_keys.borrow_mut().pressed.clear();
+                false
+            },
+        )?);
+
+        Ok(KeyboardState {
+            keys,
+            _callbacks: callbacks,
+        })
+    }
+
+    /// Returns whether the given key is held down.
+    pub fn is_pressed(&self, key: &KeyCode) -> bool {
+        self.keys.borrow().pressed.contains(key)
+    }
+
+    /// Returns whether the given key got pressed since the last call of [`new_frame`](KeyboardState::new_frame).
+    /// The key repeats of a held key don't count.
+    pub fn just_pressed(&self, key: &KeyCode) -> bool {
+        self.keys.borrow().just_pressed.contains(key)
+    }
+
+    /// Forgets the keys that were just pressed, to be called at the end of each frame.
+    pub fn new_frame(&self) {
+        self.keys.borrow_mut().just_pressed.clear();
+    }
+}
+
+/// The visibility state of the page, as reported by [`document.visibilityState`].
+///
+/// [`document.visibilityState`]: https://developer.mozilla.org/en-US/docs/Web/API/Document/visibilityState
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisibilityState {
+    Hidden,
+    Visible,
+    Prerender,
+    Unloaded,
+    /// A state that isn't known by this crate.
+    Other(c_int),
+}
+
+/// A visibility change event, as given to the function set by [`set_visibilitychange_callback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisibilityChangeEvent {
+    /// Whether the page is hidden, e.g. because its tab is in the background or the browser is minimized.
+    pub hidden: bool,
+    /// The more detailed visibility state of the page.
+    pub visibility_state: VisibilityState,
+}
+impl From<&html5::EmscriptenVisibilityChangeEvent> for VisibilityChangeEvent {
+    fn from(event: &html5::EmscriptenVisibilityChangeEvent) -> Self {
+        VisibilityChangeEvent {
+            hidden: event.hidden,
+            visibility_state: match event.visibilityState as u32 {
+                html5::EMSCRIPTEN_VISIBILITY_HIDDEN => VisibilityState::Hidden,
+                html5::EMSCRIPTEN_VISIBILITY_VISIBLE => VisibilityState::Visible,
+                html5::EMSCRIPTEN_VISIBILITY_PRERENDER => VisibilityState::Prerender,
+                html5::EMSCRIPTEN_VISIBILITY_UNLOADED => VisibilityState::Unloaded,
+                _ => VisibilityState::Other(event.visibilityState),
+            },
+        }
+    }
+}
+
+/// Sets the function to be called when the page becomes hidden or visible,
+/// using the emscripten-defined [`emscripten_set_visibilitychange_callback_on_thread`].
+/// It replaces the function previously set.
+/// It's removed when the returned [`CallbackGuard`] gets dropped.
+///
+/// The function returns `true` if it consumed the event, so that the browser won't run its default action for it.
+///
+/// [`emscripten_set_visibilitychange_callback_on_thread`]: https://emscripten.org/docs/api_reference/html5.h.html#c.emscripten_set_visibilitychange_callback_on_thread
+///
+/// # Arguments
+/// * `callback` - The function to call with the visibility change event.
+///
+/// # Examples
+/// ```rust
+/// let _visibility = set_visibilitychange_callback(|event| {
+///     if event.hidden {
+///         println!("See you later");
+///     } else {
+///         println!("Welcome back");
+///     }
+///     true
+/// })?;
+/// ```
+pub fn set_visibilitychange_callback<F>(callback: F) -> Result<CallbackGuard, EmscriptenError>
+where
+    F: 'static + FnMut(&VisibilityChangeEvent) -> bool,
+{
+    unsafe extern "C" fn trampoline(
+        _event_type: c_int,
+        event: *const html5::EmscriptenVisibilityChangeEvent,
+        user_data: *mut c_void,
+    ) -> bool {
+        let event = VisibilityChangeEvent::from(unsafe { &*event });
+        run_callback(user_data as usize, &event)
+    }
+
+    // Emscripten would keep calling the previous handler as well, so we remove it first.
+    unsafe {
+        html5::emscripten_set_visibilitychange_callback_on_thread(
+            null_mut(),
+            false,
+            None,
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        );
+    }
+
+    // The visibility change events are always listened for on the document.
+    let id = store_callback::<VisibilityChangeEvent>(
+        &EventTarget::Document,
+        html5::EMSCRIPTEN_EVENT_VISIBILITYCHANGE as c_int,
+        Box::new(callback),
+    );
+    let result = EmscriptenError::from_result(unsafe {
+        html5::emscripten_set_visibilitychange_callback_on_thread(
+            id as *mut c_void,
+            false,
+            Some(trampoline),
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        )
+    });
+    if result.is_err() {
+        remove_callback_id(id);
+    }
+
+    result.map(|()| {
+        CallbackGuard::new(id, || unsafe {
+            html5::emscripten_set_visibilitychange_callback_on_thread(
+                null_mut(),
+                false,
+                None,
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            );
+        })
+    })
+}
+
+/// Removes the function set with [`set_visibilitychange_callback`], which doesn't get called anymore.
+///
+/// # Examples
+/// ```rust
+/// remove_visibilitychange_callback()?;
+/// ```
+pub fn remove_visibilitychange_callback() -> Result<(), EmscriptenError> {
+    let result = EmscriptenError::from_result(unsafe {
+        html5::emscripten_set_visibilitychange_callback_on_thread(
+            null_mut(),
+            false,
+            None,
+            CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+        )
+    });
+    remove_target_callbacks(
+        &EventTarget::Document,
+        html5::EMSCRIPTEN_EVENT_VISIBILITYCHANGE as c_int,
+    );
+
+    result
+}
+
+// The function set by `set_online_callback`.
+type OnlineCallback = Box<dyn FnMut(bool)>;
+
+// It's `None` while it runs, so that it can be replaced from inside itself.
+// The generation is bumped each time it's set or removed, so that it can tell whether that happened while it ran.
+thread_local! {
+    static ONLINE_CALLBACK: RefCell<Option<OnlineCallback>> = const { RefCell::new(None) };
+    static ONLINE_CALLBACK_GENERATION: Cell<u64> = const { Cell::new(0) };
+    static ONLINE_LISTENERS_SET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Replaces the function set by `set_online_callback`, returning the previous one.
+fn replace_online_callback(callback: Option<OnlineCallback>) -> Option<OnlineCallback> {
+    ONLINE_CALLBACK_GENERATION.with(|generation| generation.set(generation.get().wrapping_add(1)));
+    ONLINE_CALLBACK.with(|callback_ref| callback_ref.replace(callback))
+}
+
+// Called by the `online` and `offline` event listeners installed by `set_online_callback`.
+// Emscripten has no API for these events, so the listeners are plain JavaScript ones that call this exported function.
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn emscripten_functions_online_changed(online: c_int) {
+    let callback = ONLINE_CALLBACK.with(|callback_ref| callback_ref.borrow_mut().take());
+    let Some(mut callback) = callback else {
+        return;
+    };
+    let generation = ONLINE_CALLBACK_GENERATION.with(Cell::get);
+
+    callback(online != 0);
+
+    // The function goes back in its place, unless it got replaced or removed while it ran.
+    if ONLINE_CALLBACK_GENERATION.with(Cell::get) == generation {
+        ONLINE_CALLBACK.with(|callback_ref| *callback_ref.borrow_mut() = Some(callback));
+    }
+}
+
+/// Sets the function to be called when the browser goes online or offline, with the [`online`] and [`offline`] events.
+/// It replaces the function previously set.
+///
+/// The function is given whether the browser is now online.
+/// The current state can be checked with [`is_online`](crate::emscripten::is_online).
+///
+/// The events reach the function through an exported function,
+/// so `_emscripten_functions_online_changed` must be added to the `-sEXPORTED_FUNCTIONS` link argument.
+///
+/// [`online`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/online_event
+/// [`offline`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/offline_event
+///
+/// # Arguments
+/// * `callback` - The function to call when the connectivity changes.
+///
+/// # Examples
+/// ```rust
+/// set_online_callback(|online| {
+///     if online {
+///         println!("Back online, resuming matchmaking");
+///     } else {
+///         println!("Connection lost, pausing matchmaking");
+///     }
+/// });
+/// ```
+pub fn set_online_callback<F>(callback: F)
+where
+    F: 'static + FnMut(bool),
+{
+    drop(replace_online_callback(Some(Box::new(callback))));
+
+    if !ONLINE_LISTENERS_SET.with(|set| set.replace(true)) {
+        run_script(
+            r#"
+                window.addEventListener("online", function () {
+                    Module["_emscripten_functions_online_changed"](1);
+                });
+                window.addEventListener("offline", function () {
+                    Module["_emscripten_functions_online_changed"](0);
+                });
+            "#,
+        );
+    }
+}
+
+/// Removes the function set with [`set_online_callback`], which doesn't get called anymore.
+/// It can be called from inside that function too.
+///
+/// # Examples
+/// ```rust
+/// remove_online_callback();
+/// ```
+pub fn remove_online_callback() {
+    drop(replace_online_callback(None));
+}
+
+/// A point of contact of a touch event, as found in [`TouchEvent::touches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TouchPoint {
+    /// The identifier of the touch, which stays the same for as long as the finger touches the surface.
+    pub identifier: c_int,
+    /// The touch's x coordinate, relative to the screen.
+    pub screen_x: c_int,
+    /// The touch's y coordinate, relative to the screen.
+    pub screen_y: c_int,
+    /// The touch's x coordinate, relative to the browser's viewport.
+    pub client_x: c_int,
+    /// The touch's y coordinate, relative to the browser's viewport.
+    pub client_y: c_int,
+    /// The touch's x coordinate, relative to the page.
+    pub page_x: c_int,
+    /// The touch's y coordinate, relative to the page.
+    pub page_y: c_int,
+    /// Whether the touch changed in this event, e.g. it's the finger that just touched the surface in a touch start event.
+    pub is_changed: bool,
+    /// Whether the touch started on the element the callback is registered on.
+    pub on_target: bool,
+    /// The touch's x coordinate, relative to the element the callback is registered on.
+    pub target_x: c_int,
+    /// The touch's y coordinate, relative to the element the callback is registered on.
+    pub target_y: c_int,
+}
+impl From<&html5::EmscriptenTouchPoint> for TouchPoint {
+    fn from(point: &html5::EmscriptenTouchPoint) -> Self {
+        TouchPoint {
+            identifier: point.identifier,
+            screen_x: point.screenX,
+            screen_y: point.screenY,
+            client_x: point.clientX,
+            client_y: point.clientY,
+            page_x: point.pageX,
+            page_y: point.pageY,
+            is_changed: point.isChanged,
+            on_target: point.onTarget,
+            target_x: point.targetX,
+            target_y: point.targetY,
+        }
+    }
+}
+
+/// A touch event, as found in an [`InputEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchEvent {
+    /// The time when the event happened, in milliseconds.
+    pub timestamp: f64,
+    /// Whether the Ctrl key was pressed.
+    pub ctrl_key: bool,
+    /// Whether the Shift key was pressed.
+    pub shift_key: bool,
+    /// Whether the Alt key was pressed.
+    pub alt_key: bool,
+    /// Whether the Meta key was pressed.
+    pub meta_key: bool,
+    /// All the points touching the surface, including the ones that changed in this event.
+    pub touches: Vec<TouchPoint>,
+}
+impl From<&html5::EmscriptenTouchEvent> for TouchEvent {
+    fn from(event: &html5::EmscriptenTouchEvent) -> Self {
+        let num_touches = (event.numTouches.max(0) as usize).min(event.touches.len());
+
+        TouchEvent {
+            timestamp: event.timestamp,
+            ctrl_key: event.ctrlKey,
+            shift_key: event.shiftKey,
+            alt_key: event.altKey,
+            meta_key: event.metaKey,
+            touches: event.touches[..num_touches]
+                .iter()
+                .map(TouchPoint::from)
+                .collect(),
+        }
+    }
+}
+
+/// A UI event, like the resize of the window, as found in an [`InputEvent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UiEvent {
+    /// The width of the page's body.
+    pub document_body_client_width: c_int,
+    /// The height of the page's body.
+    pub document_body_client_height: c_int,
+    /// The width of the browser's viewport.
+    pub window_inner_width: c_int,
+    /// The height of the browser's viewport.
+    pub window_inner_height: c_int,
+    /// The width of the whole browser window.
+    pub window_outer_width: c_int,
+    /// The height of the whole browser window.
+    pub window_outer_height: c_int,
+    /// How much the page is scrolled down.
+    pub scroll_top: c_int,
+    /// How much the page is scrolled to the right.
+    pub scroll_left: c_int,
+}
+impl From<&html5::EmscriptenUiEvent> for UiEvent {
+    fn from(event: &html5::EmscriptenUiEvent) -> Self {
+        UiEvent {
+            document_body_client_width: event.documentBodyClientWidth,
+            document_body_client_height: event.documentBodyClientHeight,
+            window_inner_width: event.windowInnerWidth,
+            window_inner_height: event.windowInnerHeight,
+            window_outer_width: event.windowOuterWidth,
+            window_outer_height: event.windowOuterHeight,
+            scroll_top: event.scrollTop,
+            scroll_left: event.scrollLeft,
+        }
+    }
+}
+
+/// An input event collected by an [`EventQueue`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    MouseDown(MouseEvent),
+    MouseUp(MouseEvent),
+    MouseMove(MouseEvent),
+    KeyDown(KeyEvent),
+    KeyUp(KeyEvent),
+    TouchStart(TouchEvent),
+    TouchMove(TouchEvent),
+    TouchEnd(TouchEvent),
+    TouchCancel(TouchEvent),
+    /// The browser window got resized.
+    Resize(UiEvent),
+}
+
+// Registers a callback that pushes its events, turned into `InputEvent`s, to the given queue.
+fn set_queue_callback<R, E>(
+    events: &Rc<RefCell<VecDeque<InputEvent>>>,
+    target: EventTarget,
+    event_type: u32,
+    setter: CallbackSetter<R>,
+    to_input_event: fn(E) -> InputEvent,
+) -> Result<CallbackGuard, EmscriptenError>
+where
+    R: 'static,
+    E: 'static + Clone + for<'a> From<&'a R>,
+{
+    let events = events.clone();
+    set_callback(target, event_type, setter, move |event: &E| {
+        events.borrow_mut().push_back(to_input_event(event.clone()));
+        false
+    })
+}
+
+/// Collects the mouse, keyboard, touch and resize events into a queue, to be drained at the start of each frame,
+/// much like SDL's event pump.
+///
+/// The mouse and touch events are listened for on the element matching the given target,
+/// and the keyboard and resize events on the whole window.
+/// It replaces the callbacks previously set for those events and targets, like the ones of a [`KeyboardState`],
+/// and it removes its callbacks when it's dropped.
+/// The events aren't consumed, so the browser still runs its default actions for them.
+///
+/// # Examples
+/// ```rust
+/// let events = EventQueue::new(DEFAULT_CANVAS_TARGET)?;
+///
+/// set_main_loop(move || {
+///     while let Some(event) = events.poll() {
+///         match event {
+///             InputEvent::MouseDown(event) => println!("Click at {},{}", event.target_x, event.target_y),
+///             InputEvent::KeyDown(event) => println!("{} pressed", event.key),
+///             _ => {}
+///         }
+///     }
+/// }, 0, true);
+/// ```
+pub struct EventQueue {
+    events: Rc<RefCell<VecDeque<InputEvent>>>,
+    // The events stop being collected once the queue is dropped.
+    _callbacks: Vec<CallbackGuard>,
+}
+impl EventQueue {
+    /// Starts collecting the events, by setting their callbacks.
+    ///
+    /// # Arguments
+    /// * `target` - The target to listen for mouse and touch events on, e.g. [`DEFAULT_CANVAS_TARGET`].
+    pub fn new<T>(target: T) -> Result<Self, EmscriptenError>
+    where
+        T: Into<Target>,
+    {
+        let events = Rc::new(RefCell::new(VecDeque::new()));
+        let mut callbacks = Vec::new();
+        let target = EventTarget::from(target.into());
+
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_MOUSEDOWN,
+            html5::emscripten_set_mousedown_callback_on_thread,
+            InputEvent::MouseDown,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_MOUSEUP,
+            html5::emscripten_set_mouseup_callback_on_thread,
+            InputEvent::MouseUp,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_MOUSEMOVE,
+            html5::emscripten_set_mousemove_callback_on_thread,
+            InputEvent::MouseMove,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_KEYDOWN,
+            html5::emscripten_set_keydown_callback_on_thread,
+            InputEvent::KeyDown,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_KEYUP,
+            html5::emscripten_set_keyup_callback_on_thread,
+            InputEvent::KeyUp,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_TOUCHSTART,
+            html5::emscripten_set_touchstart_callback_on_thread,
+            InputEvent::TouchStart,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_TOUCHMOVE,
+            html5::emscripten_set_touchmove_callback_on_thread,
+            InputEvent::TouchMove,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target.clone(),
+            html5::EMSCRIPTEN_EVENT_TOUCHEND,
+            html5::emscripten_set_touchend_callback_on_thread,
+            InputEvent::TouchEnd,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            target,
+            html5::EMSCRIPTEN_EVENT_TOUCHCANCEL,
+            html5::emscripten_set_touchcancel_callback_on_thread,
+            InputEvent::TouchCancel,
+        )?);
+        callbacks.push(set_queue_callback(
+            &events,
+            EventTarget::Window,
+            html5::EMSCRIPTEN_EVENT_RESIZE,
+            html5::emscripten_set_resize_callback_on_thread,
+            InputEvent::Resize,
+        )?);
+
+        Ok(EventQueue {
+            events,
+            _callbacks: callbacks,
+        })
+    }
+
+    /// Returns the oldest event that hasn't been polled yet, if any.
+    pub fn poll(&self) -> Option<InputEvent> {
+        self.events.borrow_mut().pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts the calls of online callbacks, to be set without installing the JavaScript listeners.
+    fn set_counting_online_callback<F>(calls: &Rc<Cell<usize>>, then: F)
+    where
+        F: 'static + Fn(),
+    {
+        ONLINE_LISTENERS_SET.with(|set| set.set(true));
+        let calls = calls.clone();
+        set_online_callback(move |_| {
+            calls.set(calls.get() + 1);
+            then();
+        });
+    }
+
+    #[test]
+    fn online_callback_stays_after_being_called() {
+        let calls = Rc::new(Cell::new(0));
+        set_counting_online_callback(&calls, || {});
+
+        emscripten_functions_online_changed(0);
+        emscripten_functions_online_changed(1);
+        assert_eq!(calls.get(), 2);
+
+        remove_online_callback();
+        emscripten_functions_online_changed(0);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn online_callback_removed_from_inside_itself_stays_removed() {
+        let calls = Rc::new(Cell::new(0));
+        set_counting_online_callback(&calls, remove_online_callback);
+
+        emscripten_functions_online_changed(0);
+        emscripten_functions_online_changed(1);
+        assert_eq!(calls.get(), 1);
+        assert!(ONLINE_CALLBACK.with(|callback_ref| callback_ref.borrow().is_none()));
+    }
+
+    #[test]
+    fn online_callback_replaced_from_inside_itself_is_replaced() {
+        let calls = Rc::new(Cell::new(0));
+        let new_calls = Rc::new(Cell::new(0));
+        let new_calls_ref = new_calls.clone();
+        set_counting_online_callback(&calls, move || {
+            set_counting_online_callback(&new_calls_ref, || {})
+        });
+
+        emscripten_functions_online_changed(0);
+        emscripten_functions_online_changed(1);
+        assert_eq!((calls.get(), new_calls.get()), (1, 1));
+    }
+
+    #[test]
+    fn removed_target_callback_no_longer_runs() {
+        let event_type = html5::EMSCRIPTEN_EVENT_MOUSEDOWN as c_int;
+        let calls = Rc::new(Cell::new(0));
+        let calls_ref = calls.clone();
+        let callback: Box<dyn FnMut(&MouseEvent) -> bool> = Box::new(move |_| {
+            calls_ref.set(calls_ref.get() + 1);
+            true
+        });
+        let id = store_callback(&EventTarget::Window, event_type, callback);
+
+        remove_target_callbacks(&EventTarget::Window, event_type);
+        let event = MouseEvent::from(unsafe { &std::mem::zeroed::<html5::EmscriptenMouseEvent>() });
+        assert!(!run_callback(id, &event));
+        assert_eq!(calls.get(), 0);
+    }
+
+    #[test]
+    fn mouse_event_keeps_the_bools_and_the_timestamp() {
+        let mut raw: html5::EmscriptenMouseEvent = unsafe { std::mem::zeroed() };
+        raw.timestamp = 1500.25;
+        raw.clientX = 20;
+        raw.clientY = -3;
+        raw.shiftKey = true;
+        raw.metaKey = true;
+        raw.buttons = 5;
+
+        let event = MouseEvent::from(&raw);
+        assert_eq!(event.timestamp, 1500.25);
+        assert_eq!((event.client_x, event.client_y), (20, -3));
+        assert!(event.shift_key && event.meta_key);
+        assert!(!event.ctrl_key && !event.alt_key);
+        assert_eq!(event.buttons, 5);
+    }
+
+    #[test]
+    fn key_event_strings_stop_at_the_nul_terminator() {
+        let mut raw: html5::EmscriptenKeyboardEvent = unsafe { std::mem::zeroed() };
+        raw.timestamp = 42.0;
+        raw.repeat = true;
+        for (dest, src) in raw.code.iter_mut().zip(b"KeyW\0garbage") {
+            *dest = *src as c_char;
+        }
+
+        let event = KeyEvent::from(&raw);
+        assert_eq!(event.timestamp, 42.0);
+        assert!(event.repeat && !event.ctrl_key);
+        assert_eq!(event.code, "KeyW");
+        assert_eq!(event.key_code(), KeyCode::W);
+        assert_eq!(event.key, "");
+    }
+}
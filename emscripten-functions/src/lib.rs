@@ -4,4 +4,10 @@
 #![cfg(target_os = "emscripten")]
 
 pub mod console;
+#[cfg(feature = "em_math")]
+pub mod em_math;
 pub mod emscripten;
+pub mod fetch;
+pub mod html5;
+pub mod prelude;
+pub mod websocket;
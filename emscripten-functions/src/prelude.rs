@@ -0,0 +1,26 @@
+//! The most commonly used items of this crate, for importing them all at once.
+//!
+//! # Examples
+//! ```rust
+//! use emscripten_functions::prelude::*;
+//!
+//! set_main_loop(|| {
+//!     console_log!("Frame at {} ms", get_now());
+//! }, 0, true);
+//! ```
+
+pub use crate::{console_dbg, console_error, console_log, console_trace, console_warn};
+
+pub use crate::console::{dbg, dbg_backtrace, err, error, log, out, warn};
+
+pub use crate::emscripten::{
+    cancel_main_loop, cancel_main_loop_and_take, get_now, get_now_seconds, run_script,
+    run_script_as, run_script_int, run_script_main_thread, run_script_main_thread_double,
+    run_script_main_thread_int, run_script_string, set_main_loop, set_main_loop_forever,
+    set_main_loop_with_arg, set_main_loop_with_control, FromScriptResult, MainLoop,
+};
+
+pub use crate::html5::{
+    CallbackGuard, KeyCode, KeyEvent, MouseEvent, Target, TouchEvent, TouchPoint, UiEvent,
+    VisibilityChangeEvent, VisibilityState,
+};
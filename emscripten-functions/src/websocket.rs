@@ -0,0 +1,1041 @@
+//! Select functions (with rust-native parameter and return value types) from the emscripten [`websocket.h`] header file, and helper functions and types for them.
+//!
+//! Using them requires linking with `-lwebsocket.js`.
+//!
+//! [`websocket.h`]: https://github.com/emscripten-core/emscripten/blob/main/system/include/emscripten/websocket.h
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    ffi::{CStr, CString},
+    marker::PhantomData,
+    os::raw::{c_char, c_int, c_ushort, c_void},
+    ptr::{null, null_mut},
+    rc::{Rc, Weak},
+};
+
+use emscripten_functions_sys::websocket;
+
+use crate::emscripten::{set_timeout, to_cstring_lossy, EmscriptenError};
+
+// Makes emscripten run the callbacks on the thread that registers them.
+const CALLBACK_THREAD_CONTEXT_CALLING_THREAD: websocket::pthread_t = 2 as websocket::pthread_t;
+
+/// A message received by a [`WebSocket`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebSocketMessage {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+/// The event given to the function set by [`WebSocket::on_close`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebSocketCloseEvent {
+    /// Whether the connection was closed cleanly, with a closing handshake.
+    pub was_clean: bool,
+    /// The close code sent by the server, or set by the browser, like 1000 for a normal closure or 1006 for a dropped connection.
+    pub code: u16,
+    /// The reason of the closure sent by the server, if any.
+    pub reason: String,
+}
+impl From<&websocket::EmscriptenWebSocketCloseEvent> for WebSocketCloseEvent {
+    fn from(event: &websocket::EmscriptenWebSocketCloseEvent) -> Self {
+        WebSocketCloseEvent {
+            was_clean: event.wasClean,
+            code: event.code,
+            reason: unsafe { CStr::from_ptr(event.reason.as_ptr()) }
+                .to_string_lossy()
+                .into_owned(),
+        }
+    }
+}
+
+/// The state of the connection of a [`WebSocket`], as reported by [`WebSocket.readyState`].
+///
+/// [`WebSocket.readyState`]: https://developer.mozilla.org/en-US/docs/Web/API/WebSocket/readyState
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebSocketReadyState {
+    /// The connection isn't open yet.
+    Connecting,
+    /// The connection is open and ready to communicate.
+    Open,
+    /// The connection is being closed.
+    Closing,
+    /// The connection is closed or couldn't be opened.
+    Closed,
+}
+
+type OpenCallback = Box<dyn FnMut()>;
+type MessageCallback = Box<dyn FnMut(WebSocketMessage)>;
+// The functions set by `on_message` are turned into these too, as there can only be one of them.
+type RawMessageCallback = Box<dyn FnMut(&[u8], bool)>;
+type CloseCallback = Box<dyn FnMut(&WebSocketCloseEvent)>;
+
+// The functions set on a websocket. Each one is `None` while it runs,
+// so that it can be replaced, or the websocket dropped, from inside itself.
+#[derive(Default)]
+struct WebSocketCallbacks {
+    on_open: Option<OpenCallback>,
+    on_message: Option<RawMessageCallback>,
+    on_error: Option<OpenCallback>,
+    on_close: Option<CloseCallback>,
+}
+
+// The functions set on the websockets, keyed by their emscripten handle, which is also given back in every event.
+thread_local! {
+    static CALLBACKS: RefCell<HashMap<c_int, WebSocketCallbacks>> = RefCell::new(HashMap::new());
+}
+
+// Stores the given function in the given slot of the websocket's functions, dropping the previous one.
+fn store_callback<C>(
+    socket: c_int,
+    slot: fn(&mut WebSocketCallbacks) -> &mut Option<C>,
+    callback: C,
+) {
+    let old_callback = CALLBACKS.with(|callbacks_ref| {
+        slot(callbacks_ref.borrow_mut().entry(socket).or_default()).replace(callback)
+    });
+    // The old function gets dropped outside of the borrow, in case its destructor touches the registry.
+    drop(old_callback);
+}
+
+// Runs the function in the given slot of the websocket's functions, if any.
+fn run_callback<C, F>(socket: c_int, slot: fn(&mut WebSocketCallbacks) -> &mut Option<C>, call: F)
+where
+    F: FnOnce(&mut C),
+{
+    let callback = CALLBACKS.with(|callbacks_ref| {
+        callbacks_ref
+            .borrow_mut()
+            .get_mut(&socket)
+            .and_then(|callbacks| slot(callbacks).take())
+    });
+    let Some(mut callback) = callback else {
+        return;
+    };
+
+    call(&mut callback);
+
+    // The function goes back in its place, unless it got replaced or the websocket got dropped while it ran.
+    CALLBACKS.with(|callbacks_ref| {
+        if let Some(callbacks) = callbacks_ref.borrow_mut().get_mut(&socket) {
+            let stored = slot(callbacks);
+            if stored.is_none() {
+                *stored = Some(callback);
+            }
+        }
+    });
+}
+
+unsafe extern "C" fn onopen_wrapper(
+    _event_type: c_int,
+    event: *const websocket::EmscriptenWebSocketOpenEvent,
+    _user_data: *mut c_void,
+) -> bool {
+    let socket = unsafe { (*event).socket };
+    run_callback(
+        socket,
+        |callbacks| &mut callbacks.on_open,
+        |callback| callback(),
+    );
+    true
+}
+
+unsafe extern "C" fn onmessage_wrapper(
+    _event_type: c_int,
+    event: *const websocket::EmscriptenWebSocketMessageEvent,
+    _user_data: *mut c_void,
+) -> bool {
+    let event = unsafe { &*event };
+
+    // The data is freed by emscripten once we return, so the slice mustn't outlive the call of the function.
+    let data = if event.data.is_null() || event.numBytes == 0 {
+        &[][..]
+    } else {
+        unsafe { std::slice::from_raw_parts(event.data, event.numBytes as usize) }
+    };
+    // The length of a text message counts its NUL terminator.
+    let data = if event.isText {
+        data.strip_suffix(&[0]).unwrap_or(data)
+    } else {
+        data
+    };
+
+    run_callback(
+        event.socket,
+        |callbacks| &mut callbacks.on_message,
+        |callback| callback(data, event.isText),
+    );
+    true
+}
+
+unsafe extern "C" fn onerror_wrapper(
+    _event_type: c_int,
+    event: *const websocket::EmscriptenWebSocketErrorEvent,
+    _user_data: *mut c_void,
+) -> bool {
+    let socket = unsafe { (*event).socket };
+    run_callback(
+        socket,
+        |callbacks| &mut callbacks.on_error,
+        |callback| callback(),
+    );
+    true
+}
+
+unsafe extern "C" fn onclose_wrapper(
+    _event_type: c_int,
+    event: *const websocket::EmscriptenWebSocketCloseEvent,
+    _user_data: *mut c_void,
+) -> bool {
+    let event = unsafe { &*event };
+    let close_event = WebSocketCloseEvent::from(event);
+    run_callback(
+        event.socket,
+        |callbacks| &mut callbacks.on_close,
+        |callback| callback(&close_event),
+    );
+    true
+}
+
+/// Whether a message given to [`WebSocket::send_text_throttled`] or [`WebSocket::send_binary_throttled`] was sent right away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendStatus {
+    /// The message was handed to the browser.
+    Sent,
+    /// The message was queued, to be sent once the browser's buffer drains below the high-water mark.
+    Queued,
+}
+
+// A message waiting in a send queue, ready to be handed to emscripten.
+enum QueuedMessage {
+    Text(CString),
+    Binary(Vec<u8>),
+}
+
+// The messages of a websocket waiting for its buffered amount to drop, and the high-water mark given with the latest one.
+struct SendQueue {
+    messages: VecDeque<QueuedMessage>,
+    high_water_mark: usize,
+}
+
+// The send queues of the websockets with messages waiting, keyed by their emscripten handle.
+// A queue is flushed by timeouts for as long as it has messages, and it's removed once it's empty.
+thread_local! {
+    static SEND_QUEUES: RefCell<HashMap<c_int, SendQueue>> = RefCell::new(HashMap::new());
+}
+
+// The interval between the attempts to flush a send queue.
+const SEND_QUEUE_FLUSH_INTERVAL_MS: f64 = 50.0;
+
+fn ready_state(socket: c_int) -> Result<WebSocketReadyState, EmscriptenError> {
+    let mut ready_state: c_ushort = 0;
+    EmscriptenError::from_result(unsafe {
+        websocket::emscripten_websocket_get_ready_state(socket, &mut ready_state)
+    })?;
+
+    Ok(match ready_state {
+        0 => WebSocketReadyState::Connecting,
+        1 => WebSocketReadyState::Open,
+        2 => WebSocketReadyState::Closing,
+        _ => WebSocketReadyState::Closed,
+    })
+}
+
+fn buffered_amount(socket: c_int) -> Result<usize, EmscriptenError> {
+    let mut buffered_amount: usize = 0;
+    EmscriptenError::from_result(unsafe {
+        websocket::emscripten_websocket_get_buffered_amount(socket, &mut buffered_amount)
+    })?;
+
+    Ok(buffered_amount)
+}
+
+fn send_message(socket: c_int, message: &QueuedMessage) -> Result<(), EmscriptenError> {
+    EmscriptenError::from_result(match message {
+        QueuedMessage::Text(text) => unsafe {
+            websocket::emscripten_websocket_send_utf8_text(socket, text.as_ptr())
+        },
+        // Emscripten only reads the buffer, despite the `*mut` pointer.
+        QueuedMessage::Binary(data) => unsafe {
+            websocket::emscripten_websocket_send_binary(
+                socket,
+                data.as_ptr() as *mut c_void,
+                data.len() as u32,
+            )
+        },
+    })
+}
+
+// Returns whether a message can be handed to the browser now, or `None` if the connection is closing or closed.
+fn can_send_now(socket: c_int, high_water_mark: usize) -> Option<bool> {
+    match ready_state(socket).ok()? {
+        WebSocketReadyState::Connecting => Some(false),
+        WebSocketReadyState::Open => Some(buffered_amount(socket).ok()? <= high_water_mark),
+        WebSocketReadyState::Closing | WebSocketReadyState::Closed => None,
+    }
+}
+
+// Sends the queued messages of the websocket while its buffered amount stays below the high-water mark,
+// then tries again later if there are messages left.
+fn flush_send_queue(socket: c_int) {
+    loop {
+        let high_water_mark = SEND_QUEUES.with(|queues_ref| {
+            queues_ref
+                .borrow()
+                .get(&socket)
+                .map(|queue| queue.high_water_mark)
+        });
+        // The websocket got dropped.
+        let Some(high_water_mark) = high_water_mark else {
+            return;
+        };
+
+        match can_send_now(socket, high_water_mark) {
+            Some(true) => {}
+            Some(false) => break,
+            None => {
+                // The messages can't be sent anymore.
+                let queue = SEND_QUEUES.with(|queues_ref| queues_ref.borrow_mut().remove(&socket));
+                drop(queue);
+                return;
+            }
+        }
+
+        let message = SEND_QUEUES.with(|queues_ref| {
+            let mut queues = queues_ref.borrow_mut();
+            let message = queues.get_mut(&socket)?.messages.pop_front();
+            if queues[&socket].messages.is_empty() {
+                queues.remove(&socket);
+            }
+            message
+        });
+        let Some(message) = message else {
+            return;
+        };
+        if send_message(socket, &message).is_err() {
+            let queue = SEND_QUEUES.with(|queues_ref| queues_ref.borrow_mut().remove(&socket));
+            drop(queue);
+            return;
+        }
+    }
+
+    set_timeout(SEND_QUEUE_FLUSH_INTERVAL_MS, move || {
+        flush_send_queue(socket)
+    });
+}
+
+// Sends the message right away if nothing is queued before it and the browser's buffer is below the high-water mark,
+// otherwise queues it.
+fn send_throttled(
+    socket: c_int,
+    message: QueuedMessage,
+    high_water_mark: usize,
+) -> Result<SendStatus, EmscriptenError> {
+    let has_queue = SEND_QUEUES.with(|queues_ref| queues_ref.borrow().contains_key(&socket));
+    // A closed connection gets the message right away, for the usual error to be reported.
+    if !has_queue && can_send_now(socket, high_water_mark) != Some(false) {
+        send_message(socket, &message)?;
+        return Ok(SendStatus::Sent);
+    }
+
+    SEND_QUEUES.with(|queues_ref| {
+        let mut queues = queues_ref.borrow_mut();
+        let queue = queues.entry(socket).or_insert_with(|| SendQueue {
+            messages: VecDeque::new(),
+            high_water_mark,
+        });
+        queue.messages.push_back(message);
+        queue.high_water_mark = high_water_mark;
+    });
+    if !has_queue {
+        set_timeout(SEND_QUEUE_FLUSH_INTERVAL_MS, move || {
+            flush_send_queue(socket)
+        });
+    }
+
+    Ok(SendStatus::Queued)
+}
+
+/// A WebSocket connection, created with [`WebSocket::new`].
+///
+/// Dropping it closes the connection, if it's still open, and drops the functions set on it.
+/// The functions are kept by the thread that created the socket, so it can't be sent to another thread.
+///
+/// # Examples
+/// ```rust
+/// let socket = WebSocket::new("wss://example.com/game")?;
+/// socket.on_message(|message| {
+///     if let WebSocketMessage::Text(text) = message {
+///         println!("Server says: {}", text);
+///     }
+/// })?;
+/// ```
+#[derive(Debug)]
+pub struct WebSocket {
+    socket: c_int,
+    // The callbacks live in thread-local registries, which dropping the socket on another thread would leak.
+    _not_send: PhantomData<*const ()>,
+}
+impl WebSocket {
+    /// Returns whether the browser supports WebSockets, using the emscripten-defined `emscripten_websocket_is_supported`.
+    pub fn is_supported() -> bool {
+        unsafe { websocket::emscripten_websocket_is_supported() }
+    }
+
+    /// Starts connecting to the given URL, using the emscripten-defined `emscripten_websocket_new`.
+    ///
+    /// It returns [`EmscriptenError::InvalidParam`] if the URL contains a NUL character.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to connect to, with the `ws://` or `wss://` scheme.
+    pub fn new<T>(url: T) -> Result<Self, EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        Self::with_protocols::<T, &str>(url, &[])
+    }
+
+    /// Starts connecting to the given URL, asking the server to use one of the given subprotocols,
+    /// using the emscripten-defined `emscripten_websocket_new`.
+    ///
+    /// It returns [`EmscriptenError::InvalidParam`] if the URL or a subprotocol contains a NUL character.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to connect to, with the `ws://` or `wss://` scheme.
+    /// * `protocols` - The names of the subprotocols, in order of preference.
+    pub fn with_protocols<T, U>(url: T, protocols: &[U]) -> Result<Self, EmscriptenError>
+    where
+        T: AsRef<str>,
+        U: AsRef<str>,
+    {
+        // Removing the NUL characters would connect somewhere else, so they're rejected instead.
+        let url = CString::new(url.as_ref()).map_err(|_| EmscriptenError::InvalidParam)?;
+        let protocols = if protocols.is_empty() {
+            None
+        } else {
+            let protocols: Vec<&str> = protocols.iter().map(|protocol| protocol.as_ref()).collect();
+            Some(CString::new(protocols.join(",")).map_err(|_| EmscriptenError::InvalidParam)?)
+        };
+
+        let mut attributes = websocket::EmscriptenWebSocketCreateAttributes {
+            url: url.as_ptr(),
+            protocols: protocols
+                .as_ref()
+                .map_or(null(), |protocols| protocols.as_ptr()),
+            createOnMainThread: false,
+        };
+        let socket = unsafe { websocket::emscripten_websocket_new(&mut attributes) };
+        if socket <= 0 {
+            return Err(EmscriptenError::from_result(socket)
+                .err()
+                .unwrap_or(EmscriptenError::Failed));
+        }
+
+        Ok(WebSocket {
+            socket,
+            _not_send: PhantomData,
+        })
+    }
+
+    /// Sets the function to be called when the connection opens, using the emscripten-defined `emscripten_websocket_set_onopen_callback_on_thread`.
+    /// It replaces the function previously set.
+    pub fn on_open<F>(&self, callback: F) -> Result<(), EmscriptenError>
+    where
+        F: 'static + FnMut(),
+    {
+        store_callback::<OpenCallback>(
+            self.socket,
+            |callbacks| &mut callbacks.on_open,
+            Box::new(callback),
+        );
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_set_onopen_callback_on_thread(
+                self.socket,
+                null_mut(),
+                Some(onopen_wrapper),
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            )
+        })
+    }
+
+    /// Sets the function to be called with every message received, using the emscripten-defined `emscripten_websocket_set_onmessage_callback_on_thread`.
+    /// It replaces the function previously set.
+    ///
+    /// The data of the message is copied out of emscripten's buffer. Check out [`on_message_raw`](WebSocket::on_message_raw) to avoid that.
+    pub fn on_message<F>(&self, mut callback: F) -> Result<(), EmscriptenError>
+    where
+        F: 'static + FnMut(WebSocketMessage),
+    {
+        self.on_message_raw(move |data, is_text| {
+            let message = if is_text {
+                WebSocketMessage::Text(String::from_utf8_lossy(data).into_owned())
+            } else {
+                WebSocketMessage::Binary(data.to_vec())
+            };
+            callback(message);
+        })
+    }
+
+    /// Sets the function to be called with every message received, like [`on_message`](WebSocket::on_message),
+    /// but with the data of the message borrowed from emscripten's buffer instead of copied out, along with whether it's a text message.
+    /// It replaces the function previously set with either of them.
+    ///
+    /// This saves an allocation per message, which adds up with high-frequency binary protocols.
+    ///
+    /// **The data is freed by emscripten once the function returns**, so the borrow checker keeps the slice from escaping the function.
+    /// Copy what needs to be kept, e.g. with [`to_vec`](slice::to_vec).
+    /// Text messages are given without their NUL terminator, and they may not be valid UTF-8.
+    ///
+    /// # Arguments
+    /// * `callback` - The function to call with the data of the message and whether it's a text message.
+    ///
+    /// # Examples
+    /// ```rust
+    /// socket.on_message_raw(|data, is_text| {
+    ///     if !is_text && data.len() >= 4 {
+    ///         let tick = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+    ///         println!("Server tick {}", tick);
+    ///     }
+    /// })?;
+    /// ```
+    pub fn on_message_raw<F>(&self, callback: F) -> Result<(), EmscriptenError>
+    where
+        F: 'static + FnMut(&[u8], bool),
+    {
+        store_callback::<RawMessageCallback>(
+            self.socket,
+            |callbacks| &mut callbacks.on_message,
+            Box::new(callback),
+        );
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_set_onmessage_callback_on_thread(
+                self.socket,
+                null_mut(),
+                Some(onmessage_wrapper),
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            )
+        })
+    }
+
+    /// Sets the function to be called when the connection fails, using the emscripten-defined `emscripten_websocket_set_onerror_callback_on_thread`.
+    /// It replaces the function previously set.
+    ///
+    /// The browser doesn't tell the reason of the failure, and the connection gets closed right after.
+    pub fn on_error<F>(&self, callback: F) -> Result<(), EmscriptenError>
+    where
+        F: 'static + FnMut(),
+    {
+        store_callback::<OpenCallback>(
+            self.socket,
+            |callbacks| &mut callbacks.on_error,
+            Box::new(callback),
+        );
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_set_onerror_callback_on_thread(
+                self.socket,
+                null_mut(),
+                Some(onerror_wrapper),
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            )
+        })
+    }
+
+    /// Sets the function to be called when the connection closes, using the emscripten-defined `emscripten_websocket_set_onclose_callback_on_thread`.
+    /// It replaces the function previously set.
+    pub fn on_close<F>(&self, callback: F) -> Result<(), EmscriptenError>
+    where
+        F: 'static + FnMut(&WebSocketCloseEvent),
+    {
+        store_callback::<CloseCallback>(
+            self.socket,
+            |callbacks| &mut callbacks.on_close,
+            Box::new(callback),
+        );
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_set_onclose_callback_on_thread(
+                self.socket,
+                null_mut(),
+                Some(onclose_wrapper),
+                CALLBACK_THREAD_CONTEXT_CALLING_THREAD,
+            )
+        })
+    }
+
+    /// Sends the given text message, using the emscripten-defined `emscripten_websocket_send_utf8_text`.
+    ///
+    /// # Arguments
+    /// * `text` - The message to send. Its NUL characters are removed, as emscripten takes it as a C string.
+    pub fn send_text<T>(&self, text: T) -> Result<(), EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        let text = to_cstring_lossy(text.as_ref());
+
+        send_message(self.socket, &QueuedMessage::Text(text))
+    }
+
+    /// Sends the given binary message, using the emscripten-defined `emscripten_websocket_send_binary`.
+    ///
+    /// # Arguments
+    /// * `data` - The message to send.
+    pub fn send_binary(&self, data: &[u8]) -> Result<(), EmscriptenError> {
+        // Emscripten only reads the buffer, despite the `*mut` pointer.
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_send_binary(
+                self.socket,
+                data.as_ptr() as *mut c_void,
+                data.len() as u32,
+            )
+        })
+    }
+
+    /// Sends the given text message like [`send_text`](WebSocket::send_text), unless the browser already buffers
+    /// more than `high_water_mark` bytes of sent messages, in which case the message is queued.
+    ///
+    /// The queued messages are sent in order, as soon as the buffered amount drops to the high-water mark,
+    /// which is checked every 50 ms. Messages sent while some are queued are queued after them,
+    /// and so are the ones sent while the connection is still opening.
+    /// If the connection closes, or the websocket gets dropped, the queued messages are dropped.
+    ///
+    /// This keeps a stream of messages over a slow link from piling up in the browser.
+    /// The number of queued messages is given by [`queued_messages`](WebSocket::queued_messages).
+    ///
+    /// # Arguments
+    /// * `text` - The message to send. Its NUL characters are removed, like with [`send_text`](WebSocket::send_text).
+    /// * `high_water_mark` - The number of buffered bytes above which the message is queued.
+    ///
+    /// # Examples
+    /// ```rust
+    /// if socket.send_text_throttled(telemetry_json, 64 * 1024)? == SendStatus::Queued {
+    ///     println!("The link is slow, {} messages are waiting", socket.queued_messages());
+    /// }
+    /// ```
+    pub fn send_text_throttled<T>(
+        &self,
+        text: T,
+        high_water_mark: usize,
+    ) -> Result<SendStatus, EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        let text = to_cstring_lossy(text.as_ref());
+
+        send_throttled(self.socket, QueuedMessage::Text(text), high_water_mark)
+    }
+
+    /// Sends the given binary message like [`send_binary`](WebSocket::send_binary), unless the browser already buffers
+    /// more than `high_water_mark` bytes of sent messages, in which case the message is queued,
+    /// like with [`send_text_throttled`](WebSocket::send_text_throttled).
+    ///
+    /// # Arguments
+    /// * `data` - The message to send. It's copied if it gets queued.
+    /// * `high_water_mark` - The number of buffered bytes above which the message is queued.
+    pub fn send_binary_throttled(
+        &self,
+        data: &[u8],
+        high_water_mark: usize,
+    ) -> Result<SendStatus, EmscriptenError> {
+        send_throttled(
+            self.socket,
+            QueuedMessage::Binary(data.to_vec()),
+            high_water_mark,
+        )
+    }
+
+    /// Returns the number of messages queued by [`send_text_throttled`](WebSocket::send_text_throttled)
+    /// and [`send_binary_throttled`](WebSocket::send_binary_throttled), waiting to be sent.
+    pub fn queued_messages(&self) -> usize {
+        SEND_QUEUES.with(|queues_ref| {
+            queues_ref
+                .borrow()
+                .get(&self.socket)
+                .map_or(0, |queue| queue.messages.len())
+        })
+    }
+
+    /// Starts closing the connection, using the emscripten-defined `emscripten_websocket_close`.
+    /// The function set by [`on_close`](WebSocket::on_close) is called once it's closed.
+    ///
+    /// # Arguments
+    /// * `code` - The close code to send to the server: 1000 for a normal closure, or one in the 3000-4999 range.
+    ///   Browsers throw an exception for any other code.
+    /// * `reason` - The reason of the closure to send to the server, at most 123 bytes long.
+    ///   Its NUL characters are removed.
+    pub fn close<T>(&self, code: u16, reason: T) -> Result<(), EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        let reason = to_cstring_lossy(reason.as_ref());
+
+        EmscriptenError::from_result(unsafe {
+            websocket::emscripten_websocket_close(self.socket, code, reason.as_ptr())
+        })
+    }
+
+    /// Returns the state of the connection, using the emscripten-defined `emscripten_websocket_get_ready_state`.
+    pub fn ready_state(&self) -> Result<WebSocketReadyState, EmscriptenError> {
+        ready_state(self.socket)
+    }
+
+    /// Returns the number of bytes of the sent messages that are still queued, not yet transmitted over the network,
+    /// using the emscripten-defined `emscripten_websocket_get_buffered_amount`.
+    pub fn buffered_amount(&self) -> Result<usize, EmscriptenError> {
+        buffered_amount(self.socket)
+    }
+}
+impl Drop for WebSocket {
+    fn drop(&mut self) {
+        if matches!(
+            self.ready_state(),
+            Ok(WebSocketReadyState::Connecting | WebSocketReadyState::Open)
+        ) {
+            // With no code nor reason, emscripten closes the connection with the browser's defaults.
+            unsafe { websocket::emscripten_websocket_close(self.socket, 0, null::<c_char>()) };
+        }
+        unsafe { websocket::emscripten_websocket_delete(self.socket) };
+
+        let callbacks =
+            CALLBACKS.with(|callbacks_ref| callbacks_ref.borrow_mut().remove(&self.socket));
+        drop(callbacks);
+        let queue = SEND_QUEUES.with(|queues_ref| queues_ref.borrow_mut().remove(&self.socket));
+        drop(queue);
+    }
+}
+
+type ReconnectCallback = Box<dyn FnMut(u32)>;
+
+// The functions set on an `AutoReconnectWebSocket`, given to each of its successive websockets.
+#[derive(Default)]
+struct ReconnectHandlers {
+    on_open: RefCell<Option<OpenCallback>>,
+    on_message: RefCell<Option<MessageCallback>>,
+    on_error: RefCell<Option<OpenCallback>>,
+    on_close: RefCell<Option<CloseCallback>>,
+    on_reconnect: RefCell<Option<ReconnectCallback>>,
+}
+
+// Runs the function in the given slot, if any. Like for `WebSocket`, it's taken out while it runs.
+fn run_handler<C, F>(slot: &RefCell<Option<C>>, call: F)
+where
+    F: FnOnce(&mut C),
+{
+    let Some(mut callback) = slot.borrow_mut().take() else {
+        return;
+    };
+
+    call(&mut callback);
+
+    let mut stored = slot.borrow_mut();
+    if stored.is_none() {
+        *stored = Some(callback);
+    }
+}
+
+struct ReconnectState {
+    url: String,
+    socket: Option<WebSocket>,
+    max_retries: u32,
+    base_delay_ms: u32,
+    // The number of reconnection attempts since the connection was last open.
+    retries: u32,
+    // Whether `close` was called, after which there are no more reconnections.
+    closed: bool,
+}
+
+/// A [`WebSocket`] that reconnects by itself when the connection drops, created with [`AutoReconnectWebSocket::new`].
+///
+/// When the connection closes without [`close`](AutoReconnectWebSocket::close) being called,
+/// it's opened again after a delay that doubles with every failed attempt, starting at [`base_delay_ms`](AutoReconnectWebSocket::base_delay_ms).
+/// The functions set on it are kept for the new connections.
+/// Once [`max_retries`](AutoReconnectWebSocket::max_retries) attempts in a row fail, it gives up and calls the function set by
+/// [`on_close`](AutoReconnectWebSocket::on_close), which otherwise is called only after an explicit `close`.
+///
+/// Messages sent while it's reconnecting are lost.
+///
+/// Dropping it closes the connection and stops the reconnection attempts.
+///
+/// # Examples
+/// ```rust
+/// let socket = AutoReconnectWebSocket::new("wss://example.com/game")?
+///     .max_retries(10)
+///     .base_delay_ms(250);
+/// socket.on_open(|| println!("Connected"));
+/// socket.on_reconnect(|attempts| println!("Reconnected after {} attempts", attempts));
+/// socket.on_close(|event| println!("Gave up: {}", event.code));
+/// ```
+pub struct AutoReconnectWebSocket {
+    state: Rc<RefCell<ReconnectState>>,
+    handlers: Rc<ReconnectHandlers>,
+}
+impl AutoReconnectWebSocket {
+    /// Starts connecting to the given URL, with at most 5 reconnection attempts in a row, the first one after 500 milliseconds.
+    ///
+    /// # Arguments
+    /// * `url` - The URL to connect to, with the `ws://` or `wss://` scheme.
+    pub fn new<T>(url: T) -> Result<Self, EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        let state = Rc::new(RefCell::new(ReconnectState {
+            url: url.as_ref().to_string(),
+            socket: None,
+            max_retries: 5,
+            base_delay_ms: 500,
+            retries: 0,
+            closed: false,
+        }));
+        let handlers = Rc::new(ReconnectHandlers::default());
+
+        connect(&state, &handlers)?;
+        Ok(AutoReconnectWebSocket { state, handlers })
+    }
+
+    /// Sets the number of reconnection attempts in a row after which it gives up.
+    pub fn max_retries(self, max_retries: u32) -> Self {
+        self.state.borrow_mut().max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first reconnection attempt, in milliseconds. It doubles with every failed attempt.
+    pub fn base_delay_ms(self, base_delay_ms: u32) -> Self {
+        self.state.borrow_mut().base_delay_ms = base_delay_ms;
+        self
+    }
+
+    /// Sets the function to be called every time the connection opens, including after reconnecting.
+    /// It replaces the function previously set.
+    pub fn on_open<F>(&self, callback: F)
+    where
+        F: 'static + FnMut(),
+    {
+        *self.handlers.on_open.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the function to be called with every message received.
+    /// It replaces the function previously set.
+    pub fn on_message<F>(&self, callback: F)
+    where
+        F: 'static + FnMut(WebSocketMessage),
+    {
+        *self.handlers.on_message.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the function to be called when the connection fails.
+    /// It replaces the function previously set.
+    pub fn on_error<F>(&self, callback: F)
+    where
+        F: 'static + FnMut(),
+    {
+        *self.handlers.on_error.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the function to be called when the connection closes for good: either after [`close`](AutoReconnectWebSocket::close),
+    /// or when the reconnection attempts run out, with the event of the last closure.
+    /// It replaces the function previously set.
+    pub fn on_close<F>(&self, callback: F)
+    where
+        F: 'static + FnMut(&WebSocketCloseEvent),
+    {
+        *self.handlers.on_close.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sets the function to be called when the connection opens again after dropping,
+    /// with the number of attempts it took, before the function set by [`on_open`](AutoReconnectWebSocket::on_open).
+    /// It replaces the function previously set.
+    pub fn on_reconnect<F>(&self, callback: F)
+    where
+        F: 'static + FnMut(u32),
+    {
+        *self.handlers.on_reconnect.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Sends the given text message on the current connection, like [`WebSocket::send_text`].
+    pub fn send_text<T>(&self, text: T) -> Result<(), EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        match &self.state.borrow().socket {
+            Some(socket) => socket.send_text(text),
+            None => Err(EmscriptenError::InvalidTarget),
+        }
+    }
+
+    /// Sends the given binary message on the current connection, like [`WebSocket::send_binary`].
+    pub fn send_binary(&self, data: &[u8]) -> Result<(), EmscriptenError> {
+        match &self.state.borrow().socket {
+            Some(socket) => socket.send_binary(data),
+            None => Err(EmscriptenError::InvalidTarget),
+        }
+    }
+
+    /// Closes the connection for good, like [`WebSocket::close`], stopping the reconnection attempts.
+    pub fn close<T>(&self, code: u16, reason: T) -> Result<(), EmscriptenError>
+    where
+        T: AsRef<str>,
+    {
+        let mut state = self.state.borrow_mut();
+        state.closed = true;
+        match &state.socket {
+            Some(socket) => socket.close(code, reason),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the state of the current connection, like [`WebSocket::ready_state`].
+    /// It's [`WebSocketReadyState::Closed`] while waiting to reconnect.
+    pub fn ready_state(&self) -> Result<WebSocketReadyState, EmscriptenError> {
+        match &self.state.borrow().socket {
+            Some(socket) => socket.ready_state(),
+            None => Ok(WebSocketReadyState::Closed),
+        }
+    }
+
+    /// Returns the number of bytes queued on the current connection, like [`WebSocket::buffered_amount`].
+    pub fn buffered_amount(&self) -> Result<usize, EmscriptenError> {
+        match &self.state.borrow().socket {
+            Some(socket) => socket.buffered_amount(),
+            None => Ok(0),
+        }
+    }
+}
+
+// Opens a new connection for an `AutoReconnectWebSocket`, replacing the previous one.
+// The functions set on the websocket only hold weak references to the state, so that dropping the `AutoReconnectWebSocket` frees everything.
+fn connect(
+    state: &Rc<RefCell<ReconnectState>>,
+    handlers: &Rc<ReconnectHandlers>,
+) -> Result<(), EmscriptenError> {
+    let url = state.borrow().url.clone();
+    let socket = WebSocket::new(url)?;
+
+    let weak_state = Rc::downgrade(state);
+    let open_handlers = handlers.clone();
+    socket.on_open(move || {
+        if let Some(state) = weak_state.upgrade() {
+            let attempts = std::mem::take(&mut state.borrow_mut().retries);
+            if attempts > 0 {
+                run_handler(&open_handlers.on_reconnect, |callback| callback(attempts));
+            }
+        }
+        run_handler(&open_handlers.on_open, |callback| callback());
+    })?;
+
+    let message_handlers = handlers.clone();
+    socket.on_message(move |message| {
+        run_handler(&message_handlers.on_message, |callback| callback(message));
+    })?;
+
+    let error_handlers = handlers.clone();
+    socket.on_error(move || {
+        run_handler(&error_handlers.on_error, |callback| callback());
+    })?;
+
+    let weak_state = Rc::downgrade(state);
+    let close_handlers = handlers.clone();
+    socket.on_close(move |event| {
+        let Some(state) = weak_state.upgrade() else {
+            return;
+        };
+        let closed = state.borrow().closed;
+        if closed || !schedule_reconnect(&state, &close_handlers, event) {
+            run_handler(&close_handlers.on_close, |callback| callback(event));
+        }
+    })?;
+
+    // The previous websocket, if any, is already closed.
+    let old_socket = state.borrow_mut().socket.replace(socket);
+    drop(old_socket);
+
+    Ok(())
+}
+
+// Schedules the next reconnection attempt, returning `false` if there are no attempts left.
+fn schedule_reconnect(
+    state: &Rc<RefCell<ReconnectState>>,
+    handlers: &Rc<ReconnectHandlers>,
+    event: &WebSocketCloseEvent,
+) -> bool {
+    let delay_ms = {
+        let mut state = state.borrow_mut();
+        if state.retries >= state.max_retries {
+            return false;
+        }
+        let delay_ms = state
+            .base_delay_ms
+            .saturating_mul(1 << state.retries.min(16));
+        state.retries += 1;
+        delay_ms
+    };
+
+    let weak_state: Weak<RefCell<ReconnectState>> = Rc::downgrade(state);
+    let handlers = handlers.clone();
+    let event = event.clone();
+    set_timeout(delay_ms as f64, move || {
+        let Some(state) = weak_state.upgrade() else {
+            return;
+        };
+        if state.borrow().closed {
+            return;
+        }
+        // Creating a websocket only fails when the browser doesn't support them, which retrying won't change.
+        if connect(&state, &handlers).is_err() {
+            run_handler(&handlers.on_close, |callback| callback(&event));
+        }
+    });
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_with_nul_is_rejected() {
+        assert_eq!(
+            WebSocket::new("wss://example.com/\0chat").unwrap_err(),
+            EmscriptenError::InvalidParam
+        );
+        assert_eq!(
+            WebSocket::with_protocols("wss://example.com/chat", &["chat\0v2"]).unwrap_err(),
+            EmscriptenError::InvalidParam
+        );
+    }
+
+    #[test]
+    fn throttled_text_loses_its_nul_characters() {
+        // With messages already queued, the new one is queued after them, without asking emscripten anything.
+        let socket = WebSocket {
+            socket: 42,
+            _not_send: PhantomData,
+        };
+        SEND_QUEUES.with(|queues_ref| {
+            queues_ref.borrow_mut().insert(
+                socket.socket,
+                SendQueue {
+                    messages: VecDeque::from([QueuedMessage::Binary(vec![1, 2, 3])]),
+                    high_water_mark: 16,
+                },
+            )
+        });
+
+        assert_eq!(
+            socket.send_text_throttled("po\0ng", 16),
+            Ok(SendStatus::Queued)
+        );
+        assert_eq!(socket.queued_messages(), 2);
+        SEND_QUEUES.with(|queues_ref| {
+            let queues = queues_ref.borrow();
+            match queues[&socket.socket].messages.back() {
+                Some(QueuedMessage::Text(text)) => assert_eq!(text.as_bytes(), b"pong"),
+                _ => panic!("the text message wasn't queued last"),
+            }
+        });
+
+        // Dropping it would close a socket that emscripten never opened.
+        std::mem::forget(socket);
+    }
+}
@@ -0,0 +1,7 @@
+fn main() {
+    if std::env::var("CARGO_CFG_TARGET_OS").unwrap() == "emscripten" {
+        // The worker program is loaded by the main one, and only runs the exported functions it's called with.
+        println!("cargo:rustc-link-arg-bin=worker=-sBUILD_AS_WORKER");
+        println!("cargo:rustc-link-arg-bin=worker=-sEXPORTED_FUNCTIONS=_main,_find_path");
+    }
+}
@@ -0,0 +1,6 @@
+use emscripten_functions::worker_handler;
+
+// Called by the main program with `TypedWorker::call("find_path", ...)`.
+worker_handler!(find_path, pathfinding_worker::find_path);
+
+fn main() {}
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// A cell of the maze, as its (column, row) coordinates.
+pub type Cell = (u32, u32);
+
+/// The request sent to the worker's `find_path` function.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PathRequest {
+    /// The rows of the maze, where `#` is a wall and anything else is free.
+    pub maze: Vec<String>,
+    pub from: Cell,
+    pub to: Cell,
+}
+
+/// The response of the worker's `find_path` function: the cells from `from` to `to`, if there is a path between them.
+pub type PathResponse = Option<Vec<Cell>>;
+
+/// Finds one of the shortest paths through the maze, with a breadth-first search.
+pub fn find_path(request: PathRequest) -> PathResponse {
+    let grid: Vec<&[u8]> = request.maze.iter().map(|row| row.as_bytes()).collect();
+    let is_free = |(x, y): Cell| {
+        grid.get(y as usize)
+            .and_then(|row| row.get(x as usize))
+            .is_some_and(|&cell| cell != b'#')
+    };
+    if !is_free(request.from) || !is_free(request.to) {
+        return None;
+    }
+
+    // The cell each visited cell was reached from, to walk the path back once the target is found.
+    let mut came_from = vec![vec![None; request.maze.iter().map(String::len).max()?]; grid.len()];
+    let mut queue = VecDeque::from([request.from]);
+    came_from[request.from.1 as usize][request.from.0 as usize] = Some(request.from);
+
+    while let Some(cell @ (x, y)) = queue.pop_front() {
+        if cell == request.to {
+            let mut path = vec![cell];
+            while *path.last()? != request.from {
+                let (x, y) = *path.last()?;
+                path.push(came_from[y as usize][x as usize]?);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for next @ (next_x, next_y) in neighbours {
+            if is_free(next) && came_from[next_y as usize][next_x as usize].is_none() {
+                came_from[next_y as usize][next_x as usize] = Some(cell);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
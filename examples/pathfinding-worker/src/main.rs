@@ -0,0 +1,45 @@
+use std::cell::RefCell;
+
+use emscripten_functions::{console, emscripten::worker::TypedWorker};
+use pathfinding_worker::{PathRequest, PathResponse};
+
+const MAZE: [&str; 7] = [
+    "S..#......",
+    ".#.#.####.",
+    ".#...#....",
+    ".####.#.#.",
+    "......#.#.",
+    "#.#####.#.",
+    "........#E",
+];
+
+thread_local! {
+    // The worker is destroyed when dropped, so it's kept around after `main` returns, to get its response.
+    static PATHFINDER: RefCell<Option<TypedWorker<PathRequest, PathResponse>>> = const { RefCell::new(None) };
+}
+
+fn main() {
+    let worker: TypedWorker<PathRequest, PathResponse> = TypedWorker::create("worker.js");
+    let request = PathRequest {
+        maze: MAZE.iter().map(|row| row.to_string()).collect(),
+        from: (0, 0),
+        to: (9, 6),
+    };
+
+    let sent = worker.call("find_path", &request, |response| match response {
+        Ok(Some(path)) => console::log(format!(
+            "Found a path of {} steps: {:?}",
+            path.len() - 1,
+            path
+        )),
+        Ok(None) => console::warn("There is no path through the maze"),
+        Err(err) => console::error(format!("The worker failed: {}", err)),
+    });
+    if let Err(err) = sent {
+        console::error(format!("Couldn't send the maze to the worker: {}", err));
+    }
+
+    // The main thread is free to do other things while the worker looks for the path.
+    console::log("Looking for a path...");
+    PATHFINDER.with(|pathfinder| *pathfinder.borrow_mut() = Some(worker));
+}
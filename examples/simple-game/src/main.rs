@@ -12,7 +12,7 @@ use sdl2::{
 };
 
 #[cfg(target_os = "emscripten")]
-use emscripten_functions::emscripten::set_main_loop_with_arg;
+use emscripten_functions::emscripten::set_main_loop_forever;
 
 /// A container of all the variables needed for the game to run
 struct App {
@@ -148,8 +148,6 @@ fn main() -> Result<(), String> {
     }
     #[cfg(target_os = "emscripten")]
     {
-        set_main_loop_with_arg(main_loop_iteration, app, 0, true);
+        set_main_loop_forever(main_loop_iteration, app, 0);
     }
-
-    Ok(())
 }